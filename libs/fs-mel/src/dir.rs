@@ -122,3 +122,55 @@ pub async fn scan(recursive: bool, follow_links: bool) {
             .await
     }
 }
+
+/// Remove directory.
+///
+/// Remove the directory specified in `path`, with removal rules depending on parameter:
+/// - `recursive`: when `true`, the directory and all its contents are removed; when `false`, only an empty directory
+/// can be removed, and errors are emitted if it contains anything.
+///
+/// If removal error happens, `failure` is emitted and `error` contains text of the related text of error(s).
+#[mel_treatment(
+    default recursive false
+    input path Block<string>
+    input filesystem Block<FileSystem>
+    output success Block<void>
+    output failure Block<void>
+    output error Block<string>
+)]
+pub async fn remove(recursive: bool) {
+    if let (Ok(filesystem), Ok(path)) = (
+        filesystem.recv_one().await.map(|val| {
+            GetData::<Arc<dyn Data>>::try_data(val)
+                .unwrap()
+                .downcast_arc::<FileSystem>()
+                .unwrap()
+        }),
+        path.recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+    ) {
+        filesystem
+            .filesystem
+            .remove_dir(
+                &path,
+                recursive,
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = success.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = failure.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|msg: String| {
+                    Box::pin(async {
+                        let _ = error.send_one(msg.into()).await;
+                    })
+                }),
+            )
+            .await
+    }
+}
@@ -162,3 +162,351 @@ pub async fn write(append: bool, create: bool, new: bool) {
             .await
     }
 }
+
+/// Remove one file.
+///
+/// The file located at `path` is removed.
+///
+/// `success` is emitted when file is removed. If removal error happens, `failure` is emitted and `error` contains the related text of error.
+#[mel_treatment(
+    input path Block<string>
+    input filesystem Block<FileSystem>
+    output success Block<void>
+    output failure Block<void>
+    output error Block<string>
+)]
+pub async fn remove() {
+    if let (Ok(filesystem), Ok(path)) = (
+        filesystem.recv_one().await.map(|val| {
+            GetData::<Arc<dyn Data>>::try_data(val)
+                .unwrap()
+                .downcast_arc::<FileSystem>()
+                .unwrap()
+        }),
+        path.recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+    ) {
+        filesystem
+            .filesystem
+            .remove_file(
+                &path,
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = success.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = failure.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|msg: String| {
+                    Box::pin(async {
+                        let _ = error.send_one(msg.into()).await;
+                    })
+                }),
+            )
+            .await
+    }
+}
+
+/// Rename one file or directory.
+///
+/// The entry located at `from` is moved to `to`, replacing `to` if it is an existing file.
+///
+/// `success` is emitted when entry is renamed. If renaming error happens, `failure` is emitted and `error` contains the related text of error.
+///
+/// ℹ️ Renaming across different mount points or filesystems may not be supported, `copy` and `remove` can be used instead.
+#[mel_treatment(
+    input from Block<string>
+    input to Block<string>
+    input filesystem Block<FileSystem>
+    output success Block<void>
+    output failure Block<void>
+    output error Block<string>
+)]
+pub async fn rename() {
+    if let (Ok(filesystem), Ok(from), Ok(to)) = (
+        filesystem.recv_one().await.map(|val| {
+            GetData::<Arc<dyn Data>>::try_data(val)
+                .unwrap()
+                .downcast_arc::<FileSystem>()
+                .unwrap()
+        }),
+        from.recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+        to.recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+    ) {
+        filesystem
+            .filesystem
+            .rename(
+                &from,
+                &to,
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = success.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = failure.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|msg: String| {
+                    Box::pin(async {
+                        let _ = error.send_one(msg.into()).await;
+                    })
+                }),
+            )
+            .await
+    }
+}
+
+/// Copy one file.
+///
+/// The content of the file located at `from` is copied to `to`, replacing `to` if it already exists.
+/// Permissions of the original file are copied as well.
+///
+/// `success` is emitted when file is copied. If copy error happens, `failure` is emitted and `error` contains the related text of error.
+#[mel_treatment(
+    input from Block<string>
+    input to Block<string>
+    input filesystem Block<FileSystem>
+    output success Block<void>
+    output failure Block<void>
+    output error Block<string>
+)]
+pub async fn copy() {
+    if let (Ok(filesystem), Ok(from), Ok(to)) = (
+        filesystem.recv_one().await.map(|val| {
+            GetData::<Arc<dyn Data>>::try_data(val)
+                .unwrap()
+                .downcast_arc::<FileSystem>()
+                .unwrap()
+        }),
+        from.recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+        to.recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+    ) {
+        filesystem
+            .filesystem
+            .copy(
+                &from,
+                &to,
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = success.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = failure.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|msg: String| {
+                    Box::pin(async {
+                        let _ = error.send_one(msg.into()).await;
+                    })
+                }),
+            )
+            .await
+    }
+}
+
+/// Set permissions of file or directory.
+///
+/// Permissions of entry located at `path` are set to `mode`, following Unix permission bits (such as `0o644` or `420`).
+///
+/// `success` is emitted when permissions are set. If error happens, `failure` is emitted and `error` contains the related text of error.
+///
+/// ℹ️ On non-Unix systems, only the readonly state is set, when `mode` gives no write permission.
+#[mel_treatment(
+    input path Block<string>
+    input filesystem Block<FileSystem>
+    output success Block<void>
+    output failure Block<void>
+    output error Block<string>
+)]
+pub async fn set_permissions(mode: u32) {
+    if let (Ok(filesystem), Ok(path)) = (
+        filesystem.recv_one().await.map(|val| {
+            GetData::<Arc<dyn Data>>::try_data(val)
+                .unwrap()
+                .downcast_arc::<FileSystem>()
+                .unwrap()
+        }),
+        path.recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+    ) {
+        filesystem
+            .filesystem
+            .set_permissions(
+                &path,
+                mode,
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = success.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = failure.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|msg: String| {
+                    Box::pin(async {
+                        let _ = error.send_one(msg.into()).await;
+                    })
+                }),
+            )
+            .await
+    }
+}
+
+/// Create symbolic link.
+///
+/// A symbolic link is created at `link`, pointing to `target`.
+///
+/// `success` is emitted when link is created. If error happens, `failure` is emitted and `error` contains the related text of error.
+///
+/// ℹ️ On Windows systems, `target` is required to exist to determine whether a file or directory link should be created.
+#[mel_treatment(
+    input target Block<string>
+    input link Block<string>
+    input filesystem Block<FileSystem>
+    output success Block<void>
+    output failure Block<void>
+    output error Block<string>
+)]
+pub async fn symlink() {
+    if let (Ok(filesystem), Ok(target), Ok(link)) = (
+        filesystem.recv_one().await.map(|val| {
+            GetData::<Arc<dyn Data>>::try_data(val)
+                .unwrap()
+                .downcast_arc::<FileSystem>()
+                .unwrap()
+        }),
+        target
+            .recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+        link.recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+    ) {
+        filesystem
+            .filesystem
+            .symlink(
+                &target,
+                &link,
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = success.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = failure.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|msg: String| {
+                    Box::pin(async {
+                        let _ = error.send_one(msg.into()).await;
+                    })
+                }),
+            )
+            .await
+    }
+}
+
+/// Get metadata of file or directory.
+///
+/// Metadata of entry located at `path` are sent through outputs:
+/// - `size`: size of the entry, in bytes;
+/// - `is_dir`, `is_file`, `is_symlink`: kind of the entry;
+/// - `readonly`: whether the entry is read-only;
+/// - `mode`: Unix permission bits of the entry, only emitted on Unix systems;
+/// - `modified`: last modification time, in milliseconds since Unix epoch, only emitted if available on the system.
+///
+/// If `follow_links` is `true`, metadata of the entry targeted by a symbolic link are given, else metadata of link itself.
+///
+/// If error happens, `failure` is emitted and `error` contains the related text of error.
+#[mel_treatment(
+    default follow_links true
+    input path Block<string>
+    input filesystem Block<FileSystem>
+    output size Block<u64>
+    output is_dir Block<bool>
+    output is_file Block<bool>
+    output is_symlink Block<bool>
+    output readonly Block<bool>
+    output mode Block<u32>
+    output modified Block<u64>
+    output failure Block<void>
+    output error Block<string>
+)]
+pub async fn metadata(follow_links: bool) {
+    if let (Ok(filesystem), Ok(path)) = (
+        filesystem.recv_one().await.map(|val| {
+            GetData::<Arc<dyn Data>>::try_data(val)
+                .unwrap()
+                .downcast_arc::<FileSystem>()
+                .unwrap()
+        }),
+        path.recv_one()
+            .await
+            .map(|val| GetData::<string>::try_data(val).unwrap()),
+    ) {
+        filesystem
+            .filesystem
+            .metadata(
+                &path,
+                follow_links,
+                Box::new(|metadata: EntryMetadata| {
+                    Box::pin({
+                        let (size, is_dir, is_file, is_symlink, readonly, mode, modified) = (
+                            &size,
+                            &is_dir,
+                            &is_file,
+                            &is_symlink,
+                            &readonly,
+                            &mode,
+                            &modified,
+                        );
+                        async move {
+                            let _ = size.send_one(metadata.size.into()).await;
+                            let _ = is_dir.send_one(metadata.is_dir.into()).await;
+                            let _ = is_file.send_one(metadata.is_file.into()).await;
+                            let _ = is_symlink.send_one(metadata.is_symlink.into()).await;
+                            let _ = readonly.send_one(metadata.readonly.into()).await;
+                            if let Some(mode_bits) = metadata.mode {
+                                let _ = mode.send_one(mode_bits.into()).await;
+                            }
+                            if let Some(time) = metadata.modified {
+                                let _ = modified.send_one(time.into()).await;
+                            }
+                        }
+                    })
+                }),
+                Box::new(|| {
+                    Box::pin(async {
+                        let _ = failure.send_one(().into()).await;
+                    })
+                }),
+                Box::new(|msg: String| {
+                    Box::pin(async {
+                        let _ = error.send_one(msg.into()).await;
+                    })
+                }),
+            )
+            .await
+    }
+}
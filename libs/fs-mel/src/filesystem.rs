@@ -23,6 +23,50 @@ pub type OutDataCall<'a> = Box<
         + Sync
         + 'a,
 >;
+pub type OnceMetadataCall<'a> = Box<
+    dyn FnOnce(EntryMetadata) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> + Send + Sync + 'a,
+>;
+
+/// Metadata of a filesystem entry.
+///
+/// - `mode` is only available on Unix systems,
+/// - `modified` is given in milliseconds since Unix epoch, if available on the system.
+#[derive(Debug, Clone, Default)]
+pub struct EntryMetadata {
+    pub size: u64,
+    pub is_dir: bool,
+    pub is_file: bool,
+    pub is_symlink: bool,
+    pub readonly: bool,
+    pub mode: Option<u32>,
+    pub modified: Option<u64>,
+}
+
+impl From<&std::fs::Metadata> for EntryMetadata {
+    fn from(metadata: &std::fs::Metadata) -> Self {
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            Some(metadata.permissions().mode())
+        };
+        #[cfg(not(unix))]
+        let mode = None;
+
+        Self {
+            size: metadata.len(),
+            is_dir: metadata.is_dir(),
+            is_file: metadata.is_file(),
+            is_symlink: metadata.is_symlink(),
+            readonly: metadata.permissions().readonly(),
+            mode,
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as u64),
+        }
+    }
+}
 
 #[async_trait]
 pub trait FileSystemEngine: Debug + Send + Sync {
@@ -69,16 +113,497 @@ pub trait FileSystemEngine: Debug + Send + Sync {
         finished: OnceTriggerCall<'async_trait>,
         errors: OutMessageCall<'async_trait>,
     );
+    async fn remove_file(
+        &self,
+        path: &str,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    );
+    async fn remove_dir(
+        &self,
+        path: &str,
+        recursive: bool,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    );
+    async fn rename(
+        &self,
+        from: &str,
+        to: &str,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    );
+    async fn copy(
+        &self,
+        from: &str,
+        to: &str,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    );
+    async fn set_permissions(
+        &self,
+        path: &str,
+        mode: u32,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    );
+    async fn symlink(
+        &self,
+        target: &str,
+        link: &str,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    );
+    async fn metadata(
+        &self,
+        path: &str,
+        follow_links: bool,
+        metadata: OnceMetadataCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    );
+}
+
+/// Calls `success`, or `error` and `failed`, depending on `result`.
+pub async fn report_outcome<'a, T>(
+    result: std::io::Result<T>,
+    success: OnceTriggerCall<'a>,
+    failed: OnceTriggerCall<'a>,
+    error: OnceMessageCall<'a>,
+) {
+    match result {
+        Ok(_) => success().await,
+        Err(err) => {
+            error(err.to_string()).await;
+            failed().await;
+        }
+    }
+}
+
+/// Sets permissions of `path` according to Unix `mode`.
+///
+/// On non-Unix systems, only the readonly flag is set, when `mode` gives no write permission.
+pub async fn set_mode(path: &async_std::path::Path, mode: u32) -> std::io::Result<()> {
+    #[cfg(unix)]
+    let permissions = {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::Permissions::from_mode(mode)
+    };
+    #[cfg(not(unix))]
+    let permissions = {
+        let mut permissions = async_std::fs::metadata(path).await?.permissions();
+        permissions.set_readonly(mode & 0o222 == 0);
+        permissions
+    };
+
+    async_std::fs::set_permissions(path, permissions).await
+}
+
+/// Creates a symbolic link at `link`, pointing to `target`.
+pub async fn create_symlink(
+    target: &async_std::path::Path,
+    link: &async_std::path::Path,
+) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        async_std::os::unix::fs::symlink(target, link).await
+    }
+    #[cfg(windows)]
+    {
+        if async_std::fs::metadata(target)
+            .await
+            .map(|metadata| metadata.is_dir())
+            .unwrap_or(false)
+        {
+            async_std::os::windows::fs::symlink_dir(target, link).await
+        } else {
+            async_std::os::windows::fs::symlink_file(target, link).await
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = (target, link);
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "symbolic links are not supported on this system",
+        ))
+    }
+}
+
+/// Maximal number of symbolic links followed when resolving a path, as most systems do.
+const MAX_LINKS: usize = 40;
+
+/// Resolves `path` as the system would, following symbolic links, even if its last components do not exist (yet).
+///
+/// The deepest existing ancestor is canonicalized, and remaining components are appended to it.
+fn resolve(path: &std::path::Path, links: usize) -> std::io::Result<std::path::PathBuf> {
+    if links > MAX_LINKS {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("too many symbolic links in '{}'", path.display()),
+        ));
+    }
+
+    let components: Vec<_> = path.components().collect();
+    let mut existing = components.len();
+    let mut resolved = loop {
+        if existing == 0 {
+            break std::path::PathBuf::new();
+        }
+        let ancestor: std::path::PathBuf = components[..existing].iter().collect();
+        match std::fs::canonicalize(&ancestor) {
+            Ok(resolved) => break resolved,
+            // Ancestor is a dangling symbolic link, resolved through its target.
+            Err(_) if std::fs::read_link(&ancestor).is_ok() => {
+                let target = std::fs::read_link(&ancestor)?;
+                let parent: std::path::PathBuf = components[..existing - 1].iter().collect();
+                break resolve(&parent.join(target), links + 1)?;
+            }
+            Err(_) => existing -= 1,
+        }
+    };
+
+    for (index, component) in components.iter().enumerate().skip(existing) {
+        match component {
+            std::path::Component::ParentDir => {
+                // Parent may not be the lexical one if an entry got followed, so the rest is resolved again.
+                resolved.pop();
+                let rest: std::path::PathBuf = components[index + 1..].iter().collect();
+                return resolve(&resolved.join(rest), links);
+            }
+            std::path::Component::CurDir => {}
+            component => resolved.push(component),
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Directory tree whose entries are all designated relatively to its root, such as a volume shared with containers.
+///
+/// Paths are resolved following symbolic links, and cannot lead outside of the root: absolute paths,
+/// as well as `..` components or symbolic links leading out of the root, are rejected.
+#[derive(Debug, Clone)]
+pub struct VolumeFileSystem {
+    root: async_std::path::PathBuf,
+}
+
+impl VolumeFileSystem {
+    pub fn new(root: async_std::path::PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &async_std::path::Path {
+        &self.root
+    }
+
+    /// Gives full path of `path` within the volume.
+    ///
+    /// Every directory leading to the entry is resolved, the entry itself is kept as named,
+    /// so a symbolic link can be handled as such; but it still has to lead within the volume.
+    pub fn full_path(
+        &self,
+        path: &async_std::path::Path,
+    ) -> std::io::Result<async_std::path::PathBuf> {
+        let path: &std::path::Path = path.as_ref();
+        let outside = || {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "path '{}' must be relative and stay within volume",
+                    path.display()
+                ),
+            )
+        };
+        if path.has_root()
+            || path
+                .components()
+                .any(|component| matches!(component, std::path::Component::Prefix(_)))
+        {
+            return Err(outside());
+        }
+
+        let root = resolve(self.root.as_ref(), 0)?;
+        let full_path = self.root.join(path);
+        let full_path: &std::path::Path = full_path.as_ref();
+
+        if !resolve(full_path, 0)?.starts_with(&root) {
+            return Err(outside());
+        }
+
+        match (
+            path.components().next_back(),
+            full_path.parent(),
+            full_path.file_name(),
+        ) {
+            (Some(std::path::Component::Normal(_)), Some(parent), Some(name)) => {
+                let parent = resolve(parent, 0)?;
+                if parent.starts_with(&root) {
+                    Ok(parent.join(name).into())
+                } else {
+                    Err(outside())
+                }
+            }
+            _ => Ok(resolve(full_path, 0)?.into()),
+        }
+    }
+
+    /// Checks that symbolic link `target` stays within the volume once resolved from `link` location.
+    ///
+    /// Target is relative to the link directory, and is kept as given.
+    pub fn link_target(
+        &self,
+        target: &async_std::path::Path,
+        link: &async_std::path::Path,
+    ) -> std::io::Result<async_std::path::PathBuf> {
+        let target: &std::path::Path = target.as_ref();
+        let outside = || {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "link target '{}' must be relative and stay within volume",
+                    target.display()
+                ),
+            )
+        };
+        if target.has_root()
+            || target
+                .components()
+                .any(|component| matches!(component, std::path::Component::Prefix(_)))
+        {
+            return Err(outside());
+        }
+
+        let link = self.full_path(link)?;
+        let link: &std::path::Path = link.as_ref();
+        let directory = link.parent().ok_or_else(outside)?;
+        if resolve(&directory.join(target), 0)?.starts_with(resolve(self.root.as_ref(), 0)?) {
+            Ok(target.to_path_buf().into())
+        } else {
+            Err(outside())
+        }
+    }
+
+    pub async fn remove_file<'a>(
+        &self,
+        path: &str,
+        success: OnceTriggerCall<'a>,
+        failed: OnceTriggerCall<'a>,
+        error: OnceMessageCall<'a>,
+    ) {
+        let result = match self.full_path(path.as_ref()) {
+            Ok(path) => async_std::fs::remove_file(path).await,
+            Err(err) => Err(err),
+        };
+        report_outcome(result, success, failed, error).await;
+    }
+
+    pub async fn remove_dir<'a>(
+        &self,
+        path: &str,
+        recursive: bool,
+        success: OnceTriggerCall<'a>,
+        failed: OnceTriggerCall<'a>,
+        error: OnceMessageCall<'a>,
+    ) {
+        let result = match self.full_path(path.as_ref()) {
+            Ok(path) if recursive => async_std::fs::remove_dir_all(path).await,
+            Ok(path) => async_std::fs::remove_dir(path).await,
+            Err(err) => Err(err),
+        };
+        report_outcome(result, success, failed, error).await;
+    }
+
+    pub async fn rename<'a>(
+        &self,
+        from: &str,
+        to: &str,
+        success: OnceTriggerCall<'a>,
+        failed: OnceTriggerCall<'a>,
+        error: OnceMessageCall<'a>,
+    ) {
+        let result = match (self.full_path(from.as_ref()), self.full_path(to.as_ref())) {
+            (Ok(from), Ok(to)) => async_std::fs::rename(from, to).await,
+            (Err(err), _) | (_, Err(err)) => Err(err),
+        };
+        report_outcome(result, success, failed, error).await;
+    }
+
+    pub async fn copy<'a>(
+        &self,
+        from: &str,
+        to: &str,
+        success: OnceTriggerCall<'a>,
+        failed: OnceTriggerCall<'a>,
+        error: OnceMessageCall<'a>,
+    ) {
+        let result = match (self.full_path(from.as_ref()), self.full_path(to.as_ref())) {
+            (Ok(from), Ok(to)) => async_std::fs::copy(from, to).await,
+            (Err(err), _) | (_, Err(err)) => Err(err),
+        };
+        report_outcome(result, success, failed, error).await;
+    }
+
+    pub async fn set_permissions<'a>(
+        &self,
+        path: &str,
+        mode: u32,
+        success: OnceTriggerCall<'a>,
+        failed: OnceTriggerCall<'a>,
+        error: OnceMessageCall<'a>,
+    ) {
+        let result = match self.full_path(path.as_ref()) {
+            Ok(path) => set_mode(&path, mode).await,
+            Err(err) => Err(err),
+        };
+        report_outcome(result, success, failed, error).await;
+    }
+
+    pub async fn symlink<'a>(
+        &self,
+        target: &str,
+        link: &str,
+        success: OnceTriggerCall<'a>,
+        failed: OnceTriggerCall<'a>,
+        error: OnceMessageCall<'a>,
+    ) {
+        let result = match (
+            self.link_target(target.as_ref(), link.as_ref()),
+            self.full_path(link.as_ref()),
+        ) {
+            (Ok(target), Ok(link)) => create_symlink(&target, &link).await,
+            (Err(err), _) | (_, Err(err)) => Err(err),
+        };
+        report_outcome(result, success, failed, error).await;
+    }
+
+    pub async fn metadata<'a>(
+        &self,
+        path: &str,
+        follow_links: bool,
+        metadata: OnceMetadataCall<'a>,
+        failed: OnceTriggerCall<'a>,
+        error: OnceMessageCall<'a>,
+    ) {
+        let result = match self.full_path(path.as_ref()) {
+            Ok(path) if follow_links => async_std::fs::metadata(path).await,
+            Ok(path) => async_std::fs::symlink_metadata(path).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(entry_metadata) => metadata((&entry_metadata).into()).await,
+            Err(err) => {
+                error(err.to_string()).await;
+                failed().await;
+            }
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
 /// Abstract handle to a filesystem implementation.
 ///
 /// `FileSystem` carries a filesystem engine (local, remote, container, etc.)
-/// and is consumed by filesystem treatments such as `readFile`, `writeFile`, `scanDir`, and `createDir`.
+/// and is consumed by filesystem treatments such as `file::read`, `file::write`, `file::copy`, `dir::scan`, or `dir::create`.
 /// Obtain a `FileSystem` value via companion functions such as `|local_filesystem`.
 #[mel_data]
 pub struct FileSystem {
     #[serde(skip)]
     pub filesystem: Arc<dyn FileSystemEngine>,
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    /// Gives volume made in temporary directory, with `dir` directory in it, and canonical path of a directory outside of it.
+    fn volume(name: &str) -> (VolumeFileSystem, PathBuf, PathBuf) {
+        let base = std::env::temp_dir().join(format!("fs-mel-test-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(base.join("volume").join("dir")).unwrap();
+        std::fs::create_dir_all(base.join("outside")).unwrap();
+        let base = std::fs::canonicalize(base).unwrap();
+
+        (
+            VolumeFileSystem::new(base.join("volume").into()),
+            base.join("volume"),
+            base.join("outside"),
+        )
+    }
+
+    fn full_path(volume: &VolumeFileSystem, path: &str) -> std::io::Result<PathBuf> {
+        volume
+            .full_path(Path::new(path).into())
+            .map(|path| path.into())
+    }
+
+    #[test]
+    fn test_path_within_volume() {
+        let (volume, root, _) = volume("within");
+
+        assert_eq!(
+            full_path(&volume, "dir/file").unwrap(),
+            root.join("dir").join("file")
+        );
+        assert_eq!(
+            full_path(&volume, "dir/../file").unwrap(),
+            root.join("file")
+        );
+        assert_eq!(full_path(&volume, "dir/..").unwrap(), root);
+    }
+
+    #[test]
+    fn test_path_leaving_volume() {
+        let (volume, _, _) = volume("leaving");
+
+        assert!(full_path(&volume, "../file").is_err());
+        assert!(full_path(&volume, "dir/../../file").is_err());
+        assert!(full_path(&volume, "/etc/passwd").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_staying_within_volume() {
+        let (volume, root, _) = volume("link_within");
+        std::os::unix::fs::symlink(root.join("dir"), root.join("link")).unwrap();
+
+        assert_eq!(
+            full_path(&volume, "link/file").unwrap(),
+            root.join("dir").join("file")
+        );
+        assert_eq!(full_path(&volume, "link").unwrap(), root.join("link"));
+        assert!(volume
+            .link_target(Path::new("../dir").into(), Path::new("dir/other").into())
+            .is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_escaping_volume() {
+        let (volume, root, outside) = volume("link_escaping");
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+        std::os::unix::fs::symlink(outside.join("missing"), root.join("dangling")).unwrap();
+
+        assert!(full_path(&volume, "link").is_err());
+        assert!(full_path(&volume, "link/file").is_err());
+        assert!(full_path(&volume, "dir/../link/file").is_err());
+        assert!(full_path(&volume, "dangling").is_err());
+        assert!(volume
+            .link_target(
+                Path::new("../../outside").into(),
+                Path::new("dir/other").into()
+            )
+            .is_err());
+        assert!(volume
+            .link_target(Path::new("link/file").into(), Path::new("other").into())
+            .is_err());
+    }
+}
@@ -15,5 +15,6 @@ pub mod file;
 pub mod filesystem;
 pub mod local;
 pub mod path;
+pub mod watch;
 
 mel_package!();
@@ -292,6 +292,280 @@ impl FileSystemEngine for LocalFileSystemEngine {
             let _ = failed().await;
         }
     }
+    async fn remove_file(
+        &self,
+        path: &str,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    ) {
+        #[cfg(feature = "real")]
+        {
+            let path = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(path.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+
+            report_outcome(
+                async_std::fs::remove_file(path).await,
+                success,
+                failed,
+                error,
+            )
+            .await;
+        }
+        #[cfg(feature = "mock")]
+        {
+            error("Mock mode".to_string()).await;
+            failed().await;
+        }
+    }
+    async fn remove_dir(
+        &self,
+        path: &str,
+        recursive: bool,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    ) {
+        #[cfg(feature = "real")]
+        {
+            let path = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(path.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+
+            report_outcome(
+                if recursive {
+                    async_std::fs::remove_dir_all(path).await
+                } else {
+                    async_std::fs::remove_dir(path).await
+                },
+                success,
+                failed,
+                error,
+            )
+            .await;
+        }
+        #[cfg(feature = "mock")]
+        {
+            error("Mock mode".to_string()).await;
+            failed().await;
+        }
+    }
+    async fn rename(
+        &self,
+        from: &str,
+        to: &str,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    ) {
+        #[cfg(feature = "real")]
+        {
+            let from = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(from.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+            let to = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(to.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+
+            report_outcome(
+                async_std::fs::rename(from, to).await,
+                success,
+                failed,
+                error,
+            )
+            .await;
+        }
+        #[cfg(feature = "mock")]
+        {
+            error("Mock mode".to_string()).await;
+            failed().await;
+        }
+    }
+    async fn copy(
+        &self,
+        from: &str,
+        to: &str,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    ) {
+        #[cfg(feature = "real")]
+        {
+            let from = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(from.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+            let to = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(to.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+
+            report_outcome(async_std::fs::copy(from, to).await, success, failed, error).await;
+        }
+        #[cfg(feature = "mock")]
+        {
+            error("Mock mode".to_string()).await;
+            failed().await;
+        }
+    }
+    async fn set_permissions(
+        &self,
+        path: &str,
+        mode: u32,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    ) {
+        #[cfg(feature = "real")]
+        {
+            let path = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(path.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+
+            report_outcome(set_mode(&path, mode).await, success, failed, error).await;
+        }
+        #[cfg(feature = "mock")]
+        {
+            error("Mock mode".to_string()).await;
+            failed().await;
+        }
+    }
+    async fn symlink(
+        &self,
+        target: &str,
+        link: &str,
+        success: OnceTriggerCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    ) {
+        #[cfg(feature = "real")]
+        {
+            let target = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(target.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+            let link = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(link.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+
+            report_outcome(create_symlink(&target, &link).await, success, failed, error).await;
+        }
+        #[cfg(feature = "mock")]
+        {
+            error("Mock mode".to_string()).await;
+            failed().await;
+        }
+    }
+    async fn metadata(
+        &self,
+        path: &str,
+        follow_links: bool,
+        metadata: OnceMetadataCall<'async_trait>,
+        failed: OnceTriggerCall<'async_trait>,
+        error: OnceMessageCall<'async_trait>,
+    ) {
+        #[cfg(feature = "real")]
+        {
+            let path = match self
+                .full_path(&Into::<async_std::path::PathBuf>::into(path.to_string()))
+                .await
+            {
+                Ok(path) => path,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                    return;
+                }
+            };
+
+            match if follow_links {
+                async_std::fs::metadata(path).await
+            } else {
+                async_std::fs::symlink_metadata(path).await
+            } {
+                Ok(entry_metadata) => metadata((&entry_metadata).into()).await,
+                Err(err) => {
+                    error(err.to_string()).await;
+                    failed().await;
+                }
+            }
+        }
+        #[cfg(feature = "mock")]
+        {
+            error("Mock mode".to_string()).await;
+            failed().await;
+        }
+    }
 }
 
 /// Create a local filesystem handle optionally rooted at `path`.
@@ -0,0 +1,197 @@
+use melodium_core::{common::executive::ResultStatus, *};
+use melodium_macro::{mel_context, mel_model};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, Weak},
+};
+
+/// Label of logs about watching.
+#[cfg(feature = "real")]
+const LOG_LABEL: &str = "fs/watch::FileWatcher";
+
+/// Describes a filesystem change.
+///
+/// - `path`: the path of the changed entry.
+/// - `kind`: the kind of change, being `created`, `modified`, `removed` or `renamed`.
+#[mel_context]
+pub struct FileChange {
+    pub path: string,
+    pub kind: string,
+}
+
+/// Watches changes on local filesystem.
+///
+/// The watcher reports changes happening on entries located at `path`:
+/// - `recursive`: set whether changes in subdirectories are reported, or only those of direct children.
+///
+/// Every time a change happens, a new track is created with `@FileChange` context, through `change` source.
+/// When an entry is renamed, a change of kind `renamed` is reported for both its old and new paths, if known by the system.
+///
+/// ℹ️ If `path` cannot be watched, the execution is reported as failed.
+/// Errors happening while watching are logged as warnings, watching going on.
+#[mel_model(
+    param path string none
+    param recursive bool true
+    source change (FileChange) () (
+        path Block<string>
+        kind Block<string>
+    )
+    continuous (continuous)
+    shutdown shutdown
+)]
+#[derive(Debug)]
+pub struct FileWatcher {
+    model: Weak<FileWatcherModel>,
+    #[cfg(feature = "real")]
+    watching: Mutex<Watching>,
+}
+
+/// State of watching, kept so shutdown can end it.
+#[cfg(feature = "real")]
+#[derive(Debug)]
+enum Watching {
+    NotStarted,
+    Started {
+        watcher: notify::RecommendedWatcher,
+        events: futures::channel::mpsc::UnboundedSender<notify::Result<notify::Event>>,
+    },
+    ShutDown,
+}
+
+impl FileWatcher {
+    pub fn new(model: Weak<FileWatcherModel>) -> Self {
+        Self {
+            model,
+            #[cfg(feature = "real")]
+            watching: Mutex::new(Watching::NotStarted),
+        }
+    }
+
+    #[cfg(feature = "real")]
+    async fn continuous(&self) -> ResultStatus {
+        use futures::{channel::mpsc::unbounded, StreamExt};
+        use melodium_core::common::executive::Level;
+        use notify::{
+            event::{EventKind, ModifyKind},
+            RecursiveMode, Watcher,
+        };
+
+        let model = self.model.upgrade().unwrap();
+
+        let (sender, mut events) = unbounded();
+        let events_sender = sender.clone();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.unbounded_send(event);
+        })
+        .and_then(|mut watcher| {
+            watcher
+                .watch(
+                    std::path::Path::new(&model.get_path()),
+                    if model.get_recursive() {
+                        RecursiveMode::Recursive
+                    } else {
+                        RecursiveMode::NonRecursive
+                    },
+                )
+                .map(|_| watcher)
+        });
+        match watcher {
+            Ok(watcher) => {
+                let mut watching = self.watching.lock().unwrap();
+                if let Watching::ShutDown = *watching {
+                    return ResultStatus::Ok;
+                }
+                *watching = Watching::Started {
+                    watcher,
+                    events: events_sender,
+                };
+            }
+            Err(err) => {
+                return ResultStatus::Error(format!(
+                    "File watcher cannot watch '{}': {err}",
+                    model.get_path()
+                ))
+            }
+        }
+
+        while let Some(event) = events.next().await {
+            // Events still pending at shutdown are not reported.
+            if let Watching::ShutDown = *self.watching.lock().unwrap() {
+                break;
+            }
+
+            let event: notify::Event = match event {
+                Ok(event) => event,
+                Err(err) => {
+                    if model.world().log_enabled(Level::Warning, LOG_LABEL) {
+                        model
+                            .world()
+                            .log(
+                                Level::Warning,
+                                LOG_LABEL.to_string(),
+                                format!("File watcher error on '{}': {err}", model.get_path()),
+                                None,
+                            )
+                            .await;
+                    }
+                    continue;
+                }
+            };
+            let kind = match event.kind {
+                EventKind::Create(_) => "created",
+                EventKind::Modify(ModifyKind::Name(_)) => "renamed",
+                EventKind::Modify(_) => "modified",
+                EventKind::Remove(_) => "removed",
+                _ => continue,
+            };
+
+            for path in event.paths {
+                let path = path.to_string_lossy().to_string();
+                let file_change = FileChange {
+                    path: path.clone(),
+                    kind: kind.to_string(),
+                };
+
+                model
+                    .new_change(
+                        None,
+                        file_change,
+                        &HashMap::new(),
+                        Some(Box::new(move |mut outputs| {
+                            let path_output = outputs.get("path");
+                            let kind_output = outputs.get("kind");
+
+                            vec![Box::new(Box::pin(async move {
+                                let _ = path_output.send_one(path.into()).await;
+                                let _ = kind_output.send_one(kind.to_string().into()).await;
+                                path_output.close().await;
+                                kind_output.close().await;
+                                ResultStatus::Ok
+                            }))]
+                        })),
+                    )
+                    .await;
+            }
+        }
+
+        ResultStatus::Ok
+    }
+
+    #[cfg(not(feature = "real"))]
+    async fn continuous(&self) {}
+
+    fn invoke_source(&self, _source: &str, _params: HashMap<String, Value>) {}
+
+    fn shutdown(&self) {
+        #[cfg(feature = "real")]
+        {
+            let watching =
+                std::mem::replace(&mut *self.watching.lock().unwrap(), Watching::ShutDown);
+            // Closing events stream ends the watching loop, the engine waiting for it as a continuous task.
+            if let Watching::Started { watcher, events } = watching {
+                drop(watcher);
+                events.close_channel();
+            }
+        }
+    }
+}
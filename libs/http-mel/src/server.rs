@@ -59,7 +59,7 @@ type AsyncProducerOutgoing =
 /// `HttpServer` aims to be used with `connection` treatment.
 /// Every time a new HTTP request matching a configured route comes, a new track is created with `@HttpRequest` context.
 ///
-/// ℹ️ If server binding fails, `failedBinding` is emitted.
+/// ℹ️ If server binding fails, `failedBinding` is emitted, and the execution is reported as failed.
 ///
 /// ⚠️ Use `HttpServer` with `connection` treatment, as using `incoming` source and `outgoing` treatment directly should be done carefully.
 ///
@@ -144,7 +144,7 @@ impl HttpServer {
     }
 
     #[cfg(feature = "real")]
-    async fn continuous(&self) {
+    async fn continuous(&self) -> ResultStatus {
         let model = self.model.upgrade().unwrap();
 
         self.launch_barrier.wait().await;
@@ -331,9 +331,15 @@ impl HttpServer {
                     .with_stopper(self.shutdown.clone())
                     .with_prebound_server(listener)
                     .run_async(router)
-                    .await
+                    .await;
+                ResultStatus::Ok
             }
             Err(err) => {
+                let status = ResultStatus::Error(format!(
+                    "HTTP server cannot bind on {}:{}: {err}",
+                    model.get_host().0,
+                    model.get_port()
+                ));
                 model
                     .new_failedBinding(
                         None,
@@ -350,7 +356,8 @@ impl HttpServer {
                             }))]
                         })),
                     )
-                    .await
+                    .await;
+                status
             }
         }
     }
@@ -135,7 +135,7 @@ fn get_row_as_map(row: &AnyRow) -> Map {
 ///
 /// Use `connect` to open the pool and `close` to drain it explicitly.
 /// The `connected` source fires a track once the pool is ready;
-/// `failure` fires a track when the connection attempt fails;
+/// `failure` fires a track when the connection attempt fails, and the execution is reported as failed;
 /// `closed` fires a track when the pool is drained.
 #[mel_model(
     param url string none
@@ -214,6 +214,7 @@ impl SqlPool {
                 }
                 Err(error) => {
                     let err = error.to_string();
+                    let status = ResultStatus::Error(format!("SQL pool cannot connect: {err}"));
                     model
                         .new_failure(
                             None,
@@ -226,7 +227,7 @@ impl SqlPool {
                                     let _ = error.send_one(Value::String(err)).await;
                                     failed.close().await;
                                     error.close().await;
-                                    ResultStatus::Ok
+                                    status
                                 }))]
                             })),
                         )
@@ -1,3 +1,4 @@
+use crate::flow::sort::compare;
 use melodium_core::{executive::*, *};
 use melodium_macro::{check, mel_data, mel_function, mel_treatment};
 use std::collections::HashMap;
@@ -137,3 +138,74 @@ pub async fn insert(key: string) {
         check!(map.send_one(Value::Data(Arc::new(new_map))).await)
     }
 }
+
+/// Sort a stream of maps according to the value they hold under `key`.
+///
+/// The whole stream is consumed, and then maps are sent through `sorted` in ascending order of their `key` value,
+/// or descending order if `descending` is `true`.
+/// Sort is stable, maps with equal values keep their order of arrival.
+///
+/// Maps are ordered by values of the type held under `key` by the first map having an orderable value there.
+///
+/// ℹ️ Maps not containing `key`, or containing a value of another type, are sent last, in their order of arrival.
+///
+/// ⚠️ All maps are kept in memory until `maps` stream ends.
+#[mel_treatment(
+    default descending false
+    input maps Stream<Map>
+    output sorted Stream<Map>
+)]
+pub async fn sort_by_key(key: string, descending: bool) {
+    let mut key_type = None;
+    let mut keyed = Vec::new();
+    let mut unkeyed = Vec::new();
+    while let Ok(values) = maps
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            let sort_key = GetData::<Arc<dyn Data>>::try_data(val.clone())
+                .unwrap()
+                .downcast_arc::<Map>()
+                .unwrap()
+                .map
+                .get(&key)
+                .filter(|v| {
+                    let data_type = v.datatype();
+                    match &key_type {
+                        Some(key_type) => *key_type == data_type,
+                        None if data_type
+                            .implements(&melodium_core::common::descriptor::DataTrait::Order) =>
+                        {
+                            key_type = Some(data_type);
+                            true
+                        }
+                        None => false,
+                    }
+                })
+                .cloned();
+            match sort_key {
+                Some(sort_key) => keyed.push((sort_key, val)),
+                None => unkeyed.push(val),
+            }
+        }
+    }
+
+    if descending {
+        keyed.sort_by(|(a, _), (b, _)| compare(b, a));
+    } else {
+        keyed.sort_by(|(a, _), (b, _)| compare(a, b));
+    }
+
+    let sorted_maps: VecDeque<Value> = keyed
+        .into_iter()
+        .map(|(_, map)| map)
+        .chain(unkeyed)
+        .collect();
+    if !sorted_maps.is_empty() {
+        let _ = sorted
+            .send_many(TransmissionValue::Other(sorted_maps))
+            .await;
+    }
+}
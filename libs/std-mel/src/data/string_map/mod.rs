@@ -128,3 +128,58 @@ pub async fn insert(key: string) {
         check!(map.send_one(Value::Data(Arc::new(new_map))).await)
     }
 }
+
+/// Sort a stream of maps according to the value they hold under `key`.
+///
+/// The whole stream is consumed, and then maps are sent through `sorted` in ascending lexicographic order of their `key` value,
+/// or descending order if `descending` is `true`.
+/// Sort is stable, maps with equal values keep their order of arrival.
+///
+/// ℹ️ Maps not containing `key` are sent last, in their order of arrival.
+///
+/// ⚠️ All maps are kept in memory until `maps` stream ends.
+#[mel_treatment(
+    default descending false
+    input maps Stream<StringMap>
+    output sorted Stream<StringMap>
+)]
+pub async fn sort_by_key(key: string, descending: bool) {
+    let mut keyed = Vec::new();
+    let mut unkeyed = Vec::new();
+    while let Ok(values) = maps
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            let sort_key = GetData::<Arc<dyn Data>>::try_data(val.clone())
+                .unwrap()
+                .downcast_arc::<StringMap>()
+                .unwrap()
+                .map
+                .get(&key)
+                .cloned();
+            match sort_key {
+                Some(sort_key) => keyed.push((sort_key, val)),
+                None => unkeyed.push(val),
+            }
+        }
+    }
+
+    if descending {
+        keyed.sort_by(|(a, _), (b, _)| b.cmp(a));
+    } else {
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+
+    let sorted_maps: VecDeque<Value> = keyed
+        .into_iter()
+        .map(|(_, map)| map)
+        .chain(unkeyed)
+        .collect();
+    if !sorted_maps.is_empty() {
+        let _ = sorted
+            .send_many(TransmissionValue::Other(sorted_maps))
+            .await;
+    }
+}
@@ -18,10 +18,12 @@ pub async fn log_stream(level: Level, label: string) {
         .map(|values| TryInto::<Vec<string>>::try_into(values).unwrap())
     {
         for msg in msgs {
-            engine
-                .world()
-                .log(level.level, label.clone(), msg, Some(track_id))
-                .await;
+            if engine.world().log_enabled(level.level, &label) {
+                engine
+                    .world()
+                    .log(level.level, label.clone(), msg, Some(track_id))
+                    .await;
+            }
         }
     }
 }
@@ -48,10 +50,12 @@ pub async fn log_stream_label(level: Level) {
             .map(|values| TryInto::<Vec<string>>::try_into(values).unwrap())
         {
             for msg in msgs {
-                engine
-                    .world()
-                    .log(level.level, label.clone(), msg, Some(track_id))
-                    .await;
+                if engine.world().log_enabled(level.level, &label) {
+                    engine
+                        .world()
+                        .log(level.level, label.clone(), msg, Some(track_id))
+                        .await;
+                }
             }
         }
     }
@@ -70,10 +74,12 @@ pub async fn log_block(level: Level, label: string) {
         .await
         .map(|val| GetData::<string>::try_data(val).unwrap())
     {
-        engine
-            .world()
-            .log(level.level, label, msg, Some(track_id))
-            .await;
+        if engine.world().log_enabled(level.level, &label) {
+            engine
+                .world()
+                .log(level.level, label, msg, Some(track_id))
+                .await;
+        }
     }
 }
 
@@ -98,10 +104,12 @@ pub async fn log_block_label(level: Level) {
             .await
             .map(|val| GetData::<string>::try_data(val).unwrap())
         {
-            engine
-                .world()
-                .log(level.level, label, msg, Some(track_id))
-                .await;
+            if engine.world().log_enabled(level.level, &label) {
+                engine
+                    .world()
+                    .log(level.level, label, msg, Some(track_id))
+                    .await;
+            }
         }
     }
 }
@@ -121,10 +129,12 @@ pub async fn log_data_stream(level: Level, label: string) {
         .map(|values| Into::<VecDeque<Value>>::into(values))
     {
         for val in values {
-            engine
-                .world()
-                .log(level.level, label.clone(), format!("{val}"), Some(track_id))
-                .await;
+            if engine.world().log_enabled(level.level, &label) {
+                engine
+                    .world()
+                    .log(level.level, label.clone(), format!("{val}"), Some(track_id))
+                    .await;
+            }
         }
     }
 }
@@ -152,10 +162,12 @@ pub async fn log_data_stream_label(level: Level) {
             .map(|values| Into::<VecDeque<Value>>::into(values))
         {
             for val in values {
-                engine
-                    .world()
-                    .log(level.level, label.clone(), format!("{val}"), Some(track_id))
-                    .await;
+                if engine.world().log_enabled(level.level, &label) {
+                    engine
+                        .world()
+                        .log(level.level, label.clone(), format!("{val}"), Some(track_id))
+                        .await;
+                }
             }
         }
     }
@@ -171,10 +183,12 @@ pub async fn log_data_block(level: Level, label: string) {
     let engine = EngineModel::into(engine);
 
     if let Ok(val) = display.recv_one().await {
-        engine
-            .world()
-            .log(level.level, label, format!("{val}"), Some(track_id))
-            .await;
+        if engine.world().log_enabled(level.level, &label) {
+            engine
+                .world()
+                .log(level.level, label, format!("{val}"), Some(track_id))
+                .await;
+        }
     }
 }
 
@@ -196,10 +210,12 @@ pub async fn log_data_block_label(level: Level) {
         .map(|val| GetData::<String>::try_data(val).unwrap())
     {
         if let Ok(val) = display.recv_one().await {
-            engine
-                .world()
-                .log(level.level, label, format!("{val}"), Some(track_id))
-                .await;
+            if engine.world().log_enabled(level.level, &label) {
+                engine
+                    .world()
+                    .log(level.level, label, format!("{val}"), Some(track_id))
+                    .await;
+            }
         }
     }
 }
@@ -2,7 +2,7 @@ use melodium_core::{
     common::executive::{Output, ResultStatus},
     *,
 };
-use melodium_macro::{mel_function, mel_model};
+use melodium_macro::{mel_function, mel_model, mel_treatment};
 use std::collections::HashMap;
 
 pub mod log;
@@ -49,6 +49,28 @@ impl Engine {
     fn invoke_source(&self, _source: &str, _params: HashMap<String, Value>) {}
 }
 
+/// End the execution, with the given exit code.
+///
+/// When `trigger` is received, the engine stops creating new tracks, models are shut down,
+/// and already running tracks are let to finish. Once everything ended, the process exits with `code`.
+///
+/// ℹ️ If several exits are triggered, only the first `code` is kept.
+/// If `code` is `0` while failures were reported during execution, the process exits with failure code `5` instead.
+/// As systems only keep codes from `1` to `255`, any other non-zero `code` is replaced by `5` as well.
+///
+/// See `exit` for use without explicit model.
+#[mel_treatment(
+    model engine Engine
+    input trigger Block<void>
+)]
+pub async fn terminate(code: i32) {
+    let engine = EngineModel::into(engine);
+
+    if let Ok(_) = trigger.recv_one().await {
+        engine.world().exit(code).await;
+    }
+}
+
 /// Return the current Mélodium engine version string.
 #[mel_function]
 pub fn version() -> string {
@@ -0,0 +1,157 @@
+use async_std::sync::Mutex;
+use melodium_core::*;
+use melodium_macro::{mel_model, mel_treatment};
+use std::{collections::HashMap, time::Duration};
+use web_time::Instant;
+
+/// State of breaker, trial call let to pass in `HalfOpen` being considered failed if its outcome is not recorded before `until`.
+///
+/// `until` is `None` when cooldown is too long to be represented, the state being then kept forever.
+#[derive(Debug)]
+enum State {
+    Closed { failures: u64 },
+    Open { until: Option<Instant> },
+    HalfOpen { until: Option<Instant> },
+}
+
+fn elapsed(until: Option<Instant>, now: Instant) -> bool {
+    until.is_some_and(|until| now >= until)
+}
+
+/// Short-circuits a pipeline after consecutive failures.
+///
+/// The breaker starts closed, letting everything pass through `guard`.
+/// After `threshold` consecutive failures recorded with `recordFailure`, it opens and `guard` rejects
+/// everything during `cooldown` milliseconds.
+/// Once cooldown elapsed, one trial is let to pass: if it succeeds the breaker closes again,
+/// and if it fails the breaker opens for another `cooldown`.
+/// A trial whose outcome is not recorded within `cooldown` is considered failed.
+///
+/// ℹ️ A `threshold` of `0` is considered as `1`.
+#[mel_model(
+    param threshold u64 5
+    param cooldown u64 30000
+)]
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    model: std::sync::Weak<CircuitBreakerModel>,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    pub fn new(model: std::sync::Weak<CircuitBreakerModel>) -> Self {
+        Self {
+            model,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    fn invoke_source(&self, _source: &str, _params: HashMap<String, Value>) {}
+
+    /// Tells if a call is allowed to pass.
+    pub async fn allow(&self) -> bool {
+        let cooldown = Duration::from_millis(self.model.upgrade().unwrap().get_cooldown());
+        let now = Instant::now();
+
+        let mut state = self.state.lock().await;
+        match *state {
+            State::Closed { .. } => true,
+            State::Open { until } if elapsed(until, now) => {
+                *state = State::HalfOpen {
+                    until: now.checked_add(cooldown),
+                };
+                true
+            }
+            State::HalfOpen { until } if elapsed(until, now) => {
+                *state = State::Open {
+                    until: now.checked_add(cooldown),
+                };
+                false
+            }
+            State::Open { .. } | State::HalfOpen { .. } => false,
+        }
+    }
+
+    pub async fn success(&self) {
+        *self.state.lock().await = State::Closed { failures: 0 };
+    }
+
+    pub async fn failure(&self) {
+        let model = self.model.upgrade().unwrap();
+        let open = State::Open {
+            until: Instant::now().checked_add(Duration::from_millis(model.get_cooldown())),
+        };
+
+        let mut state = self.state.lock().await;
+        *state = match *state {
+            State::Closed { failures } if failures + 1 < model.get_threshold() => State::Closed {
+                failures: failures + 1,
+            },
+            State::Closed { .. } | State::HalfOpen { .. } => open,
+            State::Open { until } => State::Open { until },
+        };
+    }
+}
+
+/// Guard a call with a circuit breaker.
+///
+/// When `trigger` is received, `pass` is emitted if `breaker` lets the call pass, else `rejected` is emitted.
+/// Outcome of the guarded call must be recorded with `recordSuccess` or `recordFailure`.
+///
+/// ```mermaid
+/// graph LR
+///     T("guard()")
+///     B["〈🟦〉"] -->|trigger| T
+///
+///     T -->|pass| P["〈🟦〉"]
+///
+///     style B fill:#ffffff,stroke:#ffffff
+///     style P fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    model breaker CircuitBreaker
+    input trigger Block<void>
+    output pass Block<void>
+    output rejected Block<void>
+)]
+pub async fn guard() {
+    let model = CircuitBreakerModel::into(breaker);
+
+    if let Ok(_) = trigger.recv_one().await {
+        if model.inner().allow().await {
+            let _ = pass.send_one(().into()).await;
+        } else {
+            let _ = rejected.send_one(().into()).await;
+        }
+    }
+}
+
+/// Record a success on a circuit breaker.
+///
+/// When `trigger` is received, `breaker` closes and its failures count is reset.
+#[mel_treatment(
+    model breaker CircuitBreaker
+    input trigger Block<void>
+)]
+pub async fn record_success() {
+    let model = CircuitBreakerModel::into(breaker);
+
+    if let Ok(_) = trigger.recv_one().await {
+        model.inner().success().await;
+    }
+}
+
+/// Record a failure on a circuit breaker.
+///
+/// When `trigger` is received, `breaker` failures count is increased, opening it if threshold is reached.
+#[mel_treatment(
+    model breaker CircuitBreaker
+    input trigger Block<void>
+)]
+pub async fn record_failure() {
+    let model = CircuitBreakerModel::into(breaker);
+
+    if let Ok(_) = trigger.recv_one().await {
+        model.inner().failure().await;
+    }
+}
@@ -1,10 +1,23 @@
+use async_std::{channel::unbounded, future::timeout, task::sleep};
 use futures::{pin_mut, select, FutureExt};
-use melodium_core::common::executive::{GetData, Value};
+use melodium_core::{
+    common::executive::{GetData, Value},
+    DataTrait, TransmissionValue,
+};
 use melodium_macro::{check, mel_treatment};
-use std::collections::VecDeque;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    hash::Hasher,
+    time::Duration,
+};
+use web_time::Instant;
 
+pub mod circuit_breaker;
 pub mod concentrate;
+pub mod retry;
+pub mod sort;
 pub mod vec;
+pub mod window;
 
 /// Chain two streams.
 ///
@@ -897,3 +910,312 @@ pub async fn waitBlock() {
         let _ = awaited.send_one(().into()).await;
     }
 }
+
+/// Emit ticks periodically.
+///
+/// Once `trigger` is received, a tick is streamed every `period` milliseconds, starting with an immediate one.
+/// Ticks are scheduled from the start time, so a slow consumer does not make the ticker drift.
+///
+/// ℹ️ Ticker continues until `tick` is closed by its consumers.
+///
+/// ```mermaid
+/// graph LR
+///     T("ticker(period=1000)")
+///     B["〈🟦〉"] -->|trigger| T
+///         
+///     T -->|tick| S["… 🟦 ⏱️ 🟦 ⏱️ 🟦"]
+///     
+///     style B fill:#ffffff,stroke:#ffffff
+///     style S fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    input trigger Block<void>
+    output tick Stream<void>
+)]
+pub async fn ticker(period: u64) {
+    if let Ok(_) = trigger.recv_one().await {
+        let period = Duration::from_millis(period);
+        let start = Instant::now();
+        let mut next = start;
+        loop {
+            check!(tick.send_one(().into()).await);
+
+            match next.checked_add(period) {
+                Some(instant) => {
+                    next = instant;
+                    sleep(next.saturating_duration_since(Instant::now())).await;
+                }
+                // Next tick is too far to be ever reached.
+                None => sleep(period).await,
+            }
+        }
+    }
+}
+
+/// Delay a stream.
+///
+/// Each value is sent through `delayed` `delay` milliseconds after it was received, keeping order.
+/// Values continue to be received while waiting, so the delay does not accumulate along the stream.
+///
+/// ```mermaid
+/// graph LR
+///     T("delay(delay=500)")
+///     V["🟨 🟨 🟨 …"] -->|stream| T
+///     
+///     T -->|delayed| O["⏱️ 🟨 🟨 🟨 …"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T ()
+    input stream Stream<T>
+    output delayed Stream<T>
+)]
+pub async fn delay(delay: u64) {
+    let delay = Duration::from_millis(delay);
+    let (sender, receiver) = unbounded();
+
+    let receiving = async {
+        let sender = sender;
+        while let Ok(values) = stream.recv_many().await {
+            check!(sender.send((Instant::now(), values)).await)
+        }
+    };
+    let sending = async {
+        let receiver = receiver;
+        while let Ok((instant, values)) = receiver.recv().await {
+            // Deadline too far to be represented is never reached, and neither is the end of a sleep that long.
+            sleep(instant.checked_add(delay).map_or(delay, |instant| {
+                instant.saturating_duration_since(Instant::now())
+            }))
+            .await;
+            check!(delayed.send_many(values).await)
+        }
+    };
+
+    futures::join!(receiving, sending);
+}
+
+/// Delay a block.
+///
+/// `value` is emitted through `delayed` `delay` milliseconds after it was received.
+///
+/// ```mermaid
+/// graph LR
+///     T("delayBlock(delay=500)")
+///     B["〈🟨〉"] -->|value| T
+///         
+///     T -->|delayed| S["⏱️〈🟨〉"]
+///     
+///     style B fill:#ffffff,stroke:#ffffff
+///     style S fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T ()
+    input value Block<T>
+    output delayed Block<T>
+)]
+pub async fn delayBlock(delay: u64) {
+    if let Ok(val) = value.recv_one().await {
+        sleep(Duration::from_millis(delay)).await;
+        let _ = delayed.send_one(val).await;
+    }
+}
+
+/// Throttle a stream.
+///
+/// Let at most `count` values pass through `throttled` for each `period` milliseconds.
+/// Values exceeding the rate are held back until next period, none of them is discarded.
+///
+/// ℹ️ If `count` is `0`, nothing is ever let through.
+///
+/// ```mermaid
+/// graph LR
+///     T("throttle(count=2, period=1000)")
+///     V["🟨 🟨 🟨 🟨 🟨 …"] -->|stream| T
+///     
+///     T -->|throttled| O["🟨 🟨 ⏱️ 🟨 🟨 ⏱️ 🟨 …"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T ()
+    input stream Stream<T>
+    output throttled Stream<T>
+)]
+pub async fn throttle(count: u64, period: u64) {
+    if count == 0 {
+        return;
+    }
+
+    let period = Duration::from_millis(period);
+    let mut window_start = Instant::now();
+    let mut window_count = 0u64;
+    while let Ok(val) = stream.recv_one().await {
+        let now = Instant::now();
+        // Window too long to be represented never ends.
+        let window_end = window_start.checked_add(period);
+        if window_end.is_some_and(|window_end| now >= window_end) {
+            window_start = now;
+            window_count = 0;
+        } else if window_count >= count {
+            sleep(window_end.map_or(period, |window_end| {
+                window_end.saturating_duration_since(now)
+            }))
+            .await;
+            window_start = Instant::now();
+            window_count = 0;
+        }
+
+        check!(throttled.send_one(val).await);
+        window_count += 1;
+    }
+}
+
+/// Debounce a stream.
+///
+/// A value is sent through `debounced` only once `period` milliseconds passed without any newer value coming in `stream`.
+/// Bursts of values are then reduced to their last one.
+///
+/// ℹ️ When `stream` ends, the pending value, if any, is sent immediately.
+///
+/// ```mermaid
+/// graph LR
+///     T("debounce(period=200)")
+///     V["🟦 🟧 🟪 ⏱️ 🟫 🟨 ⏱️"] -->|stream| T
+///     
+///     T -->|debounced| O["🟪 🟨"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T ()
+    input stream Stream<T>
+    output debounced Stream<T>
+)]
+pub async fn debounce(period: u64) {
+    let period = Duration::from_millis(period);
+    let (sender, receiver) = unbounded();
+
+    let receiving = async {
+        let sender = sender;
+        while let Ok(val) = stream.recv_one().await {
+            check!(sender.send(val).await)
+        }
+    };
+    let sending = async {
+        let receiver = receiver;
+        let mut pending = None;
+        loop {
+            match pending.take() {
+                None => match receiver.recv().await {
+                    Ok(val) => pending = Some(val),
+                    Err(_) => break,
+                },
+                Some(val) => match timeout(period, receiver.recv()).await {
+                    Ok(Ok(newer)) => pending = Some(newer),
+                    Ok(Err(_)) => {
+                        let _ = debounced.send_one(val).await;
+                        break;
+                    }
+                    Err(_) => check!(debounced.send_one(val).await),
+                },
+            }
+        }
+    };
+
+    futures::join!(receiving, sending);
+}
+
+/// Remove consecutive duplicates from a stream.
+///
+/// A value is sent through `deduplicated` only if it differs from the one just before it.
+///
+/// ```mermaid
+/// graph LR
+///     T("dedup()")
+///     V["🟦 🟦 🟧 🟧 🟧 🟦 🟪"] -->|stream| T
+///     
+///     T -->|deduplicated| O["🟦 🟧 🟦 🟪"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T (Equality)
+    input stream Stream<T>
+    output deduplicated Stream<T>
+)]
+pub async fn dedup() {
+    let mut previous: Option<Value> = None;
+    while let Ok(values) = stream
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        let mut kept = VecDeque::with_capacity(values.len());
+        for val in values {
+            if previous
+                .as_ref()
+                .map(|previous| !previous.partial_equality_eq(&val))
+                .unwrap_or(true)
+            {
+                kept.push_back(val.clone());
+            }
+            previous = Some(val);
+        }
+
+        if !kept.is_empty() {
+            check!(deduplicated.send_many(TransmissionValue::Other(kept)).await)
+        }
+    }
+}
+
+/// Remove all duplicates from a stream.
+///
+/// A value is sent through `distinct` only the first time it is seen in the stream.
+///
+/// ⚠️ Every distinct value is kept in memory until stream ends.
+///
+/// ```mermaid
+/// graph LR
+///     T("distinct()")
+///     V["🟦 🟧 🟦 🟪 🟧 🟫"] -->|stream| T
+///     
+///     T -->|distinct| O["🟦 🟧 🟪 🟫"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T (Hash Equality)
+    input stream Stream<T>
+    output distinct Stream<T>
+)]
+pub async fn distinct() {
+    let mut seen: HashMap<u64, Vec<Value>> = HashMap::new();
+    while let Ok(values) = stream
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        let mut kept = VecDeque::with_capacity(values.len());
+        for val in values {
+            let mut hasher = DefaultHasher::new();
+            DataTrait::hash(&val, &mut hasher);
+
+            let bucket = seen.entry(hasher.finish()).or_default();
+            if !bucket.iter().any(|known| known.partial_equality_eq(&val)) {
+                bucket.push(val.clone());
+                kept.push_back(val);
+            }
+        }
+
+        if !kept.is_empty() {
+            check!(distinct.send_many(TransmissionValue::Other(kept)).await)
+        }
+    }
+}
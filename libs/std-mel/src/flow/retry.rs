@@ -0,0 +1,239 @@
+use async_std::{
+    channel::{unbounded, Receiver, Sender},
+    future::timeout,
+    sync::Mutex,
+    task::sleep,
+};
+use melodium_core::{common::executive::ResultStatus, *};
+use melodium_macro::{mel_context, mel_model, mel_treatment};
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use uuid::Uuid;
+
+/// Mask of random bits taken for jitter.
+const RANDOM_BITS: u64 = (1 << 62) - 1;
+
+/// Describes a retry attempt.
+///
+/// - `id`: identifier of the retry sequence the attempt belongs to.
+/// - `number`: number of the attempt in its sequence, starting at `1`.
+#[mel_context]
+pub struct Attempt {
+    pub id: u128,
+    pub number: u64,
+}
+
+/// Retries a pipeline until it succeeds.
+///
+/// Each attempt made by `retry` creates a new track with `@Attempt` context, in which the retried pipeline runs.
+/// This pipeline has to report its outcome through `retried` (or `succeed`/`fail`).
+///
+/// - `attempts`: maximal number of attempts, including the first one.
+/// - `delay`: delay before the first retry, in milliseconds.
+/// - `multiplier`: factor applied to the delay after each failed attempt.
+/// - `max_delay`: maximal delay between attempts, in milliseconds.
+/// - `jitter`: fraction of the delay randomly removed, between `0.0` (none) and `1.0` (delay is anything from zero to its full value).
+/// - `timeout`: time an attempt has to report its outcome, in milliseconds, before being considered as failed (`0` means no timeout).
+///
+/// ⚠️ One `Retry` model should be used for only one retried pipeline, as every attempt is sent to all pipelines using it.
+///
+/// ℹ️ If an attempt never reports its outcome and `timeout` is `0`, the sequence waits forever.
+#[mel_model(
+    param attempts u64 3
+    param delay u64 100
+    param multiplier f64 2.0
+    param max_delay u64 10000
+    param jitter f64 0.0
+    param timeout u64 0
+    source attempt (Attempt) () (
+        trigger Block<void>
+    )
+)]
+#[derive(Debug)]
+pub struct Retry {
+    model: std::sync::Weak<RetryModel>,
+    counter: AtomicU64,
+    sequences: Mutex<HashMap<u128, Sender<(u64, bool)>>>,
+}
+
+impl Retry {
+    pub fn new(model: std::sync::Weak<RetryModel>) -> Self {
+        Self {
+            model,
+            counter: AtomicU64::new(0),
+            sequences: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn invoke_source(&self, _source: &str, _params: HashMap<String, Value>) {}
+
+    async fn register(&self) -> (u128, Receiver<(u64, bool)>) {
+        let id = self.counter.fetch_add(1, Ordering::Relaxed) as u128;
+        let (sender, receiver) = unbounded();
+        self.sequences.lock().await.insert(id, sender);
+        (id, receiver)
+    }
+
+    async fn unregister(&self, id: u128) {
+        self.sequences.lock().await.remove(&id);
+    }
+
+    pub async fn report(&self, id: u128, number: u64, success: bool) {
+        if let Some(sender) = self.sequences.lock().await.get(&id) {
+            let _ = sender.send((number, success)).await;
+        }
+    }
+
+    /// Gives the delay to wait after the failure of attempt `number`.
+    fn backoff(&self, number: u64) -> Duration {
+        let model = self.model.upgrade().unwrap();
+
+        let exponent = number.saturating_sub(1).min(i32::MAX as u64) as i32;
+        let delay = (model.get_delay() as f64 * model.get_multiplier().powi(exponent))
+            .min(model.get_max_delay() as f64);
+
+        let jitter = model.get_jitter().clamp(0.0, 1.0);
+        let delay = if jitter > 0.0 {
+            // Random UUID has its 62 lowest bits random, other ones being version and variant.
+            let random =
+                (Uuid::new_v4().as_u128() as u64 & RANDOM_BITS) as f64 / RANDOM_BITS as f64;
+            delay * (1.0 - jitter * random)
+        } else {
+            delay
+        };
+
+        Duration::from_millis(delay.max(0.0) as u64)
+    }
+}
+
+/// Run a pipeline, retrying it until it succeeds.
+///
+/// When `trigger` is received, a first attempt is made through `retry` model, then a new one
+/// after each failure, with exponential backoff, until success or attempts are exhausted.
+///
+/// - `succeeded`: emitted when an attempt succeeded.
+/// - `exhausted`: emitted when all attempts failed.
+/// - `attempts`: number of attempts made, emitted at the end of the sequence.
+///
+/// ```mermaid
+/// graph LR
+///     T("retry()")
+///     B["〈🟦〉"] -->|trigger| T
+///
+///     T -->|succeeded| S["〈🟦〉"]
+///     T -->|attempts| A["〈🟨〉"]
+///
+///     style B fill:#ffffff,stroke:#ffffff
+///     style S fill:#ffffff,stroke:#ffffff
+///     style A fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    model retry Retry
+    input trigger Block<void>
+    output succeeded Block<void>
+    output exhausted Block<void>
+    output attempts Block<u64>
+)]
+pub async fn retry() {
+    let model = RetryModel::into(retry);
+    let retry = model.inner();
+
+    if let Ok(_) = trigger.recv_one().await {
+        let max_attempts = model.get_attempts().max(1);
+        let attempt_timeout = model.get_timeout();
+        let (id, outcomes) = retry.register().await;
+
+        let mut number = 0;
+        let success = loop {
+            number += 1;
+
+            model
+                .new_attempt(
+                    Some(track_id),
+                    Attempt { id, number },
+                    &HashMap::new(),
+                    Some(Box::new(|mut outputs| {
+                        let trigger = outputs.get("trigger");
+
+                        vec![Box::new(Box::pin(async move {
+                            let _ = trigger.send_one(().into()).await;
+                            trigger.close().await;
+                            ResultStatus::Ok
+                        }))]
+                    })),
+                )
+                .await;
+
+            let outcome = async {
+                loop {
+                    match outcomes.recv().await {
+                        Ok((reported, success)) if reported == number => break Some(success),
+                        Ok(_) => continue,
+                        Err(_) => break None,
+                    }
+                }
+            };
+            let outcome = if attempt_timeout > 0 {
+                timeout(Duration::from_millis(attempt_timeout), outcome)
+                    .await
+                    .unwrap_or(Some(false))
+            } else {
+                outcome.await
+            };
+
+            match outcome {
+                Some(true) => break true,
+                Some(false) if number < max_attempts => sleep(retry.backoff(number)).await,
+                _ => break false,
+            }
+        };
+
+        retry.unregister(id).await;
+
+        let _ = attempts.send_one(number.into()).await;
+        if success {
+            let _ = succeeded.send_one(().into()).await;
+        } else {
+            let _ = exhausted.send_one(().into()).await;
+        }
+    }
+}
+
+/// Report success of a retry attempt.
+///
+/// When `trigger` is received, the attempt `number` of sequence `id` is considered as succeeded.
+/// Only the first outcome reported for an attempt is considered.
+///
+/// ℹ️ `retried` should be preferred to direct use of `succeed` and `fail`.
+#[mel_treatment(
+    model retry Retry
+    input trigger Block<void>
+)]
+pub async fn succeed(id: u128, number: u64) {
+    let model = RetryModel::into(retry);
+
+    if let Ok(_) = trigger.recv_one().await {
+        model.inner().report(id, number, true).await;
+    }
+}
+
+/// Report failure of a retry attempt.
+///
+/// When `trigger` is received, the attempt `number` of sequence `id` is considered as failed.
+/// Only the first outcome reported for an attempt is considered.
+///
+/// ℹ️ `retried` should be preferred to direct use of `succeed` and `fail`.
+#[mel_treatment(
+    model retry Retry
+    input trigger Block<void>
+)]
+pub async fn fail(id: u128, number: u64) {
+    let model = RetryModel::into(retry);
+
+    if let Ok(_) = trigger.recv_one().await {
+        model.inner().report(id, number, false).await;
+    }
+}
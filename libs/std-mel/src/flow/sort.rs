@@ -0,0 +1,358 @@
+use async_std::task::spawn_blocking;
+use cbor4ii::core::utils::SliceReader;
+use melodium_core::{
+    common::{descriptor::DataType, executive::ResultStatus},
+    *,
+};
+use melodium_macro::{check, mel_treatment};
+use std::{
+    cmp::Ordering,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+use uuid::Uuid;
+
+/// Compare two values of a type implementing `Order`.
+pub(crate) fn compare(a: &Value, b: &Value) -> Ordering {
+    if a.partial_order_lt(b) {
+        Ordering::Less
+    } else if a.partial_order_gt(b) {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+fn sort_values(values: &mut Vec<Value>, descending: bool) {
+    if descending {
+        values.sort_by(|a, b| compare(b, a));
+    } else {
+        values.sort_by(compare);
+    }
+}
+
+/// Sort a stream.
+///
+/// The whole stream is consumed, and then values are sent through `sorted` in ascending order,
+/// or descending order if `descending` is `true`.
+/// Sort is stable, equal values keep their order of arrival.
+///
+/// ⚠️ All values are kept in memory until `stream` ends, see `sort_external` for large amounts of data.
+///
+/// ```mermaid
+/// graph LR
+///     T("sort()")
+///     V["3️⃣ 1️⃣ 4️⃣ 1️⃣ 5️⃣"] -->|stream| T
+///
+///     T -->|sorted| O["1️⃣ 1️⃣ 3️⃣ 4️⃣ 5️⃣"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T (Order)
+    default descending false
+    input stream Stream<T>
+    output sorted Stream<T>
+)]
+pub async fn sort(descending: bool) {
+    let mut values = Vec::new();
+    while let Ok(vals) = stream
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        values.extend(vals);
+    }
+
+    if !values.is_empty() {
+        sort_values(&mut values, descending);
+        let _ = sorted
+            .send_many(TransmissionValue::Other(values.into()))
+            .await;
+    }
+}
+
+/// Number of values read back at once from a run while merging.
+const READ_BATCH: usize = 1024;
+
+/// Sorted run of values spilled on disk.
+///
+/// Each value is stored as its CBOR serialization, preceded by its length as little-endian `u32`.
+/// `byte` values are stored as `u8`, because their serialized form cannot be deserialized back.
+/// Run file gets a random name, is created exclusively, and is removed when run is dropped.
+struct Run {
+    path: PathBuf,
+    reader: Option<BufReader<File>>,
+    buffer: VecDeque<Value>,
+    ended: bool,
+}
+
+impl Run {
+    fn write(values: Vec<Value>) -> std::io::Result<Self> {
+        let path = std::env::temp_dir().join(format!("melodium-sort-{}", Uuid::new_v4()));
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        // Run is built before writing, so file gets removed if writing fails.
+        let run = Self {
+            path,
+            reader: None,
+            buffer: VecDeque::new(),
+            ended: false,
+        };
+
+        let mut writer = BufWriter::new(file);
+        for value in values {
+            let value = match value {
+                Value::Byte(byte) => Value::U8(byte),
+                value => value,
+            };
+            let bytes = cbor4ii::serde::to_vec(Vec::new(), &value)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+            writer.write_all(&bytes)?;
+        }
+        writer.flush()?;
+
+        Ok(run)
+    }
+
+    /// Gives next value of run, or `None` once run is over.
+    ///
+    /// Values are read back by batches, within blocking task.
+    async fn next(&mut self, data_type: &DataType) -> std::io::Result<Option<Value>> {
+        if self.buffer.is_empty() && !self.ended {
+            let path = self.path.clone();
+            let data_type = data_type.clone();
+            let mut reader = self.reader.take();
+            let (reader, values) = spawn_blocking(move || {
+                let values = Self::read(&mut reader, &path, &data_type);
+                (reader, values)
+            })
+            .await;
+            self.reader = reader;
+            self.buffer = values?;
+            self.ended = self.buffer.len() < READ_BATCH;
+        }
+        Ok(self.buffer.pop_front())
+    }
+
+    /// Reads at most [`READ_BATCH`] values from run file, opening it if not already done.
+    fn read(
+        reader: &mut Option<BufReader<File>>,
+        path: &Path,
+        data_type: &DataType,
+    ) -> std::io::Result<VecDeque<Value>> {
+        let reader = match reader {
+            Some(reader) => reader,
+            None => reader.insert(BufReader::new(File::open(path)?)),
+        };
+
+        let mut values = VecDeque::with_capacity(READ_BATCH);
+        while values.len() < READ_BATCH && !reader.fill_buf()?.is_empty() {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len)?;
+            let mut bytes = vec![0u8; u32::from_le_bytes(len) as usize];
+            reader.read_exact(&mut bytes)?;
+
+            let mut deserializer = cbor4ii::serde::Deserializer::new(SliceReader::new(&bytes));
+            let mut erased_deserializer =
+                Box::new(<dyn erased_serde::Deserializer>::erase(&mut deserializer));
+            let value = match data_type {
+                DataType::Byte => match DataType::U8.deserialize(&mut erased_deserializer) {
+                    Ok(Value::U8(byte)) => Ok(Value::Byte(byte)),
+                    Ok(_) => Err("unexpected value type".to_string()),
+                    Err(err) => Err(err.to_string()),
+                },
+                data_type => data_type
+                    .deserialize(&mut erased_deserializer)
+                    .map_err(|err| err.to_string()),
+            }
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            values.push_back(value);
+        }
+
+        Ok(values)
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Sort a stream using bounded memory.
+///
+/// Behaves like `sort`, but at most `chunk` values are kept in memory at once:
+/// the stream is split into sorted runs spilled in the system temporary directory,
+/// which are then merged while being sent through `sorted`.
+/// If the stream fits in one chunk, nothing is written on disk.
+///
+/// ⚠️ If spilling or reading back spilled values fails (such as when temporary directory is not writable),
+/// sort stops, `sorted` is closed and the treatment fails.
+/// If `chunk` is `0`, nothing is emitted.
+#[mel_treatment(
+    generic T (Order Serialize Deserialize)
+    default descending false
+    default chunk 1000000
+    input stream Stream<T>
+    output sorted Stream<T>
+)]
+pub async fn sort_external(descending: bool, chunk: u64) {
+    if chunk == 0 {
+        return ResultStatus::Ok;
+    }
+
+    let data_type = T;
+    let chunk = chunk as usize;
+    let mut runs = Vec::new();
+    let mut values = Vec::new();
+    while let Ok(vals) = stream
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in vals {
+            values.push(val);
+            if values.len() == chunk {
+                let mut run_values = std::mem::take(&mut values);
+                match spawn_blocking(move || {
+                    sort_values(&mut run_values, descending);
+                    Run::write(run_values)
+                })
+                .await
+                {
+                    Ok(run) => runs.push(run),
+                    Err(err) => return spill_failure(err),
+                }
+            }
+        }
+    }
+
+    sort_values(&mut values, descending);
+    if runs.is_empty() {
+        if !values.is_empty() {
+            let _ = sorted
+                .send_many(TransmissionValue::Other(values.into()))
+                .await;
+        }
+        return ResultStatus::Ok;
+    }
+
+    // Last run is kept in memory, and takes part in merge as any other, at index `runs.len()`.
+    let mut last_run: VecDeque<Value> = values.into();
+    let mut heads: Vec<Option<Value>> = Vec::with_capacity(runs.len());
+    for run in &mut runs {
+        match run.next(&data_type).await {
+            Ok(head) => heads.push(head),
+            Err(err) => return spill_failure(err),
+        }
+    }
+    let mut last_head = last_run.pop_front();
+
+    loop {
+        // Runs are checked in order of creation, and only a strictly better value replaces
+        // the current best one, so equal values keep their order of arrival.
+        let mut best: Option<(usize, &Value)> = None;
+        for (index, head) in heads.iter().chain(std::iter::once(&last_head)).enumerate() {
+            if let Some(head) = head {
+                let better = match best {
+                    None => true,
+                    Some((_, best)) => {
+                        let ordering = compare(head, best);
+                        if descending {
+                            ordering == Ordering::Greater
+                        } else {
+                            ordering == Ordering::Less
+                        }
+                    }
+                };
+                if better {
+                    best = Some((index, head));
+                }
+            }
+        }
+
+        let value = match best.map(|(index, _)| index) {
+            Some(index) if index == runs.len() => {
+                std::mem::replace(&mut last_head, last_run.pop_front()).unwrap()
+            }
+            Some(index) => match runs[index].next(&data_type).await {
+                Ok(next) => std::mem::replace(&mut heads[index], next).unwrap(),
+                Err(err) => return spill_failure(err),
+            },
+            None => break,
+        };
+
+        check!(sorted.send_one(value).await)
+    }
+
+    ResultStatus::Ok
+}
+
+fn spill_failure(err: std::io::Error) -> ResultStatus {
+    ResultStatus::Error(format!("external sort failed on spilled values: {err}"))
+}
+
+/// Gives the greatest values of a stream.
+///
+/// The whole stream is consumed, and then the `k` greatest values are sent through `top` in descending order,
+/// or the `k` lowest values in ascending order if `lowest` is `true`.
+/// Only `k` values are kept in memory at once.
+///
+/// ℹ️ If stream contains less than `k` values, all of them are sent.
+///
+/// ```mermaid
+/// graph LR
+///     T("topK(k=2)")
+///     V["3️⃣ 1️⃣ 4️⃣ 1️⃣ 5️⃣"] -->|stream| T
+///
+///     T -->|top| O["5️⃣ 4️⃣"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T (Order)
+    default lowest false
+    input stream Stream<T>
+    output top Stream<T>
+)]
+pub async fn top_k(k: u64, lowest: bool) {
+    if k == 0 {
+        return;
+    }
+
+    let k = k as usize;
+    // Kept in output order, best value first.
+    let mut best: Vec<Value> = Vec::new();
+    let ranks = |a: &Value, b: &Value| {
+        if lowest {
+            compare(a, b)
+        } else {
+            compare(b, a)
+        }
+    };
+    while let Ok(values) = stream
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            if best.len() == k && ranks(&val, &best[k - 1]) != Ordering::Less {
+                continue;
+            }
+            let position = best.partition_point(|kept| ranks(kept, &val) != Ordering::Greater);
+            best.insert(position, val);
+            best.truncate(k);
+        }
+    }
+
+    if !best.is_empty() {
+        let _ = top.send_many(TransmissionValue::Other(best.into())).await;
+    }
+}
@@ -0,0 +1,196 @@
+use async_std::{channel::unbounded, future::timeout};
+use melodium_core::*;
+use melodium_macro::{check, mel_treatment};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Maximal number of values memory is reserved for ahead, whatever size of windows is.
+const RESERVED_CAPACITY: usize = 1024;
+
+/// Group a stream into vectors of fixed size.
+///
+/// Values are gathered in order into vectors containing `size` elements each.
+///
+/// ℹ️ When `stream` ends, remaining values are sent as a last, smaller, vector.
+/// If `size` is `0`, nothing is emitted.
+///
+/// ```mermaid
+/// graph LR
+///     T("chunk(size=3)")
+///     V["🟦 🟦 🟦 🟦 🟦 🟦 🟦 🟦"] -->|stream| T
+///     
+///     T -->|chunks| O["［🟦 🟦 🟦］［🟦 🟦 🟦］［🟦 🟦］"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T ()
+    input stream Stream<T>
+    output chunks Stream<Vec<T>>
+)]
+pub async fn chunk(size: u64) {
+    if size == 0 {
+        return;
+    }
+
+    let size = size as usize;
+    let mut current = Vec::with_capacity(size.min(RESERVED_CAPACITY));
+    'main: while let Ok(values) = stream
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            current.push(val);
+            if current.len() == size {
+                let full = std::mem::replace(
+                    &mut current,
+                    Vec::with_capacity(size.min(RESERVED_CAPACITY)),
+                );
+                check!('main, chunks.send_one(full.into()).await)
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        let _ = chunks.send_one(current.into()).await;
+    }
+}
+
+/// Group a stream into vectors by tumbling time windows.
+///
+/// Time is split into consecutive, non-overlapping, windows of `duration` milliseconds,
+/// starting when the treatment starts. All values received during a window are sent as one vector when it ends.
+///
+/// ℹ️ Windows during which no value were received are not emitted.
+/// When `stream` ends, values of the current window are sent immediately.
+/// If `duration` is `0`, nothing is emitted.
+///
+/// ```mermaid
+/// graph LR
+///     T("tumbling(duration=1000)")
+///     V["🟦 🟦 ⏱️ 🟦 ⏱️ 🟦 🟦 🟦"] -->|stream| T
+///     
+///     T -->|windows| O["［🟦 🟦］［🟦］［🟦 🟦 🟦］"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T ()
+    input stream Stream<T>
+    output windows Stream<Vec<T>>
+)]
+pub async fn tumbling(duration: u64) {
+    if duration == 0 {
+        return;
+    }
+
+    let duration = Duration::from_millis(duration);
+    let (sender, receiver) = unbounded();
+
+    let receiving = async {
+        let sender = sender;
+        while let Ok(values) = stream.recv_many().await {
+            check!(sender.send(values).await)
+        }
+    };
+    let sending = async {
+        let receiver = receiver;
+        // Window end too far to be represented is never reached.
+        let mut window_end = Instant::now().checked_add(duration);
+        let mut current: Vec<Value> = Vec::new();
+        loop {
+            let now = Instant::now();
+            if window_end.is_some_and(|window_end| now >= window_end) {
+                if !current.is_empty() {
+                    check!(windows.send_one(std::mem::take(&mut current).into()).await)
+                }
+                while let Some(end) = window_end.filter(|window_end| *window_end <= now) {
+                    window_end = end.checked_add(duration);
+                }
+            }
+
+            let remaining = window_end.map_or(duration, |window_end| {
+                window_end.saturating_duration_since(now)
+            });
+            match timeout(remaining, receiver.recv()).await {
+                Ok(Ok(values)) => current.extend(Into::<VecDeque<Value>>::into(values)),
+                Ok(Err(_)) => {
+                    if !current.is_empty() {
+                        let _ = windows.send_one(current.into()).await;
+                    }
+                    break;
+                }
+                Err(_) => {}
+            }
+        }
+    };
+
+    futures::join!(receiving, sending);
+}
+
+/// Group a stream into sliding windows.
+///
+/// Each window is a vector of `size` consecutive values, and a new window starts every `step` values.
+/// When `step` is lower than `size` windows overlap, when it is greater some values are not part of any window.
+///
+/// ℹ️ Only complete windows are emitted, remaining values at stream end that cannot fill a window are discarded.
+/// If `size` or `step` is `0`, nothing is emitted.
+///
+/// ```mermaid
+/// graph LR
+///     T("sliding(size=3, step=1)")
+///     V["🟦 🟧 🟪 🟫 🟨"] -->|stream| T
+///     
+///     T -->|windows| O["［🟦 🟧 🟪］［🟧 🟪 🟫］［🟪 🟫 🟨］"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style O fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T ()
+    input stream Stream<T>
+    output windows Stream<Vec<T>>
+)]
+pub async fn sliding(size: u64, step: u64) {
+    if size == 0 || step == 0 {
+        return;
+    }
+
+    let size = size as usize;
+    let step = step as usize;
+    let mut current: VecDeque<Value> = VecDeque::with_capacity(size.min(RESERVED_CAPACITY));
+    // Number of values still to be skipped before filling next window, when `step` is greater than `size`.
+    let mut skip = 0usize;
+    'main: while let Ok(values) = stream
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+
+            current.push_back(val);
+            if current.len() == size {
+                check!(
+                    'main,
+                    windows
+                        .send_one(current.iter().cloned().collect::<Vec<_>>().into())
+                        .await
+                );
+
+                if step < size {
+                    current.drain(..step);
+                } else {
+                    current.clear();
+                    skip = step - size;
+                }
+            }
+        }
+    }
+}
@@ -14,7 +14,9 @@ pub mod data;
 pub mod engine;
 pub mod flow;
 pub mod ops;
+pub mod test;
 pub mod text;
+pub mod time;
 pub mod types;
 
 mel_package!();
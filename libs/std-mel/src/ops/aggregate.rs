@@ -0,0 +1,374 @@
+use melodium_core::{common::executive::ResultStatus, *};
+use melodium_macro::{check, mel_treatment};
+
+/// Sum all values of a stream.
+///
+/// Values passed through `value` are added together, and `sum` is emitted once stream is over.
+/// Overflow is checked for integer types: if total gets out of bounds for the data type, nothing is emitted and the treatment fails.
+///
+/// ℹ️ If `value` never transmit any data, nothing is emitted.
+#[mel_treatment(
+    generic N (Add)
+    input value Stream<N>
+    output sum Block<N>
+)]
+pub async fn sum() {
+    let mut total: Option<Value> = None;
+    while let Ok(values) = value
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            total = Some(match total {
+                Some(total) => match checked_add(&total, &val) {
+                    Some(total) => total,
+                    None => return overflow(&total),
+                },
+                None => val,
+            });
+        }
+    }
+
+    if let Some(total) = total {
+        let _ = sum.send_one(total).await;
+    }
+
+    ResultStatus::Ok
+}
+
+/// Multiply all values of a stream.
+///
+/// Values passed through `value` are multiplied together, and `product` is emitted once stream is over.
+/// Overflow is checked for integer types: if total gets out of bounds for the data type, nothing is emitted and the treatment fails.
+///
+/// ℹ️ If `value` never transmit any data, nothing is emitted.
+#[mel_treatment(
+    generic N (Mul)
+    input value Stream<N>
+    output product Block<N>
+)]
+pub async fn product() {
+    let mut total: Option<Value> = None;
+    while let Ok(values) = value
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            total = Some(match total {
+                Some(total) => match checked_mul(&total, &val) {
+                    Some(total) => total,
+                    None => return overflow(&total),
+                },
+                None => val,
+            });
+        }
+    }
+
+    if let Some(total) = total {
+        let _ = product.send_one(total).await;
+    }
+
+    ResultStatus::Ok
+}
+
+/// Gives the minimum value of a stream.
+///
+/// `min` is emitted once stream is over, with the lowest value that passed through `value`.
+///
+/// ℹ️ If `value` never transmit any data, nothing is emitted.
+/// Values that cannot be compared (such as `NaN` for floating types) are ignored, unless they come first.
+#[mel_treatment(
+    generic N (PartialOrder)
+    input value Stream<N>
+    output min Block<N>
+)]
+pub async fn min() {
+    let mut minimum: Option<Value> = None;
+    while let Ok(values) = value
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            minimum = Some(match minimum {
+                Some(minimum) if !val.partial_order_lt(&minimum) => minimum,
+                _ => val,
+            });
+        }
+    }
+
+    if let Some(minimum) = minimum {
+        let _ = min.send_one(minimum).await;
+    }
+}
+
+/// Gives the maximum value of a stream.
+///
+/// `max` is emitted once stream is over, with the greatest value that passed through `value`.
+///
+/// ℹ️ If `value` never transmit any data, nothing is emitted.
+/// Values that cannot be compared (such as `NaN` for floating types) are ignored, unless they come first.
+#[mel_treatment(
+    generic N (PartialOrder)
+    input value Stream<N>
+    output max Block<N>
+)]
+pub async fn max() {
+    let mut maximum: Option<Value> = None;
+    while let Ok(values) = value
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            maximum = Some(match maximum {
+                Some(maximum) if !val.partial_order_gt(&maximum) => maximum,
+                _ => val,
+            });
+        }
+    }
+
+    if let Some(maximum) = maximum {
+        let _ = max.send_one(maximum).await;
+    }
+}
+
+/// Gives the arithmetic mean of a stream.
+///
+/// Values passed through `value` are averaged, and `mean` is emitted once stream is over.
+///
+/// ℹ️ If `value` never transmit any data, nothing is emitted.
+#[mel_treatment(
+    generic F (Float)
+    input value Stream<F>
+    output mean Block<F>
+)]
+pub async fn mean() {
+    let mut total: Option<Value> = None;
+    let mut count = 0u64;
+    while let Ok(values) = value
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        for val in values {
+            total = Some(match total {
+                Some(total) => total.add(&val),
+                None => val,
+            });
+            count += 1;
+        }
+    }
+
+    if let Some(total) = total {
+        let count = match total {
+            Value::F32(_) => Value::F32(count as f32),
+            _ => Value::F64(count as f64),
+        };
+        let _ = mean.send_one(total.div(&count)).await;
+    }
+}
+
+/// Gives the running sum of a stream.
+///
+/// For each value passed through `value`, the sum of all values received so far is sent in `sum`.
+/// Overflow is checked for integer types: if total gets out of bounds for the data type, `sum` is closed and the treatment fails.
+///
+/// ```mermaid
+/// graph LR
+///     T("runningSum()")
+///     V["1️⃣ 2️⃣ 3️⃣ …"] -->|value| T
+///     
+///     T -->|sum| P["1️⃣ 3️⃣ 6️⃣ …"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style P fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic N (Add)
+    input value Stream<N>
+    output sum Stream<N>
+)]
+pub async fn running_sum() {
+    let mut total: Option<Value> = None;
+    while let Ok(values) = value
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        let mut totals = VecDeque::with_capacity(values.len());
+        let mut overflowed = None;
+        for val in values {
+            let new_total = match total.take() {
+                Some(total) => match checked_add(&total, &val) {
+                    Some(total) => total,
+                    None => {
+                        overflowed = Some(total);
+                        break;
+                    }
+                },
+                None => val,
+            };
+            total = Some(new_total.clone());
+            totals.push_back(new_total);
+        }
+
+        if !totals.is_empty() {
+            check!(sum.send_many(TransmissionValue::Other(totals)).await)
+        }
+        if let Some(total) = overflowed {
+            return overflow(&total);
+        }
+    }
+
+    ResultStatus::Ok
+}
+
+/// Gives the running product of a stream.
+///
+/// For each value passed through `value`, the product of all values received so far is sent in `product`.
+/// Overflow is checked for integer types: if total gets out of bounds for the data type, `product` is closed and the treatment fails.
+#[mel_treatment(
+    generic N (Mul)
+    input value Stream<N>
+    output product Stream<N>
+)]
+pub async fn running_product() {
+    let mut total: Option<Value> = None;
+    while let Ok(values) = value
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        let mut totals = VecDeque::with_capacity(values.len());
+        let mut overflowed = None;
+        for val in values {
+            let new_total = match total.take() {
+                Some(total) => match checked_mul(&total, &val) {
+                    Some(total) => total,
+                    None => {
+                        overflowed = Some(total);
+                        break;
+                    }
+                },
+                None => val,
+            };
+            total = Some(new_total.clone());
+            totals.push_back(new_total);
+        }
+
+        if !totals.is_empty() {
+            check!(product.send_many(TransmissionValue::Other(totals)).await)
+        }
+        if let Some(total) = overflowed {
+            return overflow(&total);
+        }
+    }
+
+    ResultStatus::Ok
+}
+
+/// Gives the running minimum of a stream.
+///
+/// For each value passed through `value`, the lowest value received so far is sent in `min`.
+///
+/// ℹ️ Values that cannot be compared (such as `NaN` for floating types) are ignored, unless they come first.
+#[mel_treatment(
+    generic N (PartialOrder)
+    input value Stream<N>
+    output min Stream<N>
+)]
+pub async fn running_min() {
+    let mut minimum: Option<Value> = None;
+    while let Ok(values) = value
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        check!(
+            min.send_many(TransmissionValue::Other(
+                values
+                    .into_iter()
+                    .map(|val| {
+                        let new_minimum = match minimum.take() {
+                            Some(minimum) if !val.partial_order_lt(&minimum) => minimum,
+                            _ => val,
+                        };
+                        minimum = Some(new_minimum.clone());
+                        new_minimum
+                    })
+                    .collect()
+            ))
+            .await
+        )
+    }
+}
+
+/// Gives the running maximum of a stream.
+///
+/// For each value passed through `value`, the greatest value received so far is sent in `max`.
+///
+/// ℹ️ Values that cannot be compared (such as `NaN` for floating types) are ignored, unless they come first.
+#[mel_treatment(
+    generic N (PartialOrder)
+    input value Stream<N>
+    output max Stream<N>
+)]
+pub async fn running_max() {
+    let mut maximum: Option<Value> = None;
+    while let Ok(values) = value
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        check!(
+            max.send_many(TransmissionValue::Other(
+                values
+                    .into_iter()
+                    .map(|val| {
+                        let new_maximum = match maximum.take() {
+                            Some(maximum) if !val.partial_order_gt(&maximum) => maximum,
+                            _ => val,
+                        };
+                        maximum = Some(new_maximum.clone());
+                        new_maximum
+                    })
+                    .collect()
+            ))
+            .await
+        )
+    }
+}
+
+/// Adds `val` to `total`, giving `None` on overflow for types implementing `CheckedAdd`.
+fn checked_add(total: &Value, val: &Value) -> Option<Value> {
+    if total
+        .datatype()
+        .implements(&melodium_core::common::descriptor::DataTrait::CheckedAdd)
+    {
+        total.checked_add(val)
+    } else {
+        Some(total.add(val))
+    }
+}
+
+/// Multiplies `total` by `val`, giving `None` on overflow for types implementing `CheckedMul`.
+fn checked_mul(total: &Value, val: &Value) -> Option<Value> {
+    if total
+        .datatype()
+        .implements(&melodium_core::common::descriptor::DataTrait::CheckedMul)
+    {
+        total.checked_mul(val)
+    } else {
+        Some(total.mul(val))
+    }
+}
+
+fn overflow(total: &Value) -> ResultStatus {
+    ResultStatus::Error(format!(
+        "aggregation of `{total}` overflowed {} bounds",
+        total.datatype()
+    ))
+}
@@ -1,6 +1,7 @@
 use melodium_core::*;
 use melodium_macro::{check, mel_function, mel_treatment};
 
+pub mod aggregate;
 pub mod bin;
 pub mod float;
 pub mod num;
@@ -0,0 +1,142 @@
+use melodium_core::{common::executive::ResultStatus, *};
+use melodium_macro::mel_treatment;
+
+/// Assert that `a` is equal to `b`.
+///
+/// When both `a` and `b` are received, they are compared, and the track fails if they differ.
+/// The track also fails if `a` or `b` gets closed without receiving any value.
+/// `message`, if not empty, is given as prefix of the failure reason.
+///
+/// Once equality is asserted, `checked` is emitted.
+///
+/// ```mermaid
+/// graph LR
+///     T("assertEq()")
+///     A["〈🟨〉"] -->|a| T
+///     B["〈🟨〉"] -->|b| T
+///
+///     T -->|checked| S["〈🟦〉"]
+///
+///     style A fill:#ffffff,stroke:#ffffff
+///     style B fill:#ffffff,stroke:#ffffff
+///     style S fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T (PartialEquality)
+    input a Block<T>
+    input b Block<T>
+    output checked Block<void>
+    default message ""
+)]
+pub async fn assert_eq(message: string) {
+    match (a.recv_one().await, b.recv_one().await) {
+        (Ok(a), Ok(b)) if a.partial_equality_eq(&b) => {
+            let _ = checked.send_one(().into()).await;
+            ResultStatus::Ok
+        }
+        (Ok(a), Ok(b)) => failure(&message, format!("`{a}` is not equal to `{b}`")),
+        (Err(_), Ok(_)) => failure(&message, "`a` closed without value".to_string()),
+        (Ok(_), Err(_)) => failure(&message, "`b` closed without value".to_string()),
+        (Err(_), Err(_)) => failure(&message, "`a` and `b` closed without value".to_string()),
+    }
+}
+
+/// Assert that streams `a` and `b` are equal.
+///
+/// Values of `a` and `b` are compared one by one, and the track fails at the first pair that differ.
+/// The track also fails if one of the streams ends before the other one.
+/// `message`, if not empty, is given as prefix of the failure reason.
+///
+/// Once both streams are closed and found equal, `checked` is emitted.
+///
+/// ```mermaid
+/// graph LR
+///     T("assertStreamEq()")
+///     A["🟨 🟨 🟨"] -->|a| T
+///     B["🟨 🟨 🟨"] -->|b| T
+///
+///     T -->|checked| S["〈🟦〉"]
+///
+///     style A fill:#ffffff,stroke:#ffffff
+///     style B fill:#ffffff,stroke:#ffffff
+///     style S fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T (PartialEquality)
+    input a Stream<T>
+    input b Stream<T>
+    output checked Block<void>
+    default message ""
+)]
+pub async fn assert_stream_eq(message: string) {
+    let mut index: usize = 0;
+    loop {
+        match (a.recv_one().await, b.recv_one().await) {
+            (Ok(a), Ok(b)) if a.partial_equality_eq(&b) => {}
+            (Ok(a), Ok(b)) => {
+                return failure(
+                    &message,
+                    format!("`{a}` is not equal to `{b}` at position {index}"),
+                )
+            }
+            (Ok(a), Err(_)) => {
+                return failure(
+                    &message,
+                    format!("`b` ended at position {index}, while `a` continues with `{a}`"),
+                )
+            }
+            (Err(_), Ok(b)) => {
+                return failure(
+                    &message,
+                    format!("`a` ended at position {index}, while `b` continues with `{b}`"),
+                )
+            }
+            (Err(_), Err(_)) => break,
+        }
+        index += 1;
+    }
+
+    let _ = checked.send_one(().into()).await;
+    ResultStatus::Ok
+}
+
+/// Expect `value` stream to be closed without receiving anything.
+///
+/// The track fails as soon as any value is received, and `closed` is emitted if stream ends empty.
+/// `message`, if not empty, is given as prefix of the failure reason.
+///
+/// ```mermaid
+/// graph LR
+///     T("expectClosed()")
+///     V["🟨 🟨 🟨"] -->|value| T
+///
+///     T -->|closed| S["〈🟦〉"]
+///
+///     style V fill:#ffffff,stroke:#ffffff
+///     style S fill:#ffffff,stroke:#ffffff
+/// ```
+#[mel_treatment(
+    generic T ()
+    input value Stream<T>
+    output closed Block<void>
+    default message ""
+)]
+pub async fn expect_closed(message: string) {
+    if let Ok(value) = value.recv_one().await {
+        failure(
+            &message,
+            format!("expected closed stream, received `{value}`"),
+        )
+    } else {
+        let _ = closed.send_one(().into()).await;
+        ResultStatus::Ok
+    }
+}
+
+fn failure(message: &str, reason: String) -> ResultStatus {
+    if message.is_empty() {
+        ResultStatus::Error(reason)
+    } else {
+        ResultStatus::Error(format!("{message}: {reason}"))
+    }
+}
@@ -0,0 +1,451 @@
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use melodium_core::{executive::*, *};
+use melodium_macro::{check, mel_data, mel_function, mel_treatment};
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// A point in time, in UTC.
+///
+/// `Timestamp` values are ordered chronologically, and can be compared with `std/ops` comparison elements.
+/// `ToString` and `Display` give the RFC 3339 representation, such as `"2024-06-01T12:30:00Z"`.
+/// Use `|now()`, `|from_rfc3339(…)`, `|parse(…)` or `|from_timestamp(…)` to obtain a value.
+#[mel_data(
+    traits (ToString TryToString Display Serialize Deserialize PartialEquality Equality PartialOrder Order)
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Timestamp(pub DateTime<Utc>);
+
+impl ToString for Timestamp {
+    fn to_string(&self) -> string {
+        self.0.to_rfc3339()
+    }
+}
+
+impl TryToString for Timestamp {
+    fn try_to_string(&self) -> Option<string> {
+        Some(self.0.to_rfc3339())
+    }
+}
+
+impl Display for Timestamp {
+    fn display(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{}", self.0.to_rfc3339())
+    }
+}
+
+/// A signed span of time, with nanosecond precision.
+///
+/// `Duration` values are ordered from the most negative to the most positive, and can be compared with `std/ops` comparison elements.
+/// `ToString` and `Display` give the ISO 8601 representation, such as `"PT90.5S"`.
+/// Use `|milliseconds(…)`, `|seconds(…)` or `|elapsed(…)` to obtain a value, and `|add_durations(…)` or `|sub_durations(…)` to combine them.
+#[mel_data(
+    traits (ToString TryToString Display Serialize Deserialize PartialEquality Equality PartialOrder Order)
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "DurationParts", into = "DurationParts")]
+pub struct Duration(pub TimeDelta);
+
+/// Serialized form of `Duration`, as chrono does not provide one.
+#[derive(Serialize, Deserialize)]
+struct DurationParts {
+    seconds: i64,
+    nanoseconds: i32,
+}
+
+impl From<Duration> for DurationParts {
+    fn from(value: Duration) -> Self {
+        Self {
+            seconds: value.0.num_seconds(),
+            nanoseconds: value.0.subsec_nanos(),
+        }
+    }
+}
+
+impl From<DurationParts> for Duration {
+    fn from(value: DurationParts) -> Self {
+        let bound = if value.seconds < 0 {
+            TimeDelta::MIN
+        } else {
+            TimeDelta::MAX
+        };
+        Self(
+            TimeDelta::try_seconds(value.seconds)
+                .and_then(|seconds| {
+                    seconds.checked_add(&TimeDelta::nanoseconds(value.nanoseconds as i64))
+                })
+                .unwrap_or(bound),
+        )
+    }
+}
+
+impl ToString for Duration {
+    fn to_string(&self) -> string {
+        self.0.to_string()
+    }
+}
+
+impl TryToString for Duration {
+    fn try_to_string(&self) -> Option<string> {
+        Some(self.0.to_string())
+    }
+}
+
+impl Display for Duration {
+    fn display(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn parse_timestamp(text: &str, format: &str) -> Option<Timestamp> {
+    match DateTime::parse_from_str(text, format) {
+        Ok(datetime) => Some(Timestamp(datetime.with_timezone(&Utc))),
+        Err(_) => NaiveDateTime::parse_from_str(text, format)
+            .ok()
+            .map(|datetime| Timestamp(datetime.and_utc())),
+    }
+}
+
+fn format_timestamp(timestamp: &Timestamp, format: &str) -> Option<string> {
+    let mut text = String::new();
+    write!(text, "{}", timestamp.0.format(format)).ok()?;
+    Some(text)
+}
+
+/// Return the current time.
+///
+/// ℹ️ Value is taken when function is called, during design of the program.
+/// Use `now` treatment to get time at execution.
+#[mel_function]
+pub fn now() -> Timestamp {
+    Timestamp(Utc::now())
+}
+
+/// Emit the current time.
+///
+/// When `trigger` is received, the time at that moment is emitted through `now`.
+#[mel_treatment(
+    input trigger Block<void>
+    output now Block<Timestamp>
+)]
+pub async fn now() {
+    if let Ok(_) = trigger.recv_one().await {
+        let _ = now
+            .send_one(Value::Data(Arc::new(Timestamp(Utc::now()))))
+            .await;
+    }
+}
+
+/// Parse a RFC 3339 timestamp, such as `"2024-06-01T14:30:00+02:00"`.
+///
+/// Returns `none` if `text` is not a valid RFC 3339 timestamp.
+#[mel_function]
+pub fn from_rfc3339(text: string) -> Option<Timestamp> {
+    DateTime::parse_from_rfc3339(&text)
+        .ok()
+        .map(|datetime| Timestamp(datetime.with_timezone(&Utc)))
+}
+
+/// Parse a stream of RFC 3339 timestamps.
+///
+/// Emits `none` for each element that is not a valid RFC 3339 timestamp.
+#[mel_treatment(
+    input text Stream<string>
+    output timestamp Stream<Option<Timestamp>>
+)]
+pub async fn from_rfc3339() {
+    while let Ok(texts) = text
+        .recv_many()
+        .await
+        .map(|values| TryInto::<Vec<string>>::try_into(values).unwrap())
+    {
+        check!(
+            timestamp
+                .send_many(TransmissionValue::Other(
+                    texts
+                        .into_iter()
+                        .map(
+                            |text| Value::Option(DateTime::parse_from_rfc3339(&text).ok().map(
+                                |datetime| {
+                                    Box::new(Value::Data(Arc::new(Timestamp(
+                                        datetime.with_timezone(&Utc),
+                                    ))))
+                                }
+                            ))
+                        )
+                        .collect()
+                ))
+                .await
+        )
+    }
+}
+
+/// Format a timestamp as RFC 3339 text, in UTC.
+#[mel_function]
+pub fn to_rfc3339(timestamp: Timestamp) -> string {
+    timestamp.0.to_rfc3339()
+}
+
+/// Format a stream of timestamps as RFC 3339 text, in UTC.
+#[mel_treatment(
+    input timestamp Stream<Timestamp>
+    output text Stream<string>
+)]
+pub async fn to_rfc3339() {
+    while let Ok(timestamps) = timestamp
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        check!(
+            text.send_many(TransmissionValue::String(
+                timestamps
+                    .into_iter()
+                    .map(|ts| GetData::<Arc<dyn Data>>::try_data(ts)
+                        .unwrap()
+                        .downcast_arc::<Timestamp>()
+                        .unwrap()
+                        .0
+                        .to_rfc3339())
+                    .collect()
+            ))
+            .await
+        )
+    }
+}
+
+/// Parse a timestamp according to a strftime-like `format`, such as `"%Y-%m-%d %H:%M:%S"`.
+///
+/// If `format` contains timezone specification (`%z`, `%:z`…) the offset is taken into account,
+/// else the timestamp is considered to be in UTC.
+/// Returns `none` if `text` does not match `format`, or does not describe a full date and time.
+#[mel_function]
+pub fn parse(text: string, format: string) -> Option<Timestamp> {
+    parse_timestamp(&text, &format)
+}
+
+/// Parse a stream of timestamps according to a strftime-like `format`.
+///
+/// See `|parse(…)` for details.
+/// Emits `none` for each element that does not match `format`.
+#[mel_treatment(
+    input text Stream<string>
+    output timestamp Stream<Option<Timestamp>>
+)]
+pub async fn parse(format: string) {
+    while let Ok(texts) = text
+        .recv_many()
+        .await
+        .map(|values| TryInto::<Vec<string>>::try_into(values).unwrap())
+    {
+        check!(
+            timestamp
+                .send_many(TransmissionValue::Other(
+                    texts
+                        .into_iter()
+                        .map(|text| Value::Option(
+                            parse_timestamp(&text, &format)
+                                .map(|ts| Box::new(Value::Data(Arc::new(ts))))
+                        ))
+                        .collect()
+                ))
+                .await
+        )
+    }
+}
+
+/// Format a timestamp according to a strftime-like `format`, such as `"%Y-%m-%d %H:%M:%S"`.
+///
+/// Timestamp is formatted in UTC.
+/// Returns `none` if `format` is invalid.
+#[mel_function]
+pub fn format(timestamp: Timestamp, format: string) -> Option<string> {
+    format_timestamp(&timestamp, &format)
+}
+
+/// Format a stream of timestamps according to a strftime-like `format`.
+///
+/// See `|format(…)` for details.
+/// Emits `none` for each element if `format` is invalid.
+#[mel_treatment(
+    input timestamp Stream<Timestamp>
+    output text Stream<Option<string>>
+)]
+pub async fn format(format: string) {
+    while let Ok(timestamps) = timestamp
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        check!(
+            text.send_many(TransmissionValue::Other(
+                timestamps
+                    .into_iter()
+                    .map(|ts| format_timestamp(
+                        &GetData::<Arc<dyn Data>>::try_data(ts)
+                            .unwrap()
+                            .downcast_arc::<Timestamp>()
+                            .unwrap(),
+                        &format
+                    )
+                    .into())
+                    .collect()
+            ))
+            .await
+        )
+    }
+}
+
+/// Build a timestamp from a number of seconds since Unix epoch.
+///
+/// Returns `none` if `seconds` is out of representable range.
+#[mel_function]
+pub fn from_timestamp(seconds: i64) -> Option<Timestamp> {
+    DateTime::from_timestamp(seconds, 0).map(|datetime| Timestamp(datetime))
+}
+
+/// Build a timestamp from a number of milliseconds since Unix epoch.
+///
+/// Returns `none` if `milliseconds` is out of representable range.
+#[mel_function]
+pub fn from_timestamp_millis(milliseconds: i64) -> Option<Timestamp> {
+    DateTime::from_timestamp_millis(milliseconds).map(|datetime| Timestamp(datetime))
+}
+
+/// Give the number of whole seconds since Unix epoch.
+#[mel_function]
+pub fn timestamp(timestamp: Timestamp) -> i64 {
+    timestamp.0.timestamp()
+}
+
+/// Give the number of milliseconds since Unix epoch.
+#[mel_function]
+pub fn timestamp_millis(timestamp: Timestamp) -> i64 {
+    timestamp.0.timestamp_millis()
+}
+
+/// Build timestamps from numbers of milliseconds since Unix epoch.
+///
+/// Emits `none` for each element that is out of representable range.
+#[mel_treatment(
+    input milliseconds Stream<i64>
+    output timestamp Stream<Option<Timestamp>>
+)]
+pub async fn from_timestamp_millis() {
+    while let Ok(millis) = milliseconds
+        .recv_many()
+        .await
+        .map(|values| TryInto::<Vec<i64>>::try_into(values).unwrap())
+    {
+        check!(
+            timestamp
+                .send_many(TransmissionValue::Other(
+                    millis
+                        .into_iter()
+                        .map(|millis| Value::Option(
+                            DateTime::from_timestamp_millis(millis).map(|datetime| Box::new(
+                                Value::Data(Arc::new(Timestamp(datetime)))
+                            ))
+                        ))
+                        .collect()
+                ))
+                .await
+        )
+    }
+}
+
+/// Give numbers of milliseconds since Unix epoch for a stream of timestamps.
+#[mel_treatment(
+    input timestamp Stream<Timestamp>
+    output milliseconds Stream<i64>
+)]
+pub async fn timestamp_millis() {
+    while let Ok(timestamps) = timestamp
+        .recv_many()
+        .await
+        .map(|values| Into::<VecDeque<Value>>::into(values))
+    {
+        check!(
+            milliseconds
+                .send_many(TransmissionValue::I64(
+                    timestamps
+                        .into_iter()
+                        .map(|ts| GetData::<Arc<dyn Data>>::try_data(ts)
+                            .unwrap()
+                            .downcast_arc::<Timestamp>()
+                            .unwrap()
+                            .0
+                            .timestamp_millis())
+                        .collect()
+                ))
+                .await
+        )
+    }
+}
+
+/// Add `duration` to `timestamp`.
+///
+/// Returns `none` if result is out of representable range.
+#[mel_function]
+pub fn add(timestamp: Timestamp, duration: Duration) -> Option<Timestamp> {
+    timestamp.0.checked_add_signed(duration.0).map(Timestamp)
+}
+
+/// Substract `duration` from `timestamp`.
+///
+/// Returns `none` if result is out of representable range.
+#[mel_function]
+pub fn sub(timestamp: Timestamp, duration: Duration) -> Option<Timestamp> {
+    timestamp.0.checked_sub_signed(duration.0).map(Timestamp)
+}
+
+/// Give the duration elapsed from `since` to `until`.
+///
+/// Duration is negative if `until` is before `since`.
+#[mel_function]
+pub fn elapsed(since: Timestamp, until: Timestamp) -> Duration {
+    Duration(until.0 - since.0)
+}
+
+/// Build a duration from a number of milliseconds.
+///
+/// Returns `none` if `milliseconds` is out of representable range.
+#[mel_function]
+pub fn milliseconds(milliseconds: i64) -> Option<Duration> {
+    TimeDelta::try_milliseconds(milliseconds).map(Duration)
+}
+
+/// Build a duration from a number of seconds.
+///
+/// Returns `none` if `seconds` is out of representable range.
+#[mel_function]
+pub fn seconds(seconds: i64) -> Option<Duration> {
+    TimeDelta::try_seconds(seconds).map(Duration)
+}
+
+/// Add durations `a` and `b`.
+///
+/// Returns `none` if result is out of representable range.
+#[mel_function]
+pub fn add_durations(a: Duration, b: Duration) -> Option<Duration> {
+    a.0.checked_add(&b.0).map(Duration)
+}
+
+/// Substract duration `b` from `a`.
+///
+/// Returns `none` if result is out of representable range.
+#[mel_function]
+pub fn sub_durations(a: Duration, b: Duration) -> Option<Duration> {
+    a.0.checked_sub(&b.0).map(Duration)
+}
+
+/// Give the total number of whole milliseconds in `duration`.
+#[mel_function]
+pub fn as_milliseconds(duration: Duration) -> i64 {
+    duration.0.num_milliseconds()
+}
+
+/// Give the total number of whole seconds in `duration`.
+#[mel_function]
+pub fn as_seconds(duration: Duration) -> i64 {
+    duration.0.num_seconds()
+}
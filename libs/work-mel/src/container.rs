@@ -7,7 +7,7 @@ use async_std::{
 };
 use async_trait::async_trait;
 use async_walkdir::{Filtering, WalkDir};
-use fs_mel::filesystem::{self, FileSystemEngine};
+use fs_mel::filesystem::{self, FileSystemEngine, VolumeFileSystem};
 use futures::{AsyncReadExt, AsyncWriteExt, StreamExt};
 use melodium_macro::check;
 use process_mel::{
@@ -618,7 +618,7 @@ impl ExecutorEngine for ContainerExecutor {
 pub struct ContainerFileSystem {
     #[allow(unused)]
     volume: String,
-    path: PathBuf,
+    volume_filesystem: VolumeFileSystem,
 }
 
 #[cfg(feature = "real")]
@@ -634,7 +634,7 @@ impl ContainerFileSystem {
         if let Ok(path) = std::env::var(format!("MELODIUM_RUN_VOLUME_{volume}")) {
             Ok(Self {
                 volume,
-                path: path.into(),
+                volume_filesystem: VolumeFileSystem::new(path.into()),
             })
         } else {
             return Err(format!("No volume '{volume}' available"));
@@ -642,16 +642,7 @@ impl ContainerFileSystem {
     }
 
     async fn full_path(&self, path: &Path) -> async_std::io::Result<PathBuf> {
-        let full_path = self.path.join(path);
-
-        if full_path.starts_with(&self.path) {
-            Ok(full_path)
-        } else {
-            Err(async_std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                "file not found",
-            ))
-        }
+        self.volume_filesystem.full_path(path)
     }
 }
 
@@ -923,4 +914,87 @@ impl FileSystemEngine for ContainerFileSystem {
             let _ = finished().await;
         }
     }
+    async fn remove_file(
+        &self,
+        path: &str,
+        success: filesystem::OnceTriggerCall<'async_trait>,
+        failed: filesystem::OnceTriggerCall<'async_trait>,
+        error: filesystem::OnceMessageCall<'async_trait>,
+    ) {
+        self.volume_filesystem
+            .remove_file(path, success, failed, error)
+            .await
+    }
+    async fn remove_dir(
+        &self,
+        path: &str,
+        recursive: bool,
+        success: filesystem::OnceTriggerCall<'async_trait>,
+        failed: filesystem::OnceTriggerCall<'async_trait>,
+        error: filesystem::OnceMessageCall<'async_trait>,
+    ) {
+        self.volume_filesystem
+            .remove_dir(path, recursive, success, failed, error)
+            .await
+    }
+    async fn rename(
+        &self,
+        from: &str,
+        to: &str,
+        success: filesystem::OnceTriggerCall<'async_trait>,
+        failed: filesystem::OnceTriggerCall<'async_trait>,
+        error: filesystem::OnceMessageCall<'async_trait>,
+    ) {
+        self.volume_filesystem
+            .rename(from, to, success, failed, error)
+            .await
+    }
+    async fn copy(
+        &self,
+        from: &str,
+        to: &str,
+        success: filesystem::OnceTriggerCall<'async_trait>,
+        failed: filesystem::OnceTriggerCall<'async_trait>,
+        error: filesystem::OnceMessageCall<'async_trait>,
+    ) {
+        self.volume_filesystem
+            .copy(from, to, success, failed, error)
+            .await
+    }
+    async fn set_permissions(
+        &self,
+        path: &str,
+        mode: u32,
+        success: filesystem::OnceTriggerCall<'async_trait>,
+        failed: filesystem::OnceTriggerCall<'async_trait>,
+        error: filesystem::OnceMessageCall<'async_trait>,
+    ) {
+        self.volume_filesystem
+            .set_permissions(path, mode, success, failed, error)
+            .await
+    }
+    async fn symlink(
+        &self,
+        target: &str,
+        link: &str,
+        success: filesystem::OnceTriggerCall<'async_trait>,
+        failed: filesystem::OnceTriggerCall<'async_trait>,
+        error: filesystem::OnceMessageCall<'async_trait>,
+    ) {
+        self.volume_filesystem
+            .symlink(target, link, success, failed, error)
+            .await
+    }
+    async fn metadata(
+        &self,
+        path: &str,
+        follow_links: bool,
+        metadata: filesystem::OnceMetadataCall<'async_trait>,
+        failed: filesystem::OnceTriggerCall<'async_trait>,
+        error: filesystem::OnceMessageCall<'async_trait>,
+    ) {
+        self.volume_filesystem
+            .metadata(path, follow_links, metadata, failed, error)
+            .await
+    }
 }
@@ -1,5 +1,5 @@
 use super::{
-    Attribuable, Attributes, Context, Data, Function, Identified, Identifier,
+    Attribuable, Attributes, Constant, Context, Data, Function, Identified, Identifier,
     IdentifierRequirement, Model, Treatment,
 };
 use std::cmp::Ordering;
@@ -9,6 +9,7 @@ use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub enum Entry {
+    Constant(Arc<dyn Constant>),
     Context(Arc<dyn Context>),
     Data(Arc<dyn Data>),
     Function(Arc<dyn Function>),
@@ -19,6 +20,7 @@ pub enum Entry {
 impl Attribuable for Entry {
     fn attributes(&self) -> &Attributes {
         match self {
+            Entry::Constant(c) => c.attributes(),
             Entry::Context(c) => c.attributes(),
             Entry::Function(f) => f.attributes(),
             Entry::Model(m) => m.attributes(),
@@ -31,6 +33,7 @@ impl Attribuable for Entry {
 impl Identified for Entry {
     fn identifier(&self) -> &Identifier {
         match self {
+            Entry::Constant(c) => c.identifier(),
             Entry::Context(c) => c.identifier(),
             Entry::Function(f) => f.identifier(),
             Entry::Model(m) => m.identifier(),
@@ -41,6 +44,7 @@ impl Identified for Entry {
 
     fn make_use(&self, identifier: &Identifier) -> bool {
         match self {
+            Entry::Constant(c) => c.make_use(identifier),
             Entry::Context(c) => c.make_use(identifier),
             Entry::Function(f) => f.make_use(identifier),
             Entry::Model(m) => m.make_use(identifier),
@@ -51,6 +55,7 @@ impl Identified for Entry {
 
     fn uses(&self) -> Vec<Identifier> {
         match self {
+            Entry::Constant(c) => c.uses(),
             Entry::Context(c) => c.uses(),
             Entry::Function(f) => f.uses(),
             Entry::Model(m) => m.uses(),
@@ -63,6 +68,7 @@ impl Identified for Entry {
 impl PartialEq for Entry {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
+            (Self::Constant(l0), Self::Constant(r0)) => l0.identifier() == r0.identifier(),
             (Self::Context(l0), Self::Context(r0)) => l0.identifier() == r0.identifier(),
             (Self::Function(l0), Self::Function(r0)) => l0.identifier() == r0.identifier(),
             (Self::Model(l0), Self::Model(r0)) => l0.identifier() == r0.identifier(),
@@ -78,6 +84,9 @@ impl Eq for Entry {}
 impl PartialOrd for Entry {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self, other) {
+            (Self::Constant(l0), Self::Constant(r0)) => {
+                l0.identifier().partial_cmp(r0.identifier())
+            }
             (Self::Context(l0), Self::Context(r0)) => l0.identifier().partial_cmp(r0.identifier()),
             (Self::Function(l0), Self::Function(r0)) => {
                 l0.identifier().partial_cmp(r0.identifier())
@@ -88,13 +97,18 @@ impl PartialOrd for Entry {
                 l0.identifier().partial_cmp(r0.identifier())
             }
             (Self::Data(_), _) => Some(Ordering::Less),
-            (Self::Context(_), Self::Data(_)) => Some(Ordering::Greater),
+            (Self::Constant(_), Self::Data(_)) => Some(Ordering::Greater),
+            (Self::Constant(_), _) => Some(Ordering::Less),
+            (Self::Context(_), Self::Data(_) | Self::Constant(_)) => Some(Ordering::Greater),
             (Self::Context(_), _) => Some(Ordering::Less),
-            (Self::Function(_), Self::Data(_) | Self::Context(_)) => Some(Ordering::Greater),
-            (Self::Function(_), _) => Some(Ordering::Less),
-            (Self::Model(_), Self::Data(_) | Self::Context(_) | Self::Function(_)) => {
+            (Self::Function(_), Self::Data(_) | Self::Constant(_) | Self::Context(_)) => {
                 Some(Ordering::Greater)
             }
+            (Self::Function(_), _) => Some(Ordering::Less),
+            (
+                Self::Model(_),
+                Self::Data(_) | Self::Constant(_) | Self::Context(_) | Self::Function(_),
+            ) => Some(Ordering::Greater),
             (Self::Model(_), Self::Treatment(_)) => Some(Ordering::Less),
             (Self::Treatment(_), _) => Some(Ordering::Greater),
         }
@@ -0,0 +1,9 @@
+use super::{DescribedType, Documented, Identified};
+use crate::executive::Value;
+use core::fmt::{Debug, Display};
+
+/// Named value declared at area level, usable wherever a value is expected.
+pub trait Constant: Identified + Documented + Display + Debug + Send + Sync {
+    fn described_type(&self) -> &DescribedType;
+    fn value(&self) -> &Value;
+}
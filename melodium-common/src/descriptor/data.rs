@@ -1,12 +1,17 @@
 use crate::executive::Value;
 use erased_serde::Deserializer;
 
-use super::{DataTrait, Documented, Identified};
+use super::{DataTrait, DescribedType, Documented, Identified};
 use core::fmt::{Debug, Display};
 
 pub trait Data: Identified + Documented + Display + Debug + Send + Sync {
     fn implements(&self) -> &[DataTrait];
 
+    /// Gives the structure of the data, if it is declared in a Mélodium script.
+    fn structure(&self) -> Option<&DataStructure> {
+        None
+    }
+
     // Add here specific functions for no-value traits (such as `bounded` or `deserialize`) (see #81).
     fn bounded_min(&self) -> Value;
     fn bounded_max(&self) -> Value;
@@ -26,3 +31,12 @@ impl PartialEq for dyn Data {
         self.identifier() == other.identifier()
     }
 }
+
+/// Structure of a data type declared in a Mélodium script.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DataStructure {
+    /// Data made of named fields, in order of declaration.
+    Record(Vec<(String, DescribedType)>),
+    /// Data being one of named variants, in order of declaration.
+    Enumeration(Vec<String>),
+}
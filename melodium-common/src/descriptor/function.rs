@@ -8,7 +8,7 @@ pub trait Function:
     Identified + Documented + OrderedParameterized + DowncastSync + Display + Debug + Send + Sync
 {
     fn return_type(&self) -> &DescribedType;
-    fn function(&self) -> fn(HashMap<String, DataType>, Vec<Value>) -> Value;
+    fn function(&self) -> &(dyn Fn(HashMap<String, DataType>, Vec<Value>) -> Value + Send + Sync);
     fn as_identified(&self) -> Arc<dyn Identified>;
     fn as_ordered_parameterized(&self) -> Arc<dyn OrderedParameterized>;
 }
@@ -60,6 +60,9 @@ pub enum LoadingErrorKind {
         path: PathBuf,
         error: String,
     },
+    InvalidIdentifier {
+        identifier: String,
+    },
 }
 
 impl Display for LoadingErrorKind {
@@ -137,6 +140,9 @@ impl Display for LoadingErrorKind {
                 "Loading '{path}' failed: {error}",
                 path = path.to_string_lossy()
             ),
+            LoadingErrorKind::InvalidIdentifier { identifier } => {
+                write!(f, "'{identifier}' is not a valid identifier")
+            }
         }
     }
 }
@@ -289,6 +295,13 @@ impl LoadingError {
             kind: LoadingErrorKind::LibraryLoadingError { path, error },
         }
     }
+
+    pub fn invalid_identifier(id: u32, identifier: String) -> Self {
+        Self {
+            id,
+            kind: LoadingErrorKind::InvalidIdentifier { identifier },
+        }
+    }
 }
 
 impl Display for LoadingError {
@@ -6,6 +6,7 @@
 mod attribuable;
 mod buildable;
 mod collection;
+mod constant;
 mod context;
 mod data;
 mod data_trait;
@@ -33,8 +34,9 @@ mod variability;
 pub use attribuable::{Attribuable, Attribute, Attributes};
 pub use buildable::{Buildable, ModelBuildMode, TreatmentBuildMode};
 pub use collection::{Collection, CollectionTree, Entry};
+pub use constant::Constant;
 pub use context::Context;
-pub use data::Data;
+pub use data::{Data, DataStructure};
 pub use data_trait::DataTrait;
 pub use data_type::DataType;
 pub use described_type::DescribedType;
@@ -47,8 +49,8 @@ pub use identifier::Identifier;
 pub use identifier_requirement::IdentifierRequirement;
 pub use input::Input;
 pub use loader::{
-    ContentError, ContentErrors, Loader, LoadingError, LoadingErrors, LoadingResult,
-    RepositoryError, RepositoryErrors,
+    ContentError, ContentErrors, Loader, LoadingError, LoadingErrorKind, LoadingErrors,
+    LoadingResult, RepositoryError, RepositoryErrors,
 };
 pub use model::Model;
 pub use output::Output;
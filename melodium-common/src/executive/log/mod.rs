@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
+use core::convert::TryFrom;
 use core::fmt::{Debug, Display};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -34,3 +36,87 @@ pub struct Log {
     pub run_id: Option<Uuid>,
     pub group_id: Option<Uuid>,
 }
+
+impl TryFrom<&str> for Level {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, String> {
+        match value {
+            "error" => Ok(Level::Error),
+            "warning" => Ok(Level::Warning),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("'{value}' is not a log level")),
+        }
+    }
+}
+
+/// Filter telling which logs are kept, according to their level and label.
+///
+/// A log is kept if its level is not more verbose than the one set for its label,
+/// or than the default level if none is set for its label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    level: Level,
+    labels: HashMap<String, Level>,
+}
+
+impl LogFilter {
+    pub fn new(level: Level) -> Self {
+        Self {
+            level,
+            labels: HashMap::new(),
+        }
+    }
+
+    pub fn set_label_level(&mut self, label: &str, level: Level) {
+        self.labels.insert(label.to_string(), level);
+    }
+
+    /// Sets levels from filters formatted as `<label>=<level>` and separated by commas.
+    pub fn add_filters(&mut self, filters: &str) -> Result<(), String> {
+        for filter in filters
+            .split(',')
+            .filter(|filter| !filter.trim().is_empty())
+        {
+            match filter.split_once('=') {
+                Some((label, level)) => {
+                    self.set_label_level(label.trim(), Level::try_from(level.trim())?)
+                }
+                None => return Err(format!("'{}' is not a log filter", filter.trim())),
+            }
+        }
+        Ok(())
+    }
+
+    /// Gives the level logs with `label` are kept up to.
+    pub fn level(&self, label: &str) -> Level {
+        self.labels.get(label).copied().unwrap_or(self.level)
+    }
+
+    /// Gives the most verbose level any log can be kept at.
+    pub fn max_level(&self) -> Level {
+        self.labels
+            .values()
+            .copied()
+            .fold(self.level, |max, level| max.max(level))
+    }
+
+    /// Tells if a log with `level` and `label` is kept.
+    pub fn allows(&self, level: Level, label: &str) -> bool {
+        level <= self.level(label)
+    }
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self::new(Level::Trace)
+    }
+}
+
+impl From<Level> for LogFilter {
+    fn from(level: Level) -> Self {
+        Self::new(level)
+    }
+}
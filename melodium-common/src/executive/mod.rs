@@ -24,7 +24,7 @@ pub use data_traits::DataTrait;
 pub use future::ContinuousFuture;
 pub use future::TrackFuture;
 pub use input::Input;
-pub use log::{Level, Log};
+pub use log::{Level, Log, LogFilter};
 pub use model::{Model, ModelId};
 pub use output::{Output, Outputs};
 pub use result_status::ResultStatus;
@@ -1,6 +1,25 @@
-#[derive(Debug, Clone)]
+use core::fmt::{Display, Formatter, Result};
+
+/// Status reported by a track or continuous task once it finishes.
+///
+/// `Error` reports that the task failed, while the rest of the execution keeps running.
+/// `Fatal` reports that the task failed in a way the execution cannot recover from, and ends it.
+/// In both cases the failure is logged, and the execution is considered as failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ResultStatus {
     Ok,
+    Error(String),
+    Fatal(String),
+}
+
+impl ResultStatus {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok)
+    }
+
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Self::Fatal(_))
+    }
 }
 
 impl Default for ResultStatus {
@@ -8,3 +27,19 @@ impl Default for ResultStatus {
         Self::Ok
     }
 }
+
+impl From<()> for ResultStatus {
+    fn from(_: ()) -> Self {
+        Self::Ok
+    }
+}
+
+impl Display for ResultStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ResultStatus::Ok => write!(f, "ok"),
+            ResultStatus::Error(message) => write!(f, "error: {message}"),
+            ResultStatus::Fatal(message) => write!(f, "fatal: {message}"),
+        }
+    }
+}
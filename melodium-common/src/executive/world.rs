@@ -33,6 +33,8 @@ pub trait World: Debug + Send + Sync {
         callback: Option<TrackCreationCallback>,
     );
     async fn log(&self, level: Level, label: String, message: String, track_id: Option<TrackId>);
+    /// Tells if a log with `level` and `label` is kept, so the other ones don't need to be produced at all.
+    fn log_enabled(&self, level: Level, label: &str) -> bool;
     async fn inject_log(&self, log: Log) -> Result<(), ()>;
     async fn inject_debug(&self, run_id: Uuid, data: String) -> Result<(), ()>;
     /// Resolves once no track is running nor pending anymore (having run at
@@ -43,4 +45,8 @@ pub trait World: Debug + Send + Sync {
     /// track will ever run again, instead of waiting forever or relying on
     /// an arbitrary timeout.
     async fn wait_no_more_tracks(&self);
+    /// Ends the execution gracefully, requesting the process to exit with `code`.
+    ///
+    /// Only the first requested code is kept.
+    async fn exit(&self, code: i32);
 }
@@ -57,8 +57,8 @@ impl FunctionDescriptor for Function {
         &self.return_type
     }
 
-    fn function(&self) -> fn(HashMap<String, DataType>, Vec<Value>) -> Value {
-        self.function
+    fn function(&self) -> &(dyn Fn(HashMap<String, DataType>, Vec<Value>) -> Value + Send + Sync) {
+        &self.function
     }
 
     fn as_identified(&self) -> Arc<dyn Identified> {
@@ -16,11 +16,14 @@ use futures::{pin_mut, select, FutureExt, StreamExt};
 use futures_rustls::TlsAcceptor;
 use melodium_common::executive::{Level, Log};
 use melodium_common::{
-    descriptor::{Entry, Identifier, Model as CommonModel, Treatment as CommonTreatment, Version},
+    descriptor::{
+        Constant as CommonConstant, Entry, Identifier, Model as CommonModel,
+        Treatment as CommonTreatment, Version,
+    },
     executive::{ResultStatus, TransmissionValue, Value},
 };
 use melodium_engine::debug::{DebugLevel, Event};
-use melodium_engine::descriptor::{Model, Treatment};
+use melodium_engine::descriptor::{Constant, Model, Treatment};
 use melodium_engine::execution_group_id;
 use melodium_loader::Loader;
 use melodium_share::{ProgramDump, SharingError, SharingResult};
@@ -358,6 +361,16 @@ async fn launch_listen_stream<S: Read + Write + Unpin + Send + 'static>(
     for element in distributed_collection.elements() {
         if !element.is_compiled() {
             match element {
+                melodium_share::Element::Constant(c) => {
+                    let constant: Option<Arc<Constant>> = result.merge_degrade_failure(
+                        DistributionResult::from(c.make_descriptor(&collection)),
+                    );
+                    if let Some(constant) = constant {
+                        collection.insert(Entry::Constant(
+                            Arc::clone(&constant) as Arc<dyn CommonConstant>
+                        ));
+                    }
+                }
                 melodium_share::Element::Model(m) => {
                     let model: Option<Arc<Model>> = result.merge_degrade_failure(
                         DistributionResult::from(m.make_descriptor(&collection)),
@@ -405,8 +418,11 @@ async fn launch_listen_stream<S: Read + Write + Unpin + Send + 'static>(
         .into_iter()
         .map(|(name, val)| (name, val.to_value(&collection).unwrap()))
         .collect();
-    let engine =
-        melodium_engine::new_engine(Arc::clone(&collection), Level::Trace, DebugLevel::Detailed);
+    let engine = melodium_engine::new_engine(
+        Arc::clone(&collection),
+        Level::Trace.into(),
+        DebugLevel::Detailed,
+    );
     engine.set_auto_end(false);
 
     let (logs_sender, logs_receiver) = unbounded();
@@ -424,7 +440,7 @@ async fn launch_listen_stream<S: Read + Write + Unpin + Send + 'static>(
     let watchdog_debug_receiver = debug_receiver.clone();
 
     if let Err(fail) = engine
-        .genesis(&entrypoint.try_into().unwrap(), parameters)
+        .genesis(&entrypoint.try_into().unwrap(), parameters, HashMap::new())
         .as_result()
     {
         protocol
@@ -3,8 +3,8 @@
 
 use itertools::Itertools;
 use melodium_common::descriptor::{
-    Collection, CollectionTree, Context, Data, DescribedType, Entry, Flow, Function, Identified,
-    Identifier, Input, Model, Output, Parameter, Treatment,
+    Collection, CollectionTree, Constant, Context, Data, DataStructure, DescribedType, Entry, Flow,
+    Function, Identified, Identifier, Input, Model, Output, Parameter, Treatment,
 };
 use std::collections::HashMap;
 use std::error::Error;
@@ -94,6 +94,11 @@ impl Documentation {
 
         for entry in area.entries.iter().sorted() {
             let line = match entry {
+                Entry::Constant(c) => format!(
+                    "- [≡ {}]({})\n",
+                    c.identifier().name(),
+                    Self::id_filepath(c.identifier())
+                ),
                 Entry::Context(c) => {
                     format!(
                         "- [⥱ {}]({})\n",
@@ -162,6 +167,7 @@ impl Documentation {
         }
 
         let mut datas = String::new();
+        let mut constants = String::new();
         let mut contexts = String::new();
         let mut functions = String::new();
         let mut models = String::new();
@@ -171,6 +177,16 @@ impl Documentation {
         entries.sort();
         for entry in entries {
             match entry {
+                Entry::Constant(c) => {
+                    if constants.is_empty() {
+                        constants.push_str("## Constants\n\n");
+                    }
+
+                    constants.push_str(&format!(
+                        "≡ [{name}]({name}.md)  \n",
+                        name = c.identifier().name()
+                    ));
+                }
                 Entry::Context(c) => {
                     if contexts.is_empty() {
                         contexts.push_str("## Contexts\n\n");
@@ -233,7 +249,7 @@ impl Documentation {
             format!("src/{}/index.md", path.join("/"))
         };
         let content = format!(
-            "# {title}{display_path}\n\n---\n\n{subs}{datas}{contexts}{functions}{models}{treatments}"
+            "# {title}{display_path}\n\n---\n\n{subs}{datas}{constants}{contexts}{functions}{models}{treatments}"
         );
 
         self.write(&file, content.as_bytes())?;
@@ -243,6 +259,7 @@ impl Documentation {
 
     fn make_entry(&self, entry: &Entry) -> Result<(), Box<dyn Error>> {
         let content = match entry {
+            Entry::Constant(c) => self.constant_content(c),
             Entry::Context(c) => self.context_content(c),
             Entry::Function(f) => self.function_content(f),
             Entry::Model(m) => self.model_content(m),
@@ -261,6 +278,26 @@ impl Documentation {
         Ok(())
     }
 
+    fn constant_content(&self, constant: &Arc<dyn Constant>) -> String {
+        format!(
+            "# Constant {name}\n\n`{id}`\n\n---\n\n#### Type\n\n↴ `{type}`{type_link}\n\n#### Value\n\n```\n{value}\n```\n\n---\n\n{doc}",
+            name = constant.identifier().name(),
+            id = constant.identifier().to_string(),
+            type = constant.described_type(),
+            type_link = if let Some(data) = constant.described_type().final_type().data() {
+                format!(
+                    " _([`{id}`]({link}))_",
+                    id = data.identifier(),
+                    link = self.get_link(constant.identifier(), data.identifier())
+                )
+            } else {
+                String::new()
+            },
+            value = constant.value(),
+            doc = constant.documentation(),
+        )
+    }
+
     fn context_content(&self, context: &Arc<dyn Context>) -> String {
         let entries = if !context.values().is_empty() {
             let mut string = String::new();
@@ -506,8 +543,41 @@ impl Documentation {
             String::from("_This data type do not implement any trait_")
         };
 
+        let structure = match data.structure() {
+            Some(DataStructure::Record(fields)) => {
+                let mut string = String::new();
+
+                for (name, described_type) in fields {
+                    string.push_str(&format!(
+                        "↳ `{name}:` `{described_type}`{type_link}  \n",
+                        type_link = if let Some(field_data) = described_type.final_type().data() {
+                            format!(
+                                " _([`{id}`]({link}))_",
+                                id = field_data.identifier(),
+                                link = self.get_link(data.identifier(), field_data.identifier())
+                            )
+                        } else {
+                            String::new()
+                        }
+                    ));
+                }
+
+                format!("\n\n---\n\n#### Fields\n\n{}", string)
+            }
+            Some(DataStructure::Enumeration(variants)) => {
+                let mut string = String::new();
+
+                for variant in variants {
+                    string.push_str(&format!("◇ `{variant}`  \n"));
+                }
+
+                format!("\n\n---\n\n#### Variants\n\n{}", string)
+            }
+            None => String::new(),
+        };
+
         format!(
-            "# Data {name}\n\n`{id}`\n\n---\n\n{traits}\n\n---\n\n{doc}",
+            "# Data {name}\n\n`{id}`\n\n---\n\n{traits}{structure}\n\n---\n\n{doc}",
             name = data.identifier().name(),
             id = data.identifier().to_string(),
             doc = data.documentation(),
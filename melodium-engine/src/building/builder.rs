@@ -90,6 +90,7 @@ pub(crate) fn get_value(
                 genesis_environment.get_variable(&name).cloned()
             }
         }
+        Value::Constant(constant) => Some(constant.value().clone()),
         Value::Context(context, entry) => contextual_environment
             .map(|ce| ce.get_context(context.name()).map(|c| c.get_value(entry)))
             .flatten(),
@@ -66,7 +66,7 @@ impl BuilderTrait for Builder {
         }));
 
         world
-            .builder(descriptor.base_model().unwrap().identifier())
+            .own_builder(descriptor.base_model().unwrap().identifier())
             .and_then(|builder| {
                 builder.static_build(host_treatment, host_build, label, &remastered_environment)
             })
@@ -3,10 +3,12 @@ use crate::{
     building::{BuildId, ContextualEnvironment, HostTreatment},
     world::World,
 };
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use core::fmt::Debug;
 use melodium_common::{
     descriptor::{Model, Treatment},
-    executive::{Context, ModelId, TrackId, Value},
+    executive::{Context, ModelId, ResultStatus, TrackId, TransmissionValue, Value},
 };
 use std::{collections::HashMap, sync::Arc};
 use uuid::Uuid;
@@ -63,6 +65,10 @@ pub enum EventKind {
     TrackFinished {
         info: InfoTrack,
     },
+    Failure {
+        track_id: Option<TrackId>,
+        status: ResultStatus,
+    },
     TreatmentStarted {
         treatment: Arc<dyn Treatment>,
         host_treatment: HostTreatment,
@@ -135,6 +141,23 @@ pub enum TransmissionDebug {
     Detailed(Arc<World>, TransmissionDetails),
 }
 
+/// Inspector of transmissions, able to hold them while looking at data.
+///
+/// Inspector is called only if debug level is not [`DebugLevel::None`].
+#[async_trait]
+pub trait Inspector: Debug + Send + Sync {
+    /// Called when `data` is about to be transmitted from `output` to `input` within track `track_id`.
+    ///
+    /// Transmission waits for the returned future to complete.
+    async fn transmission(
+        &self,
+        output: &TransmissionDetails,
+        input: &TransmissionDetails,
+        track_id: TrackId,
+        data: &TransmissionValue,
+    );
+}
+
 #[derive(Debug, Clone)]
 pub struct TransmissionDetails {
     pub treatment: Arc<dyn Treatment>,
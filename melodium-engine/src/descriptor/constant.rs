@@ -0,0 +1,110 @@
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use melodium_common::descriptor::{
+    Attribuable, Attribute, Attributes, Constant as ConstantDescriptor, DescribedType, Documented,
+    Identified, Identifier,
+};
+use melodium_common::executive::Value;
+use std::sync::Arc;
+
+/// Named constant value declared in a script.
+#[derive(Debug)]
+pub struct Constant {
+    identifier: Identifier,
+    #[cfg(feature = "doc")]
+    documentation: String,
+    attributes: Attributes,
+    described_type: DescribedType,
+    value: Value,
+}
+
+impl Constant {
+    pub fn new(identifier: Identifier, described_type: DescribedType, value: Value) -> Self {
+        Self {
+            identifier,
+            #[cfg(feature = "doc")]
+            documentation: String::new(),
+            attributes: Attributes::default(),
+            described_type,
+            value,
+        }
+    }
+
+    pub fn set_documentation(&mut self, documentation: &str) {
+        #[cfg(feature = "doc")]
+        {
+            self.documentation = String::from(documentation);
+        }
+        #[cfg(not(feature = "doc"))]
+        let _ = documentation;
+    }
+
+    pub fn add_attribute(&mut self, name: String, attribute: Attribute) {
+        self.attributes.insert(name, attribute);
+    }
+
+    pub fn commit(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+}
+
+impl Attribuable for Constant {
+    fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+}
+
+impl Identified for Constant {
+    fn identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    fn make_use(&self, identifier: &Identifier) -> bool {
+        self.uses().contains(identifier)
+    }
+
+    fn uses(&self) -> Vec<Identifier> {
+        let mut uses = Vec::new();
+        if let Some(data) = self.described_type.final_type().data() {
+            uses.push(data.identifier().clone());
+            uses.extend(data.uses());
+        }
+        uses
+    }
+}
+
+impl Documented for Constant {
+    fn documentation(&self) -> &str {
+        #[cfg(feature = "doc")]
+        {
+            &self.documentation
+        }
+        #[cfg(not(feature = "doc"))]
+        {
+            &""
+        }
+    }
+}
+
+impl Display for Constant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "const {}: {} = {}",
+            self.identifier.to_string(),
+            self.described_type,
+            self.value
+        )?;
+
+        Ok(())
+    }
+}
+
+impl ConstantDescriptor for Constant {
+    fn described_type(&self) -> &DescribedType {
+        &self.described_type
+    }
+
+    fn value(&self) -> &Value {
+        &self.value
+    }
+}
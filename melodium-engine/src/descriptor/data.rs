@@ -0,0 +1,328 @@
+use super::{DataValue, Function};
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use melodium_common::descriptor::{
+    Attribuable, Attribute, Attributes, Data as DataDescriptor, DataStructure, DataTrait,
+    DescribedType, Documented, Identified, Identifier, Parameter, Variability,
+};
+use melodium_common::executive::Value;
+use serde::de::Error;
+use std::sync::{Arc, Weak};
+
+/// Data declared in a script, either as record or enumeration.
+#[derive(Debug)]
+pub struct Data {
+    identifier: Identifier,
+    #[cfg(feature = "doc")]
+    documentation: String,
+    attributes: Attributes,
+    implements: Vec<DataTrait>,
+    structure: DataStructure,
+    auto_reference: Weak<Self>,
+}
+
+impl Data {
+    pub fn new(identifier: Identifier, structure: DataStructure) -> Self {
+        Self {
+            identifier,
+            #[cfg(feature = "doc")]
+            documentation: String::new(),
+            attributes: Attributes::default(),
+            implements: Vec::new(),
+            structure,
+            auto_reference: Weak::default(),
+        }
+    }
+
+    pub fn set_documentation(&mut self, documentation: &str) {
+        #[cfg(feature = "doc")]
+        {
+            self.documentation = String::from(documentation);
+        }
+        #[cfg(not(feature = "doc"))]
+        let _ = documentation;
+    }
+
+    pub fn add_attribute(&mut self, name: String, attribute: Attribute) {
+        self.attributes.insert(name, attribute);
+    }
+
+    pub fn add_implementation(&mut self, data_trait: DataTrait) {
+        if !self.implements.contains(&data_trait) {
+            self.implements.push(data_trait);
+        }
+    }
+
+    pub fn structure(&self) -> &DataStructure {
+        &self.structure
+    }
+
+    pub fn commit(self) -> Arc<Self> {
+        Arc::new_cyclic(|me| Self {
+            identifier: self.identifier,
+            #[cfg(feature = "doc")]
+            documentation: self.documentation,
+            attributes: self.attributes,
+            implements: self.implements,
+            structure: self.structure,
+            auto_reference: me.clone(),
+        })
+    }
+
+    /// Gives the functions associated with this data.
+    ///
+    /// A record gets a constructor function taking all its fields, named after the data, such as `|point` for `Point`,
+    /// and one accessor function per field, such as `|point_x` for field `x` of `Point`.
+    /// An enumeration gets one function per variant, such as `|level_low` for variant `Low` of `Level`.
+    pub fn functions(&self) -> Vec<Arc<Function>> {
+        let me = self.auto_reference.upgrade().unwrap();
+        let described_type =
+            DescribedType::Data(Box::new(Arc::clone(&me) as Arc<dyn DataDescriptor>));
+        let base_name = snake_case(self.identifier.name());
+        let mut identifiers = match &self.structure {
+            DataStructure::Record(fields) => Self::record_functions_identifiers(
+                &self.identifier,
+                &fields
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .collect::<Vec<_>>(),
+            ),
+            DataStructure::Enumeration(variants) => {
+                Self::enumeration_functions_identifiers(&self.identifier, variants)
+            }
+        }
+        .into_iter();
+
+        let mut functions = Vec::new();
+        match &self.structure {
+            DataStructure::Record(fields) => {
+                let data = Arc::clone(&me);
+                let mut constructor = Function::new(
+                    identifiers.next().unwrap(),
+                    described_type.clone(),
+                    Box::new(move |_, fields| {
+                        Value::Data(Arc::new(DataValue::new_record(&data, fields)))
+                    }),
+                );
+                constructor
+                    .set_documentation(&format!("Creates a new `{}`.", self.identifier.name()));
+                for (name, field_type) in fields {
+                    constructor.add_parameter(Parameter::new(
+                        name,
+                        Variability::Var,
+                        field_type.clone(),
+                        None,
+                        Attributes::default(),
+                    ));
+                }
+                functions.push(constructor.commit());
+
+                for (index, (name, field_type)) in fields.iter().enumerate() {
+                    let mut accessor = Function::new(
+                        identifiers.next().unwrap(),
+                        field_type.clone(),
+                        Box::new(move |_, values| match values.first() {
+                            Some(Value::Data(data)) => Arc::clone(data)
+                                .downcast_arc::<DataValue>()
+                                .ok()
+                                .and_then(|data| data.fields().get(index).cloned())
+                                .expect("Invalid data value"),
+                            _ => panic!("Invalid value type"),
+                        }),
+                    );
+                    accessor.set_documentation(&format!(
+                        "Gives `{name}` field of `{}`.",
+                        self.identifier.name()
+                    ));
+                    accessor.add_parameter(Parameter::new(
+                        &base_name,
+                        Variability::Var,
+                        described_type.clone(),
+                        None,
+                        Attributes::default(),
+                    ));
+                    functions.push(accessor.commit());
+                }
+            }
+            DataStructure::Enumeration(variants) => {
+                for (index, variant) in variants.iter().enumerate() {
+                    let data = Arc::clone(&me);
+                    let mut function = Function::new(
+                        identifiers.next().unwrap(),
+                        described_type.clone(),
+                        Box::new(move |_, _| {
+                            Value::Data(Arc::new(DataValue::new_variant(&data, index)))
+                        }),
+                    );
+                    function.set_documentation(&format!(
+                        "Gives `{variant}` variant of `{}`.",
+                        self.identifier.name()
+                    ));
+                    functions.push(function.commit());
+                }
+            }
+        }
+
+        functions
+    }
+
+    /// Gives the identifiers of the functions associated with a record, as [Self::functions] would name them.
+    pub fn record_functions_identifiers(
+        identifier: &Identifier,
+        fields: &[String],
+    ) -> Vec<Identifier> {
+        Self::record_functions_names(identifier.name(), fields)
+            .iter()
+            .map(|name| function_identifier(identifier, name))
+            .collect()
+    }
+
+    /// Gives the identifiers of the functions associated with an enumeration, as [Self::functions] would name them.
+    pub fn enumeration_functions_identifiers(
+        identifier: &Identifier,
+        variants: &[String],
+    ) -> Vec<Identifier> {
+        Self::enumeration_functions_names(identifier.name(), variants)
+            .iter()
+            .map(|name| function_identifier(identifier, name))
+            .collect()
+    }
+
+    /// Gives the names of the functions associated with a record named `name`.
+    pub fn record_functions_names(name: &str, fields: &[String]) -> Vec<String> {
+        let base_name = snake_case(name);
+        std::iter::once(format!("|{base_name}"))
+            .chain(fields.iter().map(|name| format!("|{base_name}_{name}")))
+            .collect()
+    }
+
+    /// Gives the names of the functions associated with an enumeration named `name`.
+    pub fn enumeration_functions_names(name: &str, variants: &[String]) -> Vec<String> {
+        let base_name = snake_case(name);
+        variants
+            .iter()
+            .map(|variant| format!("|{base_name}_{}", snake_case(variant)))
+            .collect()
+    }
+}
+
+fn function_identifier(identifier: &Identifier, name: &str) -> Identifier {
+    Identifier::new_optionally_versionned(identifier.version(), identifier.path().clone(), name)
+}
+
+/// Turns `UpperCamelCase` name into `snake_case`.
+///
+/// Acronyms are kept as one word, such as `HTTPServer` giving `http_server`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let previous = index.checked_sub(1).map(|index| chars[index]);
+            let next = chars.get(index + 1);
+            let word_start = match previous {
+                Some(previous) if previous.is_lowercase() || previous.is_ascii_digit() => true,
+                Some(previous) if previous.is_uppercase() => {
+                    next.map(|next| next.is_lowercase()).unwrap_or(false)
+                }
+                _ => false,
+            };
+            if word_start && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(*c);
+        }
+    }
+    snake
+}
+
+impl Attribuable for Data {
+    fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+}
+
+impl Identified for Data {
+    fn identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    fn make_use(&self, identifier: &Identifier) -> bool {
+        self.uses().contains(identifier)
+    }
+
+    fn uses(&self) -> Vec<Identifier> {
+        let mut uses = Vec::new();
+        if let DataStructure::Record(fields) = &self.structure {
+            for (_, field_type) in fields {
+                if let Some(data) = field_type.final_type().data() {
+                    uses.push(data.identifier().clone());
+                    uses.extend(data.uses());
+                }
+            }
+        }
+        uses
+    }
+}
+
+impl Documented for Data {
+    fn documentation(&self) -> &str {
+        #[cfg(feature = "doc")]
+        {
+            &self.documentation
+        }
+        #[cfg(not(feature = "doc"))]
+        {
+            &""
+        }
+    }
+}
+
+impl Display for Data {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "data {}", self.identifier.to_string())?;
+
+        Ok(())
+    }
+}
+
+impl DataDescriptor for Data {
+    fn implements(&self) -> &[DataTrait] {
+        &self.implements
+    }
+
+    fn structure(&self) -> Option<&DataStructure> {
+        Some(&self.structure)
+    }
+
+    fn bounded_min(&self) -> Value {
+        panic!("Bounded not implemented by {}", self.identifier)
+    }
+
+    fn bounded_max(&self) -> Value {
+        panic!("Bounded not implemented by {}", self.identifier)
+    }
+
+    fn float_infinity(&self) -> Value {
+        panic!("Float not implemented by {}", self.identifier)
+    }
+
+    fn float_neg_infinity(&self) -> Value {
+        panic!("Float not implemented by {}", self.identifier)
+    }
+
+    fn float_nan(&self) -> Value {
+        panic!("Float not implemented by {}", self.identifier)
+    }
+
+    fn deserialize(
+        &self,
+        _deserializer: &mut dyn erased_serde::Deserializer,
+    ) -> Result<Value, erased_serde::Error> {
+        Err(erased_serde::Error::custom(format!(
+            "Deserialize not implemented by {}",
+            self.identifier
+        )))
+    }
+}
@@ -0,0 +1,351 @@
+use super::Data;
+use core::fmt::{Debug, Formatter};
+use core::hash::Hasher;
+use melodium_common::descriptor::{
+    Data as DataDescriptor, DataStructure, DataTrait as DataTraitKind, Identified,
+};
+use melodium_common::executive::{Data as ExecutiveData, DataTrait, Value};
+use serde::ser::{Error, SerializeMap};
+use std::sync::Arc;
+
+/// Value of data declared in a script.
+pub struct DataValue {
+    descriptor: Arc<Data>,
+    content: Content,
+}
+
+#[derive(Debug)]
+enum Content {
+    Record(Vec<Value>),
+    Variant(usize),
+}
+
+impl DataValue {
+    pub fn new_record(descriptor: &Arc<Data>, fields: Vec<Value>) -> Self {
+        Self {
+            descriptor: Arc::clone(descriptor),
+            content: Content::Record(fields),
+        }
+    }
+
+    pub fn new_variant(descriptor: &Arc<Data>, index: usize) -> Self {
+        Self {
+            descriptor: Arc::clone(descriptor),
+            content: Content::Variant(index),
+        }
+    }
+
+    /// Gives the fields values, in order of declaration, or nothing if data is an enumeration.
+    pub fn fields(&self) -> &[Value] {
+        match &self.content {
+            Content::Record(fields) => fields,
+            Content::Variant(_) => &[],
+        }
+    }
+
+    /// Gives the variant name, if data is an enumeration.
+    pub fn variant(&self) -> Option<&str> {
+        match (&self.content, self.descriptor.structure()) {
+            (Content::Variant(index), DataStructure::Enumeration(variants)) => {
+                variants.get(*index).map(|variant| variant.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    fn fields_names(&self) -> Vec<&str> {
+        match self.descriptor.structure() {
+            DataStructure::Record(fields) => fields.iter().map(|(name, _)| name.as_str()).collect(),
+            DataStructure::Enumeration(_) => Vec::new(),
+        }
+    }
+
+    fn check_implements(&self, data_trait: DataTraitKind, name: &str) {
+        if !self.descriptor.implements().contains(&data_trait) {
+            panic!(
+                "{name} not implemented for {}",
+                self.descriptor.identifier().name()
+            )
+        }
+    }
+
+    fn write(
+        &self,
+        f: &mut Formatter<'_>,
+        field: impl Fn(&Value, &mut Formatter<'_>) -> core::fmt::Result,
+    ) -> core::fmt::Result {
+        match &self.content {
+            Content::Record(fields) => {
+                write!(f, "{} {{ ", self.descriptor.identifier().name())?;
+                for (index, (name, value)) in self.fields_names().iter().zip(fields).enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}: ")?;
+                    field(value, f)?;
+                }
+                write!(f, " }}")
+            }
+            Content::Variant(_) => write!(f, "{}", self.variant().unwrap_or_default()),
+        }
+    }
+}
+
+impl Debug for DataValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DataValue")
+            .field("descriptor", self.descriptor.identifier())
+            .field("content", &self.content)
+            .finish()
+    }
+}
+
+impl ExecutiveData for DataValue {
+    fn descriptor(&self) -> Arc<dyn DataDescriptor> {
+        Arc::clone(&self.descriptor) as Arc<dyn DataDescriptor>
+    }
+}
+
+impl serde::Serialize for DataValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if !self
+            .descriptor
+            .implements()
+            .contains(&DataTraitKind::Serialize)
+        {
+            return Err(S::Error::custom(format!(
+                "Serialize not implemented for {}",
+                self.descriptor.identifier().name()
+            )));
+        }
+
+        match &self.content {
+            Content::Record(fields) => {
+                // Serde requires static names for structs, record fields are serialized as a map instead.
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (name, value) in self.fields_names().iter().zip(fields) {
+                    map.serialize_entry(name, value)?;
+                }
+                map.end()
+            }
+            Content::Variant(_) => serializer.serialize_str(self.variant().unwrap_or_default()),
+        }
+    }
+}
+
+macro_rules! not_implemented {
+    ($($trait_name:literal => $(fn $name:ident(&self $(, $ty:ty)*) -> $ret:ty;)+)+) => {
+        $($(
+            fn $name(&self $(, _: $ty)*) -> $ret {
+                panic!(
+                    "{} not implemented for {}",
+                    $trait_name,
+                    self.descriptor.identifier().name()
+                )
+            }
+        )+)+
+    };
+}
+
+impl DataTrait for DataValue {
+    not_implemented! {
+        "ToI8" => fn to_i8(&self) -> i8;
+        "ToI16" => fn to_i16(&self) -> i16;
+        "ToI32" => fn to_i32(&self) -> i32;
+        "ToI64" => fn to_i64(&self) -> i64;
+        "ToI128" => fn to_i128(&self) -> i128;
+        "ToU8" => fn to_u8(&self) -> u8;
+        "ToU16" => fn to_u16(&self) -> u16;
+        "ToU32" => fn to_u32(&self) -> u32;
+        "ToU64" => fn to_u64(&self) -> u64;
+        "ToU128" => fn to_u128(&self) -> u128;
+        "ToF32" => fn to_f32(&self) -> f32;
+        "ToF64" => fn to_f64(&self) -> f64;
+        "ToBool" => fn to_bool(&self) -> bool;
+        "ToByte" => fn to_byte(&self) -> u8;
+        "ToChar" => fn to_char(&self) -> char;
+
+        "TryToI8" => fn try_to_i8(&self) -> Option<i8>;
+        "TryToI16" => fn try_to_i16(&self) -> Option<i16>;
+        "TryToI32" => fn try_to_i32(&self) -> Option<i32>;
+        "TryToI64" => fn try_to_i64(&self) -> Option<i64>;
+        "TryToI128" => fn try_to_i128(&self) -> Option<i128>;
+        "TryToU8" => fn try_to_u8(&self) -> Option<u8>;
+        "TryToU16" => fn try_to_u16(&self) -> Option<u16>;
+        "TryToU32" => fn try_to_u32(&self) -> Option<u32>;
+        "TryToU64" => fn try_to_u64(&self) -> Option<u64>;
+        "TryToU128" => fn try_to_u128(&self) -> Option<u128>;
+        "TryToF32" => fn try_to_f32(&self) -> Option<f32>;
+        "TryToF64" => fn try_to_f64(&self) -> Option<f64>;
+        "TryToBool" => fn try_to_bool(&self) -> Option<bool>;
+        "TryToByte" => fn try_to_byte(&self) -> Option<u8>;
+        "TryToChar" => fn try_to_char(&self) -> Option<char>;
+        "TryToString" => fn try_to_string(&self) -> Option<String>;
+
+        "SaturatingToI8" => fn saturating_to_i8(&self) -> i8;
+        "SaturatingToI16" => fn saturating_to_i16(&self) -> i16;
+        "SaturatingToI32" => fn saturating_to_i32(&self) -> i32;
+        "SaturatingToI64" => fn saturating_to_i64(&self) -> i64;
+        "SaturatingToI128" => fn saturating_to_i128(&self) -> i128;
+        "SaturatingToU8" => fn saturating_to_u8(&self) -> u8;
+        "SaturatingToU16" => fn saturating_to_u16(&self) -> u16;
+        "SaturatingToU32" => fn saturating_to_u32(&self) -> u32;
+        "SaturatingToU64" => fn saturating_to_u64(&self) -> u64;
+        "SaturatingToU128" => fn saturating_to_u128(&self) -> u128;
+        "SaturatingToF32" => fn saturating_to_f32(&self) -> f32;
+        "SaturatingToF64" => fn saturating_to_f64(&self) -> f64;
+
+        "Binary" =>
+            fn binary_and(&self, &Value) -> Value;
+            fn binary_or(&self, &Value) -> Value;
+            fn binary_xor(&self, &Value) -> Value;
+            fn binary_not(&self) -> Value;
+
+        "Signed" =>
+            fn signed_abs(&self) -> Option<Value>;
+            fn signed_signum(&self) -> Value;
+            fn signed_is_positive(&self) -> bool;
+            fn signed_is_negative(&self) -> bool;
+
+        "Float" =>
+            fn float_is_nan(&self) -> bool;
+            fn float_is_infinite(&self) -> bool;
+            fn float_is_finite(&self) -> bool;
+            fn float_is_normal(&self) -> bool;
+            fn float_is_subnormal(&self) -> bool;
+            fn float_floor(&self) -> Value;
+            fn float_ceil(&self) -> Value;
+            fn float_round(&self) -> Value;
+            fn float_trunc(&self) -> Value;
+            fn float_fract(&self) -> Value;
+            fn float_recip(&self) -> Value;
+            fn float_pow(&self, &Value) -> Value;
+            fn float_sqrt(&self) -> Value;
+            fn float_exp(&self) -> Value;
+            fn float_exp2(&self) -> Value;
+            fn float_ln(&self) -> Value;
+            fn float_log(&self, &Value) -> Value;
+            fn float_log2(&self) -> Value;
+            fn float_log10(&self) -> Value;
+            fn float_cbrt(&self) -> Value;
+            fn float_hypot(&self, &Value) -> Value;
+            fn float_sin(&self) -> Value;
+            fn float_cos(&self) -> Value;
+            fn float_tan(&self) -> Value;
+            fn float_asin(&self) -> Value;
+            fn float_acos(&self) -> Value;
+            fn float_atan(&self) -> Value;
+            fn float_atan2(&self, &Value) -> Value;
+            fn float_sinh(&self) -> Value;
+            fn float_cosh(&self) -> Value;
+            fn float_tanh(&self) -> Value;
+            fn float_asinh(&self) -> Value;
+            fn float_acosh(&self) -> Value;
+            fn float_atanh(&self) -> Value;
+            fn float_to_degrees(&self) -> Value;
+            fn float_to_radians(&self) -> Value;
+
+        "PartialOrder" =>
+            fn partial_order_lt(&self, &Value) -> bool;
+            fn partial_order_le(&self, &Value) -> bool;
+            fn partial_order_gt(&self, &Value) -> bool;
+            fn partial_order_ge(&self, &Value) -> bool;
+
+        "Order" =>
+            fn order_max(&self, &Value) -> Value;
+            fn order_min(&self, &Value) -> Value;
+            fn order_clamp(&self, &Value, &Value) -> Value;
+
+        "Add" => fn add(&self, &Value) -> Value;
+        "CheckedAdd" => fn checked_add(&self, &Value) -> Option<Value>;
+        "SaturatingAdd" => fn saturating_add(&self, &Value) -> Value;
+        "WrappingAdd" => fn wrapping_add(&self, &Value) -> Value;
+        "Sub" => fn sub(&self, &Value) -> Value;
+        "CheckedSub" => fn checked_sub(&self, &Value) -> Option<Value>;
+        "SaturatingSub" => fn saturating_sub(&self, &Value) -> Value;
+        "WrappingSub" => fn wrapping_sub(&self, &Value) -> Value;
+        "Mul" => fn mul(&self, &Value) -> Value;
+        "CheckedMul" => fn checked_mul(&self, &Value) -> Option<Value>;
+        "SaturatingMul" => fn saturating_mul(&self, &Value) -> Value;
+        "WrappingMul" => fn wrapping_mul(&self, &Value) -> Value;
+        "Div" => fn div(&self, &Value) -> Value;
+        "CheckedDiv" => fn checked_div(&self, &Value) -> Option<Value>;
+        "Rem" => fn rem(&self, &Value) -> Value;
+        "CheckedRem" => fn checked_rem(&self, &Value) -> Option<Value>;
+        "Neg" => fn neg(&self) -> Value;
+        "CheckedNeg" => fn checked_neg(&self) -> Option<Value>;
+        "WrappingNeg" => fn wrapping_neg(&self) -> Value;
+        "Pow" => fn pow(&self, &u32) -> Value;
+        "CheckedPow" => fn checked_pow(&self, &u32) -> Option<Value>;
+
+        "Euclid" =>
+            fn euclid_div(&self, &Value) -> Value;
+            fn euclid_rem(&self, &Value) -> Value;
+        "CheckedEuclid" =>
+            fn checked_euclid_div(&self, &Value) -> Option<Value>;
+            fn checked_euclid_rem(&self, &Value) -> Option<Value>;
+    }
+
+    fn to_string(&self) -> String {
+        self.check_implements(DataTraitKind::ToString, "ToString");
+
+        struct Text<'a>(&'a DataValue);
+        impl core::fmt::Display for Text<'_> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                self.0
+                    .write(f, |value, f| write!(f, "{}", DataTrait::to_string(value)))
+            }
+        }
+
+        Text(self).to_string()
+    }
+
+    fn partial_equality_eq(&self, other: &Value) -> bool {
+        self.check_implements(DataTraitKind::PartialEquality, "PartialEquality");
+        match other {
+            Value::Data(obj) => {
+                if let Ok(obj) = Arc::clone(obj).downcast_arc::<Self>() {
+                    match (&self.content, &obj.content) {
+                        (Content::Record(mine), Content::Record(others)) => mine == others,
+                        (Content::Variant(mine), Content::Variant(others)) => mine == others,
+                        _ => false,
+                    }
+                } else {
+                    panic!("Invalid object type")
+                }
+            }
+            _ => panic!("Invalid value type"),
+        }
+    }
+
+    fn partial_equality_ne(&self, other: &Value) -> bool {
+        !self.partial_equality_eq(other)
+    }
+
+    fn hash(&self, state: &mut dyn Hasher) {
+        self.check_implements(DataTraitKind::Hash, "Hash");
+        match &self.content {
+            Content::Record(fields) => {
+                for field in fields {
+                    DataTrait::hash(field, state);
+                }
+            }
+            Content::Variant(index) => state.write_usize(*index),
+        }
+    }
+
+    fn serialize(
+        &self,
+        serializer: &mut dyn erased_serde::Serializer,
+    ) -> Result<(), erased_serde::Error> {
+        erased_serde::Serialize::erased_serialize(self, serializer)
+    }
+
+    fn display(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        self.check_implements(DataTraitKind::Display, "Display");
+        self.write(f, |value, f| DataTrait::display(value, f))
+    }
+}
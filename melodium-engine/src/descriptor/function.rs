@@ -0,0 +1,514 @@
+use crate::designer::{Reference, Value as DesignedValue};
+use crate::{LogicError, LogicResult};
+use core::fmt::{Debug, Display, Formatter, Result};
+use melodium_common::descriptor::{
+    Attribuable, Attribute, Attributes, DataType, DescribedType, Documented,
+    Function as FunctionDescriptor, Generic, Generics, Identified, Identifier,
+    OrderedParameterized, Parameter, Parameterized, Variability,
+};
+use melodium_common::executive::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, Weak};
+
+pub type FnFunction = Box<dyn Fn(HashMap<String, DataType>, Vec<Value>) -> Value + Send + Sync>;
+
+/// Function whose implementation is provided at runtime.
+///
+/// Implementation is either given as closure, such as for functions generated for data declared in scripts,
+/// or designed as a value composed of parameters and calls to other functions, such as for functions declared in scripts.
+pub struct Function {
+    identifier: Identifier,
+    #[cfg(feature = "doc")]
+    documentation: String,
+    attributes: Attributes,
+    generics: Vec<Generic>,
+    parameters: Vec<Parameter>,
+    return_type: DescribedType,
+    function: Option<FnFunction>,
+    design: RwLock<Option<DesignedValue>>,
+    evaluation: RwLock<Option<Evaluation>>,
+    auto_reference: Weak<Self>,
+}
+
+impl Function {
+    pub fn new(identifier: Identifier, return_type: DescribedType, function: FnFunction) -> Self {
+        Self {
+            identifier,
+            #[cfg(feature = "doc")]
+            documentation: String::new(),
+            attributes: Attributes::default(),
+            generics: Vec::new(),
+            parameters: Vec::new(),
+            return_type,
+            function: Some(function),
+            design: RwLock::new(None),
+            evaluation: RwLock::new(None),
+            auto_reference: Weak::default(),
+        }
+    }
+
+    /// Creates a function whose implementation is given afterwards through [Self::set_design].
+    pub fn new_designed(identifier: Identifier, return_type: DescribedType) -> Self {
+        Self {
+            identifier,
+            #[cfg(feature = "doc")]
+            documentation: String::new(),
+            attributes: Attributes::default(),
+            generics: Vec::new(),
+            parameters: Vec::new(),
+            return_type,
+            function: None,
+            design: RwLock::new(None),
+            evaluation: RwLock::new(None),
+            auto_reference: Weak::default(),
+        }
+    }
+
+    pub fn set_documentation(&mut self, documentation: &str) {
+        #[cfg(feature = "doc")]
+        {
+            self.documentation = String::from(documentation);
+        }
+        #[cfg(not(feature = "doc"))]
+        let _ = documentation;
+    }
+
+    pub fn add_attribute(&mut self, name: String, attribute: Attribute) {
+        self.attributes.insert(name, attribute);
+    }
+
+    pub fn add_generic(&mut self, generic: Generic) {
+        self.generics.push(generic);
+    }
+
+    pub fn add_parameter(&mut self, parameter: Parameter) {
+        self.parameters.push(parameter);
+    }
+
+    pub fn commit(self) -> Arc<Self> {
+        Arc::new_cyclic(|me: &Weak<Self>| Self {
+            identifier: self.identifier,
+            #[cfg(feature = "doc")]
+            documentation: self.documentation,
+            attributes: self.attributes,
+            generics: self.generics,
+            parameters: self.parameters,
+            return_type: self.return_type,
+            function: Some(self.function.unwrap_or_else(|| {
+                let me = me.clone();
+                Box::new(move |generics, parameters| {
+                    me.upgrade().unwrap().call_design(generics, parameters)
+                })
+            })),
+            design: self.design,
+            evaluation: self.evaluation,
+            auto_reference: me.clone(),
+        })
+    }
+
+    /// Sets the value the function evaluates to, checking it against parameters and return type.
+    pub fn set_design(
+        &self,
+        value: DesignedValue,
+        design_reference: Option<Arc<dyn Reference>>,
+    ) -> LogicResult<()> {
+        if calls(&value, &self.identifier, &mut Vec::new()) {
+            return LogicResult::new_failure(LogicError::recursive_function(
+                245,
+                self.identifier.clone(),
+                design_reference,
+            ));
+        }
+
+        let scope: Arc<dyn Parameterized> = Arc::new(FunctionScope {
+            function: self.auto_reference.upgrade().unwrap(),
+            parameters: self
+                .parameters
+                .iter()
+                .map(|parameter| (parameter.name().to_string(), parameter.clone()))
+                .collect(),
+        });
+        let generics = Arc::new(RwLock::new(
+            self.generics
+                .iter()
+                .map(|generic| {
+                    (
+                        generic.name.clone(),
+                        DescribedType::Generic(Box::new(generic.clone())),
+                    )
+                })
+                .collect(),
+        ));
+
+        let mut result = value.check(
+            &self.return_type,
+            &scope,
+            &generics,
+            &self.identifier,
+            &generics,
+            "return",
+            Variability::Var,
+            &design_reference,
+        );
+
+        if result.is_success() && result.has_errors() {
+            result = result.and(LogicResult::new_failure(LogicError::erroneous_design(
+                246,
+                self.identifier.clone(),
+                design_reference.clone(),
+            )));
+        }
+
+        let mut evaluation = self.evaluation(&value, &design_reference);
+        if evaluation.is_success() && evaluation.has_errors() {
+            evaluation = evaluation.and(LogicResult::new_failure(LogicError::erroneous_design(
+                259,
+                self.identifier.clone(),
+                design_reference,
+            )));
+        }
+
+        result.and(evaluation).and_then(|evaluation| {
+            *self.evaluation.write().unwrap() = Some(evaluation);
+            *self.design.write().unwrap() = Some(value);
+            LogicResult::new_success(())
+        })
+    }
+
+    pub fn design(&self) -> Option<DesignedValue> {
+        self.design.read().unwrap().clone()
+    }
+
+    /// Turns designed value into its evaluation, rejecting elements that cannot be evaluated within a function.
+    fn evaluation(
+        &self,
+        value: &DesignedValue,
+        design_reference: &Option<Arc<dyn Reference>>,
+    ) -> LogicResult<Evaluation> {
+        match value {
+            DesignedValue::Raw(value) => LogicResult::new_success(Evaluation::Value(value.clone())),
+            DesignedValue::Constant(constant) => {
+                LogicResult::new_success(Evaluation::Value(constant.value().clone()))
+            }
+            DesignedValue::Array(array) => {
+                let mut result = LogicResult::new_success(());
+                let mut evaluations = Vec::with_capacity(array.len());
+                for value in array {
+                    if let Some(evaluation) =
+                        result.merge_degrade_failure(self.evaluation(value, design_reference))
+                    {
+                        evaluations.push(evaluation);
+                    }
+                }
+                result.and(LogicResult::new_success(Evaluation::Array(evaluations)))
+            }
+            DesignedValue::Variable(name) => {
+                if let Some(position) = self
+                    .parameters
+                    .iter()
+                    .position(|parameter| parameter.name() == name)
+                {
+                    LogicResult::new_success(Evaluation::Parameter(position))
+                } else {
+                    LogicResult::new_failure(LogicError::unexisting_variable(
+                        256,
+                        self.identifier.clone(),
+                        "return".to_string(),
+                        name.clone(),
+                        design_reference.clone(),
+                    ))
+                }
+            }
+            DesignedValue::Context(context, _) => {
+                LogicResult::new_failure(LogicError::unavailable_context(
+                    257,
+                    self.identifier.clone(),
+                    context.identifier().clone(),
+                    design_reference.clone(),
+                ))
+            }
+            DesignedValue::Function(descriptor, function_generics, parameters) => {
+                let mut result = LogicResult::new_success(());
+                for described_type in function_generics.values() {
+                    if let DescribedType::Generic(generic) = described_type.final_type() {
+                        if !self.generics.iter().any(|g| g.name == generic.name) {
+                            result.errors_mut().push(LogicError::undefined_generic(
+                                258,
+                                self.identifier.clone(),
+                                descriptor.identifier().clone(),
+                                described_type.clone(),
+                                design_reference.clone(),
+                            ));
+                        }
+                    }
+                }
+                let mut evaluations = Vec::with_capacity(parameters.len());
+                for value in parameters {
+                    if let Some(evaluation) =
+                        result.merge_degrade_failure(self.evaluation(value, design_reference))
+                    {
+                        evaluations.push(evaluation);
+                    }
+                }
+                result.and(LogicResult::new_success(Evaluation::Call(
+                    Arc::clone(descriptor),
+                    function_generics.clone(),
+                    evaluations,
+                )))
+            }
+        }
+    }
+
+    fn call_design(&self, generics: HashMap<String, DataType>, parameters: Vec<Value>) -> Value {
+        let generics = generics
+            .iter()
+            .map(|(name, data_type)| (name.clone(), DescribedType::from(data_type)))
+            .collect();
+
+        // Functions whose design got rejected make their script fail to build, so they are never called.
+        self.evaluation
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|evaluation| evaluation.evaluate(&generics, &parameters))
+            .unwrap_or(Value::Void(()))
+    }
+}
+
+/// Tells if designed value calls the function, directly or through other designed functions.
+fn calls(value: &DesignedValue, identifier: &Identifier, visited: &mut Vec<Identifier>) -> bool {
+    match value {
+        DesignedValue::Array(array) => array.iter().any(|value| calls(value, identifier, visited)),
+        DesignedValue::Function(descriptor, _, parameters) => {
+            if descriptor.identifier() == identifier
+                || parameters
+                    .iter()
+                    .any(|value| calls(value, identifier, visited))
+            {
+                return true;
+            }
+            if visited.contains(descriptor.identifier()) {
+                return false;
+            }
+            visited.push(descriptor.identifier().clone());
+
+            Arc::clone(descriptor)
+                .downcast_arc::<Function>()
+                .ok()
+                .and_then(|function| function.design())
+                .map(|design| calls(&design, identifier, visited))
+                .unwrap_or(false)
+        }
+        DesignedValue::Raw(_)
+        | DesignedValue::Variable(_)
+        | DesignedValue::Constant(_)
+        | DesignedValue::Context(_, _) => false,
+    }
+}
+
+/// Evaluable form of a function design, only made of elements available within function calls.
+enum Evaluation {
+    Value(Value),
+    Array(Vec<Evaluation>),
+    Parameter(usize),
+    Call(
+        Arc<dyn FunctionDescriptor>,
+        HashMap<String, DescribedType>,
+        Vec<Evaluation>,
+    ),
+}
+
+impl Evaluation {
+    /// Evaluates with generics and parameters being the ones of the function call.
+    fn evaluate(
+        &self,
+        generics: &HashMap<String, DescribedType>,
+        parameters: &Vec<Value>,
+    ) -> Value {
+        match self {
+            Evaluation::Value(value) => value.clone(),
+            Evaluation::Array(array) => Value::Vec(
+                array
+                    .iter()
+                    .map(|evaluation| evaluation.evaluate(generics, parameters))
+                    .collect(),
+            ),
+            Evaluation::Parameter(position) => parameters[*position].clone(),
+            Evaluation::Call(descriptor, function_generics, evaluations) => {
+                let function_generics = function_generics
+                    .iter()
+                    .filter_map(|(name, described_type)| {
+                        described_type
+                            .to_datatype(generics)
+                            .map(|data_type| (name.clone(), data_type))
+                    })
+                    .collect();
+                let parameters = evaluations
+                    .iter()
+                    .map(|evaluation| evaluation.evaluate(generics, parameters))
+                    .collect();
+
+                descriptor.function()(function_generics, parameters)
+            }
+        }
+    }
+}
+
+/// Scope of a function design, giving access to its parameters as variables.
+#[derive(Debug)]
+struct FunctionScope {
+    function: Arc<Function>,
+    parameters: HashMap<String, Parameter>,
+}
+
+impl Attribuable for FunctionScope {
+    fn attributes(&self) -> &Attributes {
+        self.function.attributes()
+    }
+}
+
+impl Identified for FunctionScope {
+    fn identifier(&self) -> &Identifier {
+        self.function.identifier()
+    }
+
+    fn make_use(&self, identifier: &Identifier) -> bool {
+        self.function.make_use(identifier)
+    }
+
+    fn uses(&self) -> Vec<Identifier> {
+        self.function.uses()
+    }
+}
+
+impl Generics for FunctionScope {
+    fn generics(&self) -> &Vec<Generic> {
+        self.function.generics()
+    }
+}
+
+impl Parameterized for FunctionScope {
+    fn parameters(&self) -> &HashMap<String, Parameter> {
+        &self.parameters
+    }
+
+    fn as_identified(&self) -> Arc<dyn Identified> {
+        Arc::clone(&self.function) as Arc<dyn Identified>
+    }
+}
+
+impl Display for FunctionScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(&self.function, f)
+    }
+}
+
+impl Attribuable for Function {
+    fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+}
+
+impl FunctionDescriptor for Function {
+    fn return_type(&self) -> &DescribedType {
+        &self.return_type
+    }
+
+    fn function(&self) -> &(dyn Fn(HashMap<String, DataType>, Vec<Value>) -> Value + Send + Sync) {
+        self.function.as_ref().unwrap()
+    }
+
+    fn as_identified(&self) -> Arc<dyn Identified> {
+        self.auto_reference.upgrade().unwrap()
+    }
+
+    fn as_ordered_parameterized(&self) -> Arc<dyn OrderedParameterized> {
+        self.auto_reference.upgrade().unwrap()
+    }
+}
+
+impl Identified for Function {
+    fn identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    fn make_use(&self, identifier: &Identifier) -> bool {
+        self.uses().contains(identifier)
+    }
+
+    fn uses(&self) -> Vec<Identifier> {
+        let mut uses = Vec::new();
+        if let Some(design) = self.design.read().unwrap().as_ref() {
+            uses.extend(design.uses());
+        }
+        if let Some(data) = self.return_type.final_type().data() {
+            uses.push(data.identifier().clone());
+            uses.extend(data.uses());
+        }
+        for param in &self.parameters {
+            if let Some(data) = param.described_type().final_type().data() {
+                uses.push(data.identifier().clone());
+                uses.extend(data.uses());
+            }
+        }
+        uses
+    }
+}
+
+impl Documented for Function {
+    fn documentation(&self) -> &str {
+        #[cfg(feature = "doc")]
+        {
+            &self.documentation
+        }
+        #[cfg(not(feature = "doc"))]
+        {
+            &""
+        }
+    }
+}
+
+impl OrderedParameterized for Function {
+    fn parameters(&self) -> &Vec<Parameter> {
+        &self.parameters
+    }
+
+    fn as_identified(&self) -> Arc<dyn Identified> {
+        self.auto_reference.upgrade().unwrap()
+    }
+}
+
+impl Generics for Function {
+    fn generics(&self) -> &Vec<Generic> {
+        &self.generics
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "function {}({})",
+            self.identifier.to_string(),
+            self.parameters()
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )?;
+
+        Ok(())
+    }
+}
+
+impl Debug for Function {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Function")
+            .field("identifier", &self.identifier)
+            .field("attributes", &self.attributes)
+            .field("generics", &self.generics)
+            .field("parameters", &self.parameters)
+            .field("return_type", &self.return_type)
+            .finish()
+    }
+}
@@ -1,5 +1,13 @@
+mod constant;
+mod data;
+mod data_value;
+mod function;
 mod model;
 mod treatment;
 
+pub use constant::Constant;
+pub use data::Data;
+pub use data_value::DataValue;
+pub use function::{FnFunction, Function};
 pub use model::Model;
 pub use treatment::Treatment;
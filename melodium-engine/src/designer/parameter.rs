@@ -88,6 +88,23 @@ impl Parameter {
                     Ok(Value::Array(new_array))
                 }
                 Value::Variable(variable) => Ok(Value::Variable(variable.clone())),
+                Value::Constant(former_constant) => {
+                    if let Some(Entry::Constant(new_constant)) = collection.get(
+                        &replace
+                            .get(former_constant.identifier())
+                            .unwrap_or_else(|| former_constant.identifier())
+                            .into(),
+                    ) {
+                        Ok(Value::Constant(new_constant.clone()))
+                    } else {
+                        Err(LogicError::unexisting_constant(
+                            248,
+                            scope_id.clone(),
+                            former_constant.identifier().into(),
+                            design_reference.clone(),
+                        ))
+                    }
+                }
                 Value::Context(former_context, entry) => {
                     if let Some(Entry::Context(new_context)) = collection.get(
                         &replace
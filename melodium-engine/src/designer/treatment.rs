@@ -9,12 +9,13 @@ use crate::design::{
     TreatmentInstanciation as TreatmentInstanciationDesign, IO as IODesign,
 };
 use crate::error::{LogicError, LogicResult};
+use crate::lint::{Lint, LintWarning};
 use core::fmt::Debug;
 use melodium_common::descriptor::{
-    Attribuable, Attributes, Collection, DescribedType, Entry, Generics, Identified, Identifier,
-    IdentifierRequirement, Parameterized, Treatment as TreatmentTrait,
+    Attribuable, Attributes, Collection, DescribedType, Entry, Flow, Generics, Identified,
+    Identifier, IdentifierRequirement, Parameterized, Treatment as TreatmentTrait,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock, RwLockReadGuard, Weak};
 
 #[derive(Debug)]
@@ -994,6 +995,163 @@ impl Treatment {
         result
     }
 
+    /// Gives lint warnings about the design, except the ones allowed through `#[allow(…)]` attributes.
+    pub fn lint(&self) -> Vec<LintWarning> {
+        let descriptor = self.descriptor();
+        let identifier = descriptor.identifier();
+        let mut warnings = Vec::new();
+
+        // Checking all parameters are used by some instanciation.
+        let used_variables = self
+            .model_instanciations
+            .values()
+            .flat_map(|model| {
+                model
+                    .read()
+                    .unwrap()
+                    .parameters()
+                    .values()
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .chain(self.treatments.values().flat_map(|treatment| {
+                treatment
+                    .read()
+                    .unwrap()
+                    .parameters()
+                    .values()
+                    .cloned()
+                    .collect::<Vec<_>>()
+            }))
+            .flat_map(|parameter| {
+                parameter
+                    .read()
+                    .unwrap()
+                    .value()
+                    .as_ref()
+                    .map(|value| value.variables())
+                    .unwrap_or_default()
+            })
+            .collect::<HashSet<_>>();
+        let mut parameters = descriptor.parameters().iter().collect::<Vec<_>>();
+        parameters.sort_by_key(|(name, _)| *name);
+        for (name, parameter) in parameters {
+            if !used_variables.contains(name)
+                && !Lint::UnusedParameter.is_allowed_by(parameter.attributes())
+            {
+                warnings.push(LintWarning::unused_parameter(
+                    identifier.clone(),
+                    name.clone(),
+                    self.design_reference.clone(),
+                ));
+            }
+        }
+
+        let mut treatments = self.treatments.iter().collect::<Vec<_>>();
+        treatments.sort_by_key(|(name, _)| *name);
+
+        // Checking treatments having outputs get at least one connected.
+        for (name, arc_treatment) in &treatments {
+            let treatment = arc_treatment.read().unwrap();
+            if treatment.descriptor().outputs().is_empty()
+                || Lint::UnconnectedOutputs.is_allowed_by(treatment.attributes())
+            {
+                continue;
+            }
+
+            let connected = self.connections.iter().any(|connection| {
+                connection.output_treatment == IO::Treatment(Arc::downgrade(arc_treatment))
+            });
+            if !connected {
+                warnings.push(LintWarning::unconnected_outputs(
+                    identifier.clone(),
+                    name.to_string(),
+                    treatment.design_reference().clone(),
+                ));
+            }
+        }
+
+        // Checking blocking inputs are not fed by streams depending on the treatment itself.
+        for (name, arc_treatment) in &treatments {
+            let treatment = arc_treatment.read().unwrap();
+            if Lint::UnclosableStream.is_allowed_by(treatment.attributes()) {
+                continue;
+            }
+
+            let mut inputs = treatment
+                .descriptor()
+                .inputs()
+                .iter()
+                .filter(|(_, input)| *input.flow() == Flow::Block)
+                .map(|(input_name, _)| input_name.clone())
+                .collect::<Vec<_>>();
+            inputs.sort();
+            for input_name in inputs {
+                let fed_by_itself = self.connections.iter().any(|connection| {
+                    connection.input_treatment == IO::Treatment(Arc::downgrade(arc_treatment))
+                        && connection.input_name == input_name
+                        && match &connection.output_treatment {
+                            IO::Treatment(feeding) => feeding
+                                .upgrade()
+                                .map(|feeding| self.reaches_through_stream(arc_treatment, &feeding))
+                                .unwrap_or(false),
+                            IO::Sequence() => false,
+                        }
+                });
+                if fed_by_itself {
+                    warnings.push(LintWarning::unclosable_stream(
+                        identifier.clone(),
+                        name.to_string(),
+                        input_name,
+                        treatment.design_reference().clone(),
+                    ));
+                }
+            }
+        }
+
+        warnings.retain(|warning| !warning.lint().is_allowed_by(descriptor.attributes()));
+        warnings
+    }
+
+    /// Tells if `to` can be reached from outputs of `from` through connections, at least one of them being a stream.
+    fn reaches_through_stream(
+        &self,
+        from: &Arc<RwLock<TreatmentInstanciation>>,
+        to: &Arc<RwLock<TreatmentInstanciation>>,
+    ) -> bool {
+        let mut visited = HashSet::new();
+        let mut pending = vec![(Arc::clone(from), false)];
+
+        while let Some((current, through_stream)) = pending.pop() {
+            if !visited.insert((Arc::as_ptr(&current), through_stream)) {
+                continue;
+            }
+
+            let current_descriptor = current.read().unwrap().descriptor();
+            for connection in &self.connections {
+                if connection.output_treatment != IO::Treatment(Arc::downgrade(&current)) {
+                    continue;
+                }
+                if let IO::Treatment(next) = &connection.input_treatment {
+                    if let Some(next) = next.upgrade() {
+                        let through_stream = through_stream
+                            || current_descriptor
+                                .outputs()
+                                .get(&connection.output_name)
+                                .map(|output| *output.flow() == Flow::Stream)
+                                .unwrap_or(false);
+                        if through_stream && Arc::ptr_eq(&next, to) {
+                            return true;
+                        }
+                        pending.push((next, through_stream));
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
     pub fn make_use(&self, identifier: &Identifier) -> bool {
         self.unvalidated_design()
             .success()
@@ -1,6 +1,7 @@
 use core::fmt::{Display, Formatter, Result};
 use melodium_common::descriptor::{
-    Context, DataType, DescribedType, Function, Generic, Identifier, Parameterized, Variability,
+    Constant, Context, DataType, DescribedType, Function, Generic, Identifier, Parameterized,
+    Variability,
 };
 use melodium_common::executive::Value as ExecutiveValue;
 use std::collections::HashMap;
@@ -15,6 +16,7 @@ pub enum Value {
     Raw(ExecutiveValue),
     Array(Vec<Value>),
     Variable(String),
+    Constant(Arc<dyn Constant>),
     Context(Arc<dyn Context>, String),
     Function(
         Arc<dyn Function>,
@@ -29,6 +31,7 @@ impl Value {
             Value::Raw(_) => false,
             Value::Array(array) => array.iter().any(|val| val.make_use(identifier)),
             Value::Variable(_) => false,
+            Value::Constant(constant) => constant.identifier() == identifier,
             Value::Context(context, _) => context.identifier() == identifier,
             Value::Function(function, described_types, values) => {
                 function.identifier() == identifier
@@ -55,6 +58,7 @@ impl Value {
                 ids.extend(identifiers);
                 ids
             }),
+            Value::Constant(constant) => vec![constant.identifier().clone()],
             Value::Context(context, _) => vec![context.identifier().clone()],
             Value::Function(function, described_types, values) => {
                 let mut uses = vec![function.identifier().clone()];
@@ -67,6 +71,17 @@ impl Value {
         }
     }
 
+    /// Gives names of variables the value refers to.
+    pub fn variables(&self) -> Vec<String> {
+        match self {
+            Value::Variable(name) => vec![name.clone()],
+            Value::Array(values) | Value::Function(_, _, values) => {
+                values.iter().flat_map(|value| value.variables()).collect()
+            }
+            Value::Raw(_) | Value::Constant(_) | Value::Context(_, _) => vec![],
+        }
+    }
+
     pub fn check(
         &self,
         described_type: &DescribedType,
@@ -179,6 +194,26 @@ impl Value {
                     ))
                 }
             }
+            Value::Constant(constant) => {
+                if described_type.is_compatible(
+                    &parent_generics.read().unwrap(),
+                    constant.described_type(),
+                    &HashMap::new(),
+                ) {
+                    LogicResult::new_success(Variability::Const)
+                } else {
+                    LogicResult::new_failure(LogicError::unmatching_datatype(
+                        247,
+                        scope_descriptor.identifier().clone(),
+                        called_id.clone(),
+                        parameter_name.to_string(),
+                        self.clone(),
+                        described_type.clone(),
+                        constant.described_type().clone(),
+                        design_reference.clone(),
+                    ))
+                }
+            }
             Value::Context(context, name) => {
                 let mut result = LogicResult::new_success(());
                 if parameter_variability == Variability::Const {
@@ -302,6 +337,7 @@ impl Display for Value {
                     .join(", ")
             ),
             Value::Variable(name) => write!(f, "{}", name),
+            Value::Constant(desc) => write!(f, "{}", desc.identifier().name()),
             Value::Context(desc, entry) => write!(f, "{}[{}]", desc.name(), entry),
             Value::Function(desc, described_types, params) => write!(
                 f,
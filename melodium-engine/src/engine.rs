@@ -1,26 +1,46 @@
 use crate::{
-    debug::{DebugLevel, Event},
+    debug::{DebugLevel, Event, Inspector},
     error::{LogicErrors, LogicResult},
+    metrics::Metrics,
 };
 use async_std::channel::Sender;
 use async_trait::async_trait;
 use melodium_common::{
     descriptor::{Collection, Identifier},
-    executive::{DirectCreationCallback, Level as LogLevel, Log, Value},
+    executive::{DirectCreationCallback, Log, LogFilter, ResultStatus, Value},
 };
 use std::{collections::HashMap, sync::Arc};
 
 #[async_trait]
 pub trait Engine: Send + Sync {
     fn collection(&self) -> Arc<Collection>;
-    fn genesis(&self, entry: &Identifier, params: HashMap<String, Value>) -> LogicResult<()>;
+    /// Builds the program starting from `entry` treatment.
+    ///
+    /// `overrides` gives models or treatments to build in place of other ones, keyed by identifier of the overridden element,
+    /// allowing to substitute stand-ins to elements the program uses, as for testing purposes.
+    fn genesis(
+        &self,
+        entry: &Identifier,
+        params: HashMap<String, Value>,
+        overrides: HashMap<Identifier, Identifier>,
+    ) -> LogicResult<()>;
     fn errors(&self) -> LogicErrors;
+    /// Non-ok statuses reported by tracks and continuous tasks while living.
+    fn failures(&self) -> Vec<ResultStatus>;
+    /// Exit code requested by the program while living, if any.
+    fn exit_code(&self) -> Option<i32>;
     fn set_auto_end(&self, auto_end: bool);
     fn auto_end(&self) -> bool;
-    fn log_level(&self) -> LogLevel;
+    fn log_filter(&self) -> LogFilter;
     fn add_logs_listener(&self, sender: Sender<Log>);
     fn debug_level(&self) -> DebugLevel;
     fn add_debug_listener(&self, sender: Sender<Event>);
+    /// Sets inspector of transmissions, see [`Inspector`].
+    fn set_inspector(&self, inspector: Arc<dyn Inspector>);
+    /// Enables runtime metrics, giving them.
+    ///
+    /// Metrics have to be enabled before genesis for transmissions to be counted.
+    fn enable_metrics(&self) -> Arc<Metrics>;
     async fn live(&self);
     async fn instanciate(&self, callback: Option<DirectCreationCallback>) -> LogicResult<()>;
     async fn end(&self);
@@ -36,6 +36,14 @@ pub enum LogicErrorKind {
     LaunchWrongParameter { parameter: String },
     /// The launch cannot be done through model-requiring treatment.
     LaunchTreatmentExpectModel { wrong_identifier: Identifier },
+    /// The element to override or overriding one doesn't exist, or is neither a model nor a treatment.
+    UnexistingOverride { identifier: Identifier },
+    /// The overriding element cannot be used in place of the overridden one.
+    IncompatibleOverride {
+        overridden: Identifier,
+        overriding: Identifier,
+        reason: String,
+    },
     /// No direct track exist for id.
     NoDirectTrack { id: TrackId },
     /// The referenced variable for value doesn't exist.
@@ -260,6 +268,13 @@ pub enum LogicErrorKind {
         described_type: DescribedType,
         unsatisfied_traits: Vec<DataTrait>,
     },
+    /// A function calls itself within its own body, directly or through other functions
+    RecursiveFunction { function: Identifier },
+    /// The constant is not existing within current available constants.
+    UnexistingConstant {
+        scope: Identifier,
+        claimed: IdentifierRequirement,
+    },
 }
 
 impl LogicErrorKind {
@@ -287,6 +302,8 @@ impl Display for LogicErrorKind {
                 },
             LogicErrorKind::LaunchWrongParameter { parameter } => write!(f, "Parameter '{parameter}' has no valid value for launch"),
             LogicErrorKind::LaunchTreatmentExpectModel {wrong_identifier} => write!(f, "Launch must be done with a treatment that does not require model, but '{wrong_identifier}' expect some model"),
+            LogicErrorKind::UnexistingOverride { identifier } => write!(f, "No model or treatment '{identifier}' available for override"),
+            LogicErrorKind::IncompatibleOverride { overridden, overriding, reason } => write!(f, "'{overriding}' cannot override '{overridden}', {reason}"),
             LogicErrorKind::NoDirectTrack { id } => write!(f, "No directly instancied track exist for id {id}"),
             LogicErrorKind::UnexistingVariable {identifier,
                 parameter,
@@ -338,6 +355,8 @@ impl Display for LogicErrorKind {
             LogicErrorKind::UnexistingGeneric { scope, element, name, described_type } => write!(f, "The generic type '{name}' ({}) doesn't exist for '{element}' in '{scope}'", Self::described_type_details(described_type)),
             LogicErrorKind::UndefinedGeneric { scope, element, described_type } => write!(f, "Generic '{described_type}' ({}) is not defined for '{element}' in '{scope}'", Self::described_type_details(described_type)),
             LogicErrorKind::UnsatisfiedTraits { scope, element, described_type, unsatisfied_traits } => write!(f, "Type '{described_type}' ({}) does not satisfy trait {} for '{element}' in '{scope}'", Self::described_type_details(described_type), unsatisfied_traits.iter().map(|tr| tr.to_string()).collect::<Vec<_>>().join(" + ")),
+            LogicErrorKind::RecursiveFunction { function } => write!(f, "Function '{function}' cannot call itself, directly or through other functions"),
+            LogicErrorKind::UnexistingConstant { scope: _, claimed } => write!(f, "Constant '{claimed}' does not exist"),
         }
     }
 }
@@ -450,6 +469,33 @@ impl LogicError {
         }
     }
 
+    /// Generates a new error with [`LogicErrorKind::UnexistingOverride`] kind.
+    pub fn unexisting_override(id: u32, identifier: Identifier) -> Self {
+        Self {
+            id,
+            design_reference: None,
+            kind: LogicErrorKind::UnexistingOverride { identifier },
+        }
+    }
+
+    /// Generates a new error with [`LogicErrorKind::IncompatibleOverride`] kind.
+    pub fn incompatible_override(
+        id: u32,
+        overridden: Identifier,
+        overriding: Identifier,
+        reason: String,
+    ) -> Self {
+        Self {
+            id,
+            design_reference: None,
+            kind: LogicErrorKind::IncompatibleOverride {
+                overridden,
+                overriding,
+                reason,
+            },
+        }
+    }
+
     pub fn no_direct_track(id: u32, track_id: TrackId) -> Self {
         Self {
             id,
@@ -1161,6 +1207,33 @@ impl LogicError {
             },
         }
     }
+
+    /// Generates a new error with [`LogicErrorKind::RecursiveFunction`] kind.
+    pub fn recursive_function(
+        id: u32,
+        function: Identifier,
+        design_reference: Option<Arc<dyn Reference>>,
+    ) -> Self {
+        Self {
+            id,
+            design_reference,
+            kind: LogicErrorKind::RecursiveFunction { function },
+        }
+    }
+
+    /// Generates a new error with [`LogicErrorKind::UnexistingConstant`] kind.
+    pub fn unexisting_constant(
+        id: u32,
+        scope: Identifier,
+        claimed: IdentifierRequirement,
+        design_reference: Option<Arc<dyn Reference>>,
+    ) -> Self {
+        Self {
+            id,
+            design_reference,
+            kind: LogicErrorKind::UnexistingConstant { scope, claimed },
+        }
+    }
 }
 
 impl Display for LogicError {
@@ -12,20 +12,22 @@ pub mod designer;
 pub mod engine;
 pub mod error;
 pub(crate) mod ids;
+pub mod lint;
+pub mod metrics;
 mod transmission;
 mod world;
 
 pub use engine::Engine;
 pub use error::{LogicError, LogicErrors, LogicResult};
-use melodium_common::{descriptor::Collection, executive::Level};
+use melodium_common::{descriptor::Collection, executive::LogFilter};
 use std::sync::Arc;
 
 pub fn new_engine(
     collection: Arc<Collection>,
-    log_level: Level,
+    log_filter: LogFilter,
     debug_level: crate::debug::DebugLevel,
 ) -> Arc<dyn Engine> {
-    world::World::new(collection, log_level, debug_level)
+    world::World::new(collection, log_filter, debug_level)
 }
 
 pub mod build {
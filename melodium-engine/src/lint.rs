@@ -0,0 +1,373 @@
+//! Provides Mélodium lints, warning about designs that are valid but most likely mistaken.
+//!
+//! The main type of this module is [`LintWarning`], telling which [`Lint`] is concerned with kind of warning detailed with [`LintKind`].
+//! Any lint can be silenced on an element through `#[allow(<lint>, …)]` attribute, giving lint names or codes.
+
+use crate::descriptor::Treatment as DesignedTreatment;
+use crate::designer::Reference;
+use core::convert::TryFrom;
+use core::fmt::{Debug, Display};
+use melodium_common::descriptor::{
+    Attribuable, Attributes, Collection, Entry, Identified, Identifier,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Attribute listing lints that are allowed on element.
+pub const ALLOW_ATTRIBUTE: &str = "allow";
+
+/// Lint that can be checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Lint {
+    /// A `use` is never referenced within its script.
+    UnusedUse,
+    /// A parameter of designed treatment is never used.
+    UnusedParameter,
+    /// None of the outputs of treatment instanciation are connected.
+    UnconnectedOutputs,
+    /// A blocking input is fed by data depending on stream that cannot close before the treatment ends.
+    UnclosableStream,
+    /// A treatment is not reachable from any entrypoint.
+    UnreachableTreatment,
+}
+
+impl Lint {
+    /// All the existing lints.
+    pub const ALL: [Lint; 5] = [
+        Lint::UnusedUse,
+        Lint::UnusedParameter,
+        Lint::UnconnectedOutputs,
+        Lint::UnclosableStream,
+        Lint::UnreachableTreatment,
+    ];
+
+    /// Stable code of the lint.
+    pub fn code(&self) -> u32 {
+        match self {
+            Lint::UnusedUse => 1,
+            Lint::UnusedParameter => 2,
+            Lint::UnconnectedOutputs => 3,
+            Lint::UnclosableStream => 4,
+            Lint::UnreachableTreatment => 5,
+        }
+    }
+
+    /// Name of the lint, as used in `#[allow(…)]` attribute.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnusedUse => "unused_use",
+            Lint::UnusedParameter => "unused_parameter",
+            Lint::UnconnectedOutputs => "unconnected_outputs",
+            Lint::UnclosableStream => "unclosable_stream",
+            Lint::UnreachableTreatment => "unreachable_treatment",
+        }
+    }
+
+    /// Tells if lint is allowed by the `#[allow(…)]` attribute within `attributes`.
+    pub fn is_allowed_by(&self, attributes: &Attributes) -> bool {
+        attributes
+            .get(ALLOW_ATTRIBUTE)
+            .map(|allowed| {
+                allowed
+                    .split(',')
+                    .any(|lint| Lint::try_from(lint.trim()).ok() == Some(*self))
+            })
+            .unwrap_or(false)
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "W{:04}", self.code())
+    }
+}
+
+impl TryFrom<&str> for Lint {
+    type Error = String;
+
+    /// Gives lint from its name or code.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Lint::ALL
+            .iter()
+            .find(|lint| lint.name() == value || lint.to_string() == value)
+            .copied()
+            .ok_or_else(|| value.to_string())
+    }
+}
+
+/// Level at which lint is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    /// Lint is not reported.
+    Allow,
+    /// Lint is reported as warning.
+    Warn,
+    /// Lint is reported as error.
+    Deny,
+}
+
+/// Configuration of lint levels, every lint being at [`LintLevel::Warn`] level unless set otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct LintConfig {
+    levels: HashMap<Lint, LintLevel>,
+}
+
+impl LintConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_level(&mut self, lint: Lint, level: LintLevel) {
+        self.levels.insert(lint, level);
+    }
+
+    pub fn level(&self, lint: Lint) -> LintLevel {
+        self.levels.get(&lint).copied().unwrap_or(LintLevel::Warn)
+    }
+}
+
+/// Kind of lint warning that might be given.
+#[derive(Debug, Clone)]
+pub enum LintKind {
+    /// The element used as `name` in `script` is never referenced.
+    UnusedUse { script: String, name: String },
+    /// The `parameter` of treatment `identifier` is never used.
+    UnusedParameter {
+        identifier: Identifier,
+        parameter: String,
+    },
+    /// The outputs of `treatment` in `identifier` are never connected.
+    UnconnectedOutputs {
+        identifier: Identifier,
+        treatment: String,
+    },
+    /// The blocking `input` of `treatment` in `identifier` is fed by data depending on its own output streams.
+    UnclosableStream {
+        identifier: Identifier,
+        treatment: String,
+        input: String,
+    },
+    /// The treatment `identifier` is not reachable from any entrypoint.
+    UnreachableTreatment { identifier: Identifier },
+}
+
+impl LintKind {
+    /// Gives lint this kind of warning belongs to.
+    pub fn lint(&self) -> Lint {
+        match self {
+            LintKind::UnusedUse { .. } => Lint::UnusedUse,
+            LintKind::UnusedParameter { .. } => Lint::UnusedParameter,
+            LintKind::UnconnectedOutputs { .. } => Lint::UnconnectedOutputs,
+            LintKind::UnclosableStream { .. } => Lint::UnclosableStream,
+            LintKind::UnreachableTreatment { .. } => Lint::UnreachableTreatment,
+        }
+    }
+}
+
+impl Display for LintKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintKind::UnusedUse { script, name } => {
+                write!(f, "Used element '{name}' is never referenced in '{script}'")
+            }
+            LintKind::UnusedParameter {
+                identifier,
+                parameter,
+            } => write!(f, "Parameter '{parameter}' of '{identifier}' is never used"),
+            LintKind::UnconnectedOutputs {
+                identifier,
+                treatment,
+            } => write!(
+                f,
+                "No output of treatment '{treatment}' is connected in '{identifier}'"
+            ),
+            LintKind::UnclosableStream {
+                identifier,
+                treatment,
+                input,
+            } => write!(
+                f,
+                "Blocking input '{input}' of treatment '{treatment}' in '{identifier}' is fed through streams coming from '{treatment}' itself, that cannot close before it ends"
+            ),
+            LintKind::UnreachableTreatment { identifier } => write!(
+                f,
+                "Treatment '{identifier}' is not reachable from any entrypoint"
+            ),
+        }
+    }
+}
+
+/// Handles and describe a Mélodium lint warning.
+#[derive(Debug, Clone)]
+pub struct LintWarning {
+    /// Kind of warning.
+    pub kind: LintKind,
+    /// Optional design reference attached to warning.
+    pub design_reference: Option<Arc<dyn Reference>>,
+}
+
+impl LintWarning {
+    /// Generates a new warning with [`LintKind::UnusedUse`] kind.
+    pub fn unused_use(
+        script: String,
+        name: String,
+        design_reference: Option<Arc<dyn Reference>>,
+    ) -> Self {
+        Self {
+            kind: LintKind::UnusedUse { script, name },
+            design_reference,
+        }
+    }
+
+    /// Generates a new warning with [`LintKind::UnusedParameter`] kind.
+    pub fn unused_parameter(
+        identifier: Identifier,
+        parameter: String,
+        design_reference: Option<Arc<dyn Reference>>,
+    ) -> Self {
+        Self {
+            kind: LintKind::UnusedParameter {
+                identifier,
+                parameter,
+            },
+            design_reference,
+        }
+    }
+
+    /// Generates a new warning with [`LintKind::UnconnectedOutputs`] kind.
+    pub fn unconnected_outputs(
+        identifier: Identifier,
+        treatment: String,
+        design_reference: Option<Arc<dyn Reference>>,
+    ) -> Self {
+        Self {
+            kind: LintKind::UnconnectedOutputs {
+                identifier,
+                treatment,
+            },
+            design_reference,
+        }
+    }
+
+    /// Generates a new warning with [`LintKind::UnclosableStream`] kind.
+    pub fn unclosable_stream(
+        identifier: Identifier,
+        treatment: String,
+        input: String,
+        design_reference: Option<Arc<dyn Reference>>,
+    ) -> Self {
+        Self {
+            kind: LintKind::UnclosableStream {
+                identifier,
+                treatment,
+                input,
+            },
+            design_reference,
+        }
+    }
+
+    /// Generates a new warning with [`LintKind::UnreachableTreatment`] kind.
+    pub fn unreachable_treatment(
+        identifier: Identifier,
+        design_reference: Option<Arc<dyn Reference>>,
+    ) -> Self {
+        Self {
+            kind: LintKind::UnreachableTreatment { identifier },
+            design_reference,
+        }
+    }
+
+    /// Gives lint this warning belongs to.
+    pub fn lint(&self) -> Lint {
+        self.kind.lint()
+    }
+}
+
+impl Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} [{}]", self.lint(), self.kind, self.lint().name())
+    }
+}
+
+/// Gives lint warnings about the designed treatments of `package` within `collection`.
+///
+/// `roots` are the treatments package is meant to be used through, such as its entrypoints.
+/// Treatments of the package that are not reachable from them are reported, unless `roots` is empty,
+/// package being then considered as library.
+pub fn lint_package(
+    collection: &Arc<Collection>,
+    package: &str,
+    roots: &[Identifier],
+) -> Vec<LintWarning> {
+    let mut treatments = collection
+        .identifiers()
+        .into_iter()
+        .filter(|identifier| identifier.root() == package)
+        .filter_map(|identifier| match collection.get(&(&identifier).into()) {
+            Some(Entry::Treatment(treatment)) => Arc::clone(treatment)
+                .downcast_arc::<DesignedTreatment>()
+                .ok(),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    treatments.sort_by(|a, b| a.identifier().cmp(b.identifier()));
+
+    let mut warnings = Vec::new();
+    for treatment in &treatments {
+        if let Some(designer) = treatment.designer(Arc::clone(collection), None).success() {
+            warnings.extend(designer.read().unwrap().lint());
+        }
+    }
+
+    if !roots.is_empty() {
+        let reached = reachable_treatments(collection, roots);
+        for treatment in &treatments {
+            if !reached.contains(treatment.identifier())
+                && !Lint::UnreachableTreatment.is_allowed_by(treatment.attributes())
+            {
+                warnings.push(LintWarning::unreachable_treatment(
+                    treatment.identifier().clone(),
+                    treatment
+                        .designer(Arc::clone(collection), None)
+                        .success()
+                        .and_then(|designer| designer.read().unwrap().design_reference().clone()),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Gives identifiers of every treatment reachable from `roots`, roots included.
+fn reachable_treatments(collection: &Collection, roots: &[Identifier]) -> HashSet<Identifier> {
+    let mut reached = HashSet::new();
+    let mut pending = roots
+        .iter()
+        .filter_map(|root| match collection.get(&root.into()) {
+            Some(Entry::Treatment(treatment)) => Some(treatment.identifier().clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    while let Some(identifier) = pending.pop() {
+        if !reached.insert(identifier.clone()) {
+            continue;
+        }
+
+        if let Some(Entry::Treatment(treatment)) = collection.get(&(&identifier).into()) {
+            if let Ok(treatment) = Arc::clone(treatment).downcast_arc::<DesignedTreatment>() {
+                if let Some(design) = treatment.design().success() {
+                    pending.extend(
+                        design
+                            .uses()
+                            .into_iter()
+                            .filter(|used| !reached.contains(used)),
+                    );
+                }
+            }
+        }
+    }
+
+    reached
+}
@@ -0,0 +1,247 @@
+//! Provides runtime metrics of engine, as counted while program lives.
+//!
+//! Metrics are only counted once enabled through [`Engine::enable_metrics`](crate::Engine::enable_metrics),
+//! and can be rendered using Prometheus text exposition format with [`Metrics::prometheus`].
+
+use core::fmt::Write;
+use core::sync::atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use melodium_common::descriptor::Identifier;
+use melodium_common::executive::TrackId;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// Source tracks are created from, as `(model, source)`.
+///
+/// Tracks directly created for entrypoint have empty model and `direct` source.
+type TrackSource = (String, String);
+
+/// Transmission point, as `(treatment, name)`.
+type TransmissionPoint = (String, String);
+
+/// Metrics of an output, shared by all the outputs with same name of same treatment.
+#[derive(Debug, Default)]
+pub struct OutputMetrics {
+    sent: AtomicU64,
+    backlog: AtomicI64,
+}
+
+impl OutputMetrics {
+    /// Counts values sent by treatment, kept as backlog until transmitted to inputs.
+    pub(crate) fn sent(&self, count: usize) {
+        self.sent.fetch_add(count as u64, Ordering::Relaxed);
+        self.backlog.fetch_add(count as i64, Ordering::Relaxed);
+    }
+
+    /// Removes values from backlog, being transmitted to inputs (or dropped if none is left).
+    pub(crate) fn transmitted(&self, count: usize) {
+        self.backlog.fetch_sub(count as i64, Ordering::Relaxed);
+    }
+
+    /// Puts back values in backlog, inputs being not ready to receive them.
+    pub(crate) fn held_back(&self, count: usize) {
+        self.backlog.fetch_add(count as i64, Ordering::Relaxed);
+    }
+}
+
+/// Metrics of an input, shared by all the inputs with same name of same treatment.
+#[derive(Debug, Default)]
+pub struct InputMetrics {
+    received: AtomicU64,
+}
+
+impl InputMetrics {
+    /// Counts values received by treatment.
+    pub(crate) fn received(&self, count: usize) {
+        self.received.fetch_add(count as u64, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Default)]
+struct TracksMetrics {
+    created: BTreeMap<TrackSource, u64>,
+    finished: BTreeMap<(TrackSource, bool), u64>,
+    running: HashMap<TrackId, TrackSource>,
+}
+
+/// Runtime metrics of engine.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    tracks: Mutex<TracksMetrics>,
+    outputs: Mutex<BTreeMap<TransmissionPoint, Arc<OutputMetrics>>>,
+    inputs: Mutex<BTreeMap<TransmissionPoint, Arc<InputMetrics>>>,
+    continuous_tasks: AtomicUsize,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts track creation from `source` of `model`, being `None` for tracks directly created for entrypoint.
+    pub(crate) fn track_created(
+        &self,
+        track_id: TrackId,
+        model: Option<&Identifier>,
+        source: &str,
+    ) {
+        let source = (
+            model.map(|model| model.to_string()).unwrap_or_default(),
+            source.to_string(),
+        );
+        let mut tracks = self.tracks.lock().unwrap();
+        *tracks.created.entry(source.clone()).or_default() += 1;
+        tracks.running.insert(track_id, source);
+    }
+
+    /// Counts track end, `ok` telling if everything within track ended successfully.
+    pub(crate) fn track_finished(&self, track_id: TrackId, ok: bool) {
+        let mut tracks = self.tracks.lock().unwrap();
+        if let Some(source) = tracks.running.remove(&track_id) {
+            *tracks.finished.entry((source, ok)).or_default() += 1;
+        }
+    }
+
+    pub(crate) fn set_continuous_tasks(&self, count: usize) {
+        self.continuous_tasks.store(count, Ordering::Relaxed);
+    }
+
+    /// Gives metrics of output `name` of `treatment`.
+    pub(crate) fn output(&self, treatment: &Identifier, name: &str) -> Arc<OutputMetrics> {
+        Arc::clone(
+            self.outputs
+                .lock()
+                .unwrap()
+                .entry((treatment.to_string(), name.to_string()))
+                .or_default(),
+        )
+    }
+
+    /// Gives metrics of input `name` of `treatment`.
+    pub(crate) fn input(&self, treatment: &Identifier, name: &str) -> Arc<InputMetrics> {
+        Arc::clone(
+            self.inputs
+                .lock()
+                .unwrap()
+                .entry((treatment.to_string(), name.to_string()))
+                .or_default(),
+        )
+    }
+
+    /// Renders metrics using Prometheus text exposition format.
+    pub fn prometheus(&self) -> String {
+        let mut text = String::new();
+
+        {
+            let tracks = self.tracks.lock().unwrap();
+
+            header(
+                &mut text,
+                "melodium_tracks_created_total",
+                "counter",
+                "Tracks created, by model and source.",
+            );
+            for ((model, source), count) in &tracks.created {
+                let _ = writeln!(
+                    text,
+                    "melodium_tracks_created_total{{model=\"{}\",source=\"{}\"}} {count}",
+                    escape(model),
+                    escape(source)
+                );
+            }
+
+            header(
+                &mut text,
+                "melodium_tracks_finished_total",
+                "counter",
+                "Tracks finished, by model, source, and result.",
+            );
+            for (((model, source), ok), count) in &tracks.finished {
+                let _ = writeln!(
+                    text,
+                    "melodium_tracks_finished_total{{model=\"{}\",source=\"{}\",result=\"{}\"}} {count}",
+                    escape(model),
+                    escape(source),
+                    if *ok { "ok" } else { "failed" }
+                );
+            }
+        }
+
+        {
+            let outputs = self.outputs.lock().unwrap();
+
+            header(
+                &mut text,
+                "melodium_output_values_sent_total",
+                "counter",
+                "Values sent through outputs, by treatment and output.",
+            );
+            for ((treatment, output), metrics) in outputs.iter() {
+                let _ = writeln!(
+                    text,
+                    "melodium_output_values_sent_total{{treatment=\"{}\",output=\"{}\"}} {}",
+                    escape(treatment),
+                    escape(output),
+                    metrics.sent.load(Ordering::Relaxed)
+                );
+            }
+
+            header(
+                &mut text,
+                "melodium_output_backlog_values",
+                "gauge",
+                "Values sent through outputs but not yet transmitted to inputs, by treatment and output.",
+            );
+            for ((treatment, output), metrics) in outputs.iter() {
+                let _ = writeln!(
+                    text,
+                    "melodium_output_backlog_values{{treatment=\"{}\",output=\"{}\"}} {}",
+                    escape(treatment),
+                    escape(output),
+                    metrics.backlog.load(Ordering::Relaxed).max(0)
+                );
+            }
+        }
+
+        header(
+            &mut text,
+            "melodium_input_values_received_total",
+            "counter",
+            "Values received through inputs, by treatment and input.",
+        );
+        for ((treatment, input), metrics) in self.inputs.lock().unwrap().iter() {
+            let _ = writeln!(
+                text,
+                "melodium_input_values_received_total{{treatment=\"{}\",input=\"{}\"}} {}",
+                escape(treatment),
+                escape(input),
+                metrics.received.load(Ordering::Relaxed)
+            );
+        }
+
+        header(
+            &mut text,
+            "melodium_continuous_tasks_active",
+            "gauge",
+            "Continuous tasks of models currently running.",
+        );
+        let _ = writeln!(
+            text,
+            "melodium_continuous_tasks_active {}",
+            self.continuous_tasks.load(Ordering::Relaxed)
+        );
+
+        text
+    }
+}
+
+fn header(text: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(text, "# HELP {name} {help}");
+    let _ = writeln!(text, "# TYPE {name} {kind}");
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -1,4 +1,5 @@
 use crate::debug::{DataContent, Event, EventKind, TransmissionDebug};
+use crate::metrics::InputMetrics;
 use async_std::channel::{bounded, Receiver, Sender};
 use async_std::sync::Mutex as AsyncMutex;
 use async_trait::async_trait;
@@ -6,6 +7,7 @@ use melodium_common::descriptor::Flow;
 use melodium_common::executive::{
     Input as ExecutiveInput, RecvResult, TrackId, TransmissionError, TransmissionValue, Value,
 };
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct Input {
@@ -15,10 +17,16 @@ pub struct Input {
     flow: Flow,
     track_id: TrackId,
     debug: TransmissionDebug,
+    metrics: Option<Arc<InputMetrics>>,
 }
 
 impl Input {
-    pub fn new(flow: Flow, track_id: TrackId, debug: TransmissionDebug) -> Self {
+    pub fn new(
+        flow: Flow,
+        track_id: TrackId,
+        debug: TransmissionDebug,
+        metrics: Option<Arc<InputMetrics>>,
+    ) -> Self {
         let (sender, receiver) = bounded(1);
         Self {
             receiver,
@@ -27,6 +35,7 @@ impl Input {
             flow,
             track_id,
             debug,
+            metrics,
         }
     }
 
@@ -74,6 +83,10 @@ impl ExecutiveInput for Input {
             }
         };
 
+        if let Some(metrics) = &self.metrics {
+            metrics.received(data.len());
+        }
+
         match &self.debug {
             TransmissionDebug::None => {}
             TransmissionDebug::Basic(world, transmission_details)
@@ -110,6 +123,10 @@ impl ExecutiveInput for Input {
             *lock = None;
         }
 
+        if let (Some(metrics), Ok(_)) = (&self.metrics, &value) {
+            metrics.received(1);
+        }
+
         match &self.debug {
             TransmissionDebug::None => {}
             TransmissionDebug::Basic(world, transmission_details)
@@ -137,6 +154,7 @@ impl Clone for Input {
             flow: self.flow.clone(),
             track_id: self.track_id,
             debug: self.debug.clone(),
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::debug::{DataContent, Event, EventKind, TransmissionDebug, TransmissionDetails};
+use crate::metrics::OutputMetrics;
 use crate::transmission::Input;
 use async_std::channel::{Sender, TrySendError};
 use async_std::sync::Mutex as AsyncMutex;
@@ -21,10 +22,16 @@ pub struct Output {
     flow: Flow,
     track_id: TrackId,
     debug: TransmissionDebug,
+    metrics: Option<Arc<OutputMetrics>>,
 }
 
 impl Output {
-    pub fn new(flow: Flow, track_id: TrackId, debug: TransmissionDebug) -> Self {
+    pub fn new(
+        flow: Flow,
+        track_id: TrackId,
+        debug: TransmissionDebug,
+        metrics: Option<Arc<OutputMetrics>>,
+    ) -> Self {
         Self {
             senders: Mutex::new(Arc::new(Vec::new())),
             count_receivers: AtomicUsize::new(0),
@@ -32,6 +39,7 @@ impl Output {
             flow,
             track_id,
             debug,
+            metrics,
         }
     }
 
@@ -67,6 +75,22 @@ impl Output {
         }
     }
 
+    /// Lets inspector look at data about to be transmitted to input, waiting for it.
+    async fn inspect(&self, data: &TransmissionValue, input_details: &Option<TransmissionDetails>) {
+        match (&self.debug, input_details) {
+            (_, None) | (TransmissionDebug::None, _) => {}
+            (
+                TransmissionDebug::Basic(world, output_details)
+                | TransmissionDebug::Detailed(world, output_details),
+                Some(input_details),
+            ) => {
+                world
+                    .inspect_transmission(output_details, input_details, self.track_id, data)
+                    .await
+            }
+        }
+    }
+
     async fn check_send(&self, force: bool) -> SendResult {
         let buffer_len = self
             .buffer
@@ -79,12 +103,16 @@ impl Output {
         if buffer_len > 0 {
             // We can unwrap the `take` because buffer_len must be > 0, so buffer have value.
             let data = self.buffer.lock().await.take().unwrap();
+            if let Some(metrics) = &self.metrics {
+                metrics.transmitted(buffer_len);
+            }
             if self.flow == Flow::Block || buffer_len >= LIMIT || force {
                 match self.count_receivers.load(Ordering::Relaxed) {
                     0 => Err(TransmissionError::NoReceiver),
                     1 => {
                         let senders = Arc::clone(&self.senders.lock().unwrap());
                         if let Some((sender, input_transmission_details)) = senders.first() {
+                            self.inspect(&data, input_transmission_details).await;
                             match sender.send(data).await {
                                 Ok(_) => {
                                     match (&self.debug, input_transmission_details) {
@@ -127,6 +155,7 @@ impl Output {
                             let transmission = {
                                 let data = &data;
                                 async move {
+                                    self.inspect(data, input_transmission_details).await;
                                     match sender.send(data.clone()).await {
                                         Ok(_) => {
                                             match (&self.debug, input_transmission_details) {
@@ -180,6 +209,9 @@ impl Output {
                     1 => {
                         let senders = Arc::clone(&self.senders.lock().unwrap());
                         if let Some((sender, input_transmission_details)) = senders.first() {
+                            if !sender.is_full() {
+                                self.inspect(&data, input_transmission_details).await;
+                            }
                             match sender.try_send(data) {
                                 Ok(_) => {
                                     match (&self.debug, input_transmission_details) {
@@ -209,6 +241,9 @@ impl Output {
                                     Ok(())
                                 }
                                 Err(TrySendError::Full(data)) => {
+                                    if let Some(metrics) = &self.metrics {
+                                        metrics.held_back(buffer_len);
+                                    }
                                     self.buffer.lock().await.replace(data);
                                     Ok(())
                                 }
@@ -232,7 +267,16 @@ impl Output {
                                 let transmission = {
                                     let data = &data;
                                     async move {
-                                        match sender.try_send(data.clone()) {
+                                        self.inspect(data, input_transmission_details).await;
+                                        let sent = match sender.try_send(data.clone()) {
+                                            Ok(_) => Ok(()),
+                                            // Inspection may have let input be filled by other outputs meanwhile.
+                                            Err(TrySendError::Full(data)) => {
+                                                sender.send(data).await.map_err(|_| ())
+                                            }
+                                            Err(TrySendError::Closed(_)) => Err(()),
+                                        };
+                                        match sent {
                                             Ok(_) => {
                                                 match (&self.debug, input_transmission_details) {
                                                     (_, None) | (TransmissionDebug::None, _) => {}
@@ -266,8 +310,7 @@ impl Output {
                                                 }
                                                 true
                                             }
-                                            Err(TrySendError::Full(_)) => unreachable!(),
-                                            Err(TrySendError::Closed(_)) => false,
+                                            Err(_) => false,
                                         }
                                     }
                                 };
@@ -282,6 +325,9 @@ impl Output {
                                 Err(TransmissionError::EverythingClosed)
                             }
                         } else {
+                            if let Some(metrics) = &self.metrics {
+                                metrics.held_back(buffer_len);
+                            }
                             self.buffer.lock().await.replace(data);
                             Ok(())
                         }
@@ -316,6 +362,10 @@ impl ExecutiveOutput for Output {
     }
 
     async fn send_many(&self, data: TransmissionValue) -> SendResult {
+        if let Some(metrics) = &self.metrics {
+            metrics.sent(data.len());
+        }
+
         match &self.debug {
             TransmissionDebug::None => {}
             TransmissionDebug::Basic(world, transmission_details) => {
@@ -353,6 +403,10 @@ impl ExecutiveOutput for Output {
     }
 
     async fn send_one(&self, data: Value) -> SendResult {
+        if let Some(metrics) = &self.metrics {
+            metrics.sent(1);
+        }
+
         match &self.debug {
             TransmissionDebug::None => {}
             TransmissionDebug::Basic(world, transmission_details) => {
@@ -395,7 +449,12 @@ impl ExecutiveOutput for Output {
 
 impl From<Input> for Output {
     fn from(value: Input) -> Self {
-        let o = Output::new(*value.flow(), *value.track_id(), TransmissionDebug::None);
+        let o = Output::new(
+            *value.flow(),
+            *value.track_id(),
+            TransmissionDebug::None,
+            None,
+        );
         o.add_transmission(&vec![value]);
         o
     }
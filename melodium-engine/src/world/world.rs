@@ -6,10 +6,11 @@ use crate::building::{
     GenesisEnvironment, StaticBuildResult,
 };
 use crate::debug::{
-    DebugLevel, Event, EventKind, TrackCreation, TransmissionDebug, TransmissionDetails,
+    DebugLevel, Event, EventKind, Inspector, TrackCreation, TransmissionDebug, TransmissionDetails,
 };
 use crate::engine::Engine;
 use crate::error::{LogicError, LogicErrors, LogicResult};
+use crate::metrics::Metrics;
 use crate::transmission::{Input, Output, Outputs};
 use async_std::channel::{unbounded, Receiver, Sender};
 use async_std::sync::{Barrier, Mutex, RwLock as AsyncRwLock};
@@ -22,12 +23,13 @@ use futures::join;
 use futures::stream::{FuturesUnordered, StreamExt};
 use futures::{pin_mut, select, FutureExt};
 use melodium_common::descriptor::{
-    Collection, Entry as CollectionEntry, Flow, Identifier, Treatment,
+    Collection, Entry as CollectionEntry, Flow, Identifier, Model as ModelDescriptor, Parameter,
+    Treatment,
 };
 use melodium_common::executive::{
     Context as ExecutiveContext, ContinuousFuture, DirectCreationCallback, Input as ExecutiveInput,
-    Level as LogLevel, Log, Model, ModelId, Output as ExecutiveOutput, ResultStatus,
-    TrackCreationCallback, TrackFuture, TrackId, Value, World as ExecutiveWorld,
+    Level as LogLevel, Log, LogFilter, Model, ModelId, Output as ExecutiveOutput, ResultStatus,
+    TrackCreationCallback, TrackFuture, TrackId, TransmissionValue, Value, World as ExecutiveWorld,
 };
 use std::collections::{hash_map::Entry, HashMap};
 use std::sync::{
@@ -44,8 +46,11 @@ pub struct World {
     sources: RwLock<HashMap<ModelId, HashMap<String, Vec<SourceEntry>>>>,
 
     builders: RwLock<HashMap<Identifier, Arc<dyn Builder>>>,
+    overrides: RwLock<HashMap<Identifier, Identifier>>,
 
     errors: RwLock<LogicErrors>,
+    failures: RwLock<Vec<ResultStatus>>,
+    exit_code: RwLock<Option<i32>>,
     main: RwLock<Option<Arc<dyn Treatment>>>,
     main_id: RwLock<Option<Identifier>>,
     main_build_id: RwLock<BuildId>,
@@ -61,7 +66,7 @@ pub struct World {
     tracks_receiver: Receiver<ExecutionTrack>,
     tracks_running: AtomicUsize,
 
-    logs_level: LogLevel,
+    logs_filter: LogFilter,
     logs_sender: Sender<Log>,
     logs_receiver: Receiver<Log>,
     logs_listeners: AsyncRwLock<Vec<Sender<Log>>>,
@@ -70,6 +75,9 @@ pub struct World {
     debug_sender: Sender<Event>,
     debug_receiver: Receiver<Event>,
     debug_listeners: AsyncRwLock<Vec<Sender<Event>>>,
+    inspector: RwLock<Option<Arc<dyn Inspector>>>,
+
+    metrics: RwLock<Option<Arc<Metrics>>>,
 
     close_at_continuous_end: AtomicBool,
     continous_ended: AtomicBool,
@@ -103,7 +111,7 @@ impl Debug for World {
 impl World {
     pub fn new(
         collection: Arc<Collection>,
-        logs_level: LogLevel,
+        logs_filter: LogFilter,
         debug_level: DebugLevel,
     ) -> Arc<Self> {
         let (tracks_sender, tracks_receiver) = unbounded();
@@ -118,7 +126,10 @@ impl World {
             models: RwLock::new(Vec::new()),
             sources: RwLock::new(HashMap::new()),
             builders: RwLock::new(HashMap::new()),
+            overrides: RwLock::new(HashMap::new()),
             errors: RwLock::new(Vec::new()),
+            failures: RwLock::new(Vec::new()),
+            exit_code: RwLock::new(None),
             main: RwLock::new(None),
             main_id: RwLock::new(None),
             main_build_id: RwLock::new(0),
@@ -131,7 +142,7 @@ impl World {
             tracks_sender,
             tracks_receiver,
             tracks_running: AtomicUsize::new(0),
-            logs_level,
+            logs_filter,
             logs_sender,
             logs_receiver,
             logs_listeners: AsyncRwLock::new(Vec::new()),
@@ -139,6 +150,8 @@ impl World {
             debug_sender,
             debug_receiver,
             debug_listeners: AsyncRwLock::new(Vec::new()),
+            inspector: RwLock::new(None),
+            metrics: RwLock::new(None),
             close_at_continuous_end: AtomicBool::new(true),
             continous_ended: AtomicBool::new(false),
             continous_ended_barrier: Barrier::new(2),
@@ -228,7 +241,23 @@ impl World {
         )
     }
 
+    /// Gives the builder of the element, or of the element overriding it if any.
     pub fn builder(&self, identifier: &Identifier) -> LogicResult<Arc<dyn Builder>> {
+        let identifier = self
+            .overrides
+            .read()
+            .unwrap()
+            .get(identifier)
+            .cloned()
+            .unwrap_or_else(|| identifier.clone());
+
+        self.own_builder(&identifier)
+    }
+
+    /// Gives the builder of the element itself, whatever overrides are.
+    ///
+    /// This is used to build base of designed models, as a model overriding another one can be based on it.
+    pub fn own_builder(&self, identifier: &Identifier) -> LogicResult<Arc<dyn Builder>> {
         let possible_builder;
         {
             possible_builder = self.builders.read().unwrap().get(identifier).cloned();
@@ -267,24 +296,223 @@ impl World {
         }
     }
 
+    /// Checks overrides, giving them keyed by actual identifiers of overridden elements.
+    ///
+    /// Overriding element must be of the same kind as the overridden one, and be usable in place of it:
+    /// - a treatment must have the same inputs, outputs, models, and generics;
+    /// - a model must provide at least the same sources, and be based on the same compiled model,
+    ///   as compiled treatments can only work with the models they are made for;
+    /// - in both cases, parameters not existing for the overridden element must have a default value.
+    fn check_overrides(
+        &self,
+        overrides: HashMap<Identifier, Identifier>,
+    ) -> LogicResult<HashMap<Identifier, Identifier>> {
+        let mut result = LogicResult::new_success(());
+        let mut checked = HashMap::new();
+
+        for (overridden, overriding) in overrides {
+            let (overridden, overriding) = match (
+                self.collection.get(&(&overridden).into()),
+                self.collection.get(&(&overriding).into()),
+            ) {
+                (
+                    Some(CollectionEntry::Treatment(overridden)),
+                    Some(CollectionEntry::Treatment(overriding)),
+                ) => {
+                    if let Some(reason) = Self::treatment_override_incompatibility(
+                        overridden.as_ref(),
+                        overriding.as_ref(),
+                    ) {
+                        result = result.and_degrade_failure(LogicResult::new_failure(
+                            LogicError::incompatible_override(
+                                251,
+                                overridden.identifier().clone(),
+                                overriding.identifier().clone(),
+                                reason,
+                            ),
+                        ));
+                    }
+                    (
+                        overridden.identifier().clone(),
+                        overriding.identifier().clone(),
+                    )
+                }
+                (
+                    Some(CollectionEntry::Model(overridden)),
+                    Some(CollectionEntry::Model(overriding)),
+                ) => {
+                    if let Some(reason) = Self::model_override_incompatibility(
+                        overridden.as_ref(),
+                        overriding.as_ref(),
+                    ) {
+                        result = result.and_degrade_failure(LogicResult::new_failure(
+                            LogicError::incompatible_override(
+                                252,
+                                overridden.identifier().clone(),
+                                overriding.identifier().clone(),
+                                reason,
+                            ),
+                        ));
+                    }
+                    (
+                        overridden.identifier().clone(),
+                        overriding.identifier().clone(),
+                    )
+                }
+                (
+                    Some(CollectionEntry::Treatment(_) | CollectionEntry::Model(_)),
+                    Some(CollectionEntry::Treatment(_) | CollectionEntry::Model(_)),
+                ) => {
+                    result = result.and_degrade_failure(LogicResult::new_failure(
+                        LogicError::incompatible_override(
+                            253,
+                            overridden,
+                            overriding,
+                            "models and treatments cannot override each other".to_string(),
+                        ),
+                    ));
+                    continue;
+                }
+                (Some(CollectionEntry::Treatment(_) | CollectionEntry::Model(_)), _) => {
+                    result = result.and_degrade_failure(LogicResult::new_failure(
+                        LogicError::unexisting_override(254, overriding),
+                    ));
+                    continue;
+                }
+                _ => {
+                    result = result.and_degrade_failure(LogicResult::new_failure(
+                        LogicError::unexisting_override(250, overridden),
+                    ));
+                    continue;
+                }
+            };
+
+            checked.insert(overridden, overriding);
+        }
+
+        result.and(LogicResult::new_success(checked))
+    }
+
+    fn treatment_override_incompatibility(
+        overridden: &dyn Treatment,
+        overriding: &dyn Treatment,
+    ) -> Option<String> {
+        for (name, input) in overridden.inputs() {
+            match overriding.inputs().get(name) {
+                Some(other)
+                    if other.flow() == input.flow()
+                        && other.described_type() == input.described_type() => {}
+                _ => return Some(format!("input '{name}' does not match")),
+            }
+        }
+        if let Some(name) = overriding
+            .inputs()
+            .keys()
+            .find(|name| !overridden.inputs().contains_key(*name))
+        {
+            return Some(format!("input '{name}' is not expected"));
+        }
+
+        for (name, output) in overridden.outputs() {
+            match overriding.outputs().get(name) {
+                Some(other)
+                    if other.flow() == output.flow()
+                        && other.described_type() == output.described_type() => {}
+                _ => return Some(format!("output '{name}' does not match")),
+            }
+        }
+        if let Some(name) = overriding
+            .outputs()
+            .keys()
+            .find(|name| !overridden.outputs().contains_key(*name))
+        {
+            return Some(format!("output '{name}' is not expected"));
+        }
+
+        if let Some(name) = overridden
+            .models()
+            .keys()
+            .chain(overriding.models().keys())
+            .find(|name| {
+                !overridden.models().contains_key(*name) || !overriding.models().contains_key(*name)
+            })
+        {
+            return Some(format!("model '{name}' does not match"));
+        }
+
+        if overridden.generics() != overriding.generics() {
+            return Some("generics do not match".to_string());
+        }
+
+        Self::parameters_override_incompatibility(overridden.parameters(), overriding.parameters())
+    }
+
+    fn model_override_incompatibility(
+        overridden: &dyn ModelDescriptor,
+        overriding: &dyn ModelDescriptor,
+    ) -> Option<String> {
+        let overridden_base = Self::compiled_base_model(overridden);
+        let overriding_base = Self::compiled_base_model(overriding);
+        if overridden_base != overriding_base {
+            return Some(format!(
+                "it is based on '{overriding_base}' while '{overridden_base}' is required"
+            ));
+        }
+
+        if let Some(name) = overridden
+            .sources()
+            .keys()
+            .find(|name| !overriding.sources().contains_key(*name))
+        {
+            return Some(format!("source '{name}' is missing"));
+        }
+
+        Self::parameters_override_incompatibility(overridden.parameters(), overriding.parameters())
+    }
+
+    /// Gives identifier of the compiled model the model is ultimately based on.
+    fn compiled_base_model(model: &dyn ModelDescriptor) -> Identifier {
+        match model.base_model() {
+            Some(base) => Self::compiled_base_model(base.as_ref()),
+            None => model.identifier().clone(),
+        }
+    }
+
+    fn parameters_override_incompatibility(
+        overridden: &HashMap<String, Parameter>,
+        overriding: &HashMap<String, Parameter>,
+    ) -> Option<String> {
+        overriding
+            .iter()
+            .find(|(name, parameter)| {
+                !overridden.contains_key(*name) && parameter.default().is_none()
+            })
+            .map(|(name, _)| format!("parameter '{name}' is not expected and has no default value"))
+    }
+
     pub fn new_input(&self, flow: Flow, track_id: TrackId, details: TransmissionDetails) -> Input {
+        let metrics = self
+            .metrics()
+            .map(|metrics| metrics.input(details.treatment.identifier(), &details.name));
         match self.debug_level {
-            DebugLevel::None => Input::new(flow, track_id, TransmissionDebug::None),
+            DebugLevel::None => Input::new(flow, track_id, TransmissionDebug::None, metrics),
             DebugLevel::Basic => Input::new(
                 flow,
                 track_id,
                 TransmissionDebug::Basic(self.auto_reference.upgrade().unwrap(), details),
+                metrics,
             ),
             DebugLevel::Detailed => Input::new(
                 flow,
                 track_id,
                 TransmissionDebug::Detailed(self.auto_reference.upgrade().unwrap(), details),
+                metrics,
             ),
         }
     }
 
     pub fn new_blocked_input(&self, flow: Flow, track_id: TrackId) -> Input {
-        let input = Input::new(flow, track_id, TransmissionDebug::None);
+        let input = Input::new(flow, track_id, TransmissionDebug::None, None);
         input.close();
         input
     }
@@ -295,21 +523,30 @@ impl World {
         track_id: TrackId,
         details: TransmissionDetails,
     ) -> Output {
+        let metrics = self
+            .metrics()
+            .map(|metrics| metrics.output(details.treatment.identifier(), &details.name));
         match self.debug_level {
-            DebugLevel::None => Output::new(flow, track_id, TransmissionDebug::None),
+            DebugLevel::None => Output::new(flow, track_id, TransmissionDebug::None, metrics),
             DebugLevel::Basic => Output::new(
                 flow,
                 track_id,
                 TransmissionDebug::Basic(self.auto_reference.upgrade().unwrap(), details),
+                metrics,
             ),
             DebugLevel::Detailed => Output::new(
                 flow,
                 track_id,
                 TransmissionDebug::Detailed(self.auto_reference.upgrade().unwrap(), details),
+                metrics,
             ),
         }
     }
 
+    fn metrics(&self) -> Option<Arc<Metrics>> {
+        self.metrics.read().unwrap().clone()
+    }
+
     pub fn send_debug(&self, event: Event) {
         let _ = self.debug_sender.send_blocking(event);
     }
@@ -318,6 +555,20 @@ impl World {
         let _ = self.debug_sender.send(event).await;
     }
 
+    /// Lets inspector, if any, look at data about to be transmitted, waiting for it.
+    pub async fn inspect_transmission(
+        &self,
+        output: &TransmissionDetails,
+        input: &TransmissionDetails,
+        track_id: TrackId,
+        data: &TransmissionValue,
+    ) {
+        let inspector = self.inspector.read().unwrap().clone();
+        if let Some(inspector) = inspector {
+            inspector.transmission(output, input, track_id, data).await;
+        }
+    }
+
     async fn run_tracks(&self) {
         let mut futures = FuturesUnordered::new();
 
@@ -328,11 +579,9 @@ impl World {
         async fn track_future(mut track: ExecutionTrack) -> TrackResult {
             let mut non_ok: Vec<ResultStatus> = Vec::new();
             while let Some(r) = track.future.next().await {
-                // The `_ =>` is unreachable for now, but will when ResultStatus will be complexified.
-                #[allow(unreachable_patterns)]
                 match r {
                     ResultStatus::Ok => {}
-                    _ => non_ok.push(r.clone()),
+                    _ => non_ok.push(r),
                 }
             }
 
@@ -353,6 +602,12 @@ impl World {
                 },
                 result = futures.select_next_some() => {
                     let _ = self.tracks_running.fetch_sub(1, Ordering::Relaxed);
+                    if let Some(metrics) = self.metrics() {
+                        match &result {
+                            TrackResult::AllOk(id) => metrics.track_finished(*id, true),
+                            TrackResult::NotAllOk(id, _) => metrics.track_finished(*id, false),
+                        }
+                    }
                     let track_info = match result {
                         TrackResult::AllOk(id) => {
                             if let Some(info) = self.tracks_info.lock().await.get_mut(&id) {
@@ -361,8 +616,11 @@ impl World {
                                 Some(info.clone())
                             } else {None}
                         }
-                        TrackResult::NotAllOk(id, _) => {
-                             if let Some(info) = self.tracks_info.lock().await.get_mut(&id) {
+                        TrackResult::NotAllOk(id, ref non_ok) => {
+                            for status in non_ok {
+                                self.report_failure(Some(id), status.clone()).await;
+                            }
+                            if let Some(info) = self.tracks_info.lock().await.get_mut(&id) {
                                 info.results = Some(result);
 
                                 Some(info.clone())
@@ -382,6 +640,39 @@ impl World {
         }
     }
 
+    /// Records a non-ok status, logs it, and ends the world if it is fatal.
+    async fn report_failure(&self, track_id: Option<TrackId>, status: ResultStatus) {
+        let message = match &status {
+            ResultStatus::Ok => return,
+            ResultStatus::Error(message) | ResultStatus::Fatal(message) => message.clone(),
+        };
+        let message = match track_id {
+            Some(track_id) => format!("track {track_id} failed: {message}"),
+            None => format!("continuous task failed: {message}"),
+        };
+
+        self.failures.write().unwrap().push(status.clone());
+        ExecutiveWorld::log(
+            self,
+            LogLevel::Error,
+            "engine".to_string(),
+            message,
+            track_id,
+        )
+        .await;
+        let _ = self
+            .debug_sender
+            .send(Event::new(EventKind::Failure {
+                track_id,
+                status: status.clone(),
+            }))
+            .await;
+
+        if status.is_fatal() {
+            self.end().await;
+        }
+    }
+
     async fn check_closing(&self) {
         let tracks_recv = self.tracks_receiver.len();
         let tracks_run = self.tracks_running.load(Ordering::Relaxed);
@@ -404,7 +695,12 @@ impl Engine for World {
         Arc::clone(&self.collection)
     }
 
-    fn genesis(&self, entry: &Identifier, mut params: HashMap<String, Value>) -> LogicResult<()> {
+    fn genesis(
+        &self,
+        entry: &Identifier,
+        mut params: HashMap<String, Value>,
+        overrides: HashMap<Identifier, Identifier>,
+    ) -> LogicResult<()> {
         let mut gen_env = GenesisEnvironment::new();
 
         {
@@ -413,6 +709,17 @@ impl Engine for World {
             }
         }
 
+        let overrides = self.check_overrides(overrides);
+        match overrides.success() {
+            Some(overrides) => *self.overrides.write().unwrap() = overrides.clone(),
+            None => {
+                let mut errors = self.errors.write().unwrap();
+                errors.extend(overrides.errors().iter().cloned());
+                errors.extend(overrides.failure().cloned());
+                return overrides.and(LogicResult::new_success(()));
+            }
+        }
+
         let descriptor = if let Some(CollectionEntry::Treatment(descriptor)) =
             self.collection.get(&entry.into())
         {
@@ -537,6 +844,14 @@ impl Engine for World {
         self.errors.read().unwrap().clone()
     }
 
+    fn failures(&self) -> Vec<ResultStatus> {
+        self.failures.read().unwrap().clone()
+    }
+
+    fn exit_code(&self) -> Option<i32> {
+        *self.exit_code.read().unwrap()
+    }
+
     fn set_auto_end(&self, auto_end: bool) {
         self.close_at_continuous_end
             .store(auto_end, Ordering::Relaxed);
@@ -546,8 +861,8 @@ impl Engine for World {
         self.close_at_continuous_end.load(Ordering::Relaxed)
     }
 
-    fn log_level(&self) -> LogLevel {
-        self.logs_level
+    fn log_filter(&self) -> LogFilter {
+        self.logs_filter.clone()
     }
 
     #[cfg(not(target_os = "unknown"))]
@@ -570,6 +885,19 @@ impl Engine for World {
     #[cfg(target_os = "unknown")]
     fn add_debug_listener(&self, _sender: Sender<Event>) {}
 
+    fn set_inspector(&self, inspector: Arc<dyn Inspector>) {
+        *self.inspector.write().unwrap() = Some(inspector);
+    }
+
+    fn enable_metrics(&self) -> Arc<Metrics> {
+        Arc::clone(
+            self.metrics
+                .write()
+                .unwrap()
+                .get_or_insert_with(|| Arc::new(Metrics::new())),
+        )
+    }
+
     async fn live(&self) {
         let me = self.auto_reference.upgrade().unwrap();
         let continuum = {
@@ -577,15 +905,24 @@ impl Engine for World {
             async move {
                 let mut continuous = FuturesUnordered::new();
 
+                let metrics = me.metrics();
                 while let Ok(c) = me.continuous_tasks_receiver.recv().await {
                     continuous.push(c);
                 }
+                if let Some(metrics) = &metrics {
+                    metrics.set_continuous_tasks(continuous.len());
+                }
 
                 let _ = me
                     .debug_sender
                     .send(Event::new(EventKind::ContinuousModelsStarted))
                     .await;
-                while let Some(_) = continuous.next().await {}
+                while let Some(status) = continuous.next().await {
+                    if let Some(metrics) = &metrics {
+                        metrics.set_continuous_tasks(continuous.len());
+                    }
+                    me.report_failure(None, status).await;
+                }
 
                 me.continous_ended.store(true, Ordering::Relaxed);
                 let _ = me
@@ -686,7 +1023,7 @@ impl Engine for World {
 
         let mut inputs = HashMap::new();
         for (name, descriptor) in main.outputs() {
-            let input = Input::new(*descriptor.flow(), track_id, TransmissionDebug::None); //self.new_input(*descriptor.flow());
+            let input = Input::new(*descriptor.flow(), track_id, TransmissionDebug::None, None); //self.new_input(*descriptor.flow());
             inputs.insert(name.clone(), input);
         }
         {
@@ -746,6 +1083,9 @@ impl Engine for World {
 
         let info_track = InfoTrack::new(track_id, None, 0);
         let execution_track = ExecutionTrack::new(track_id, 0, track_futures.into_iter().collect());
+        if let Some(metrics) = self.metrics() {
+            metrics.track_created(track_id, None, "direct");
+        }
         self.tracks_info
             .lock()
             .await
@@ -878,6 +1218,10 @@ impl ExecutiveWorld for World {
         let info_track = InfoTrack::new(track_id, parent_track, ancestry);
         let execution_track =
             ExecutionTrack::new(track_id, ancestry, track_futures.into_iter().collect());
+        if let Some(metrics) = self.metrics() {
+            let model = self.models.read().unwrap()[id].descriptor();
+            metrics.track_created(track_id, Some(model.identifier()), source);
+        }
         self.tracks_info
             .lock()
             .await
@@ -904,6 +1248,10 @@ impl ExecutiveWorld for World {
         message: String,
         track_id: Option<TrackId>,
     ) {
+        if !self.logs_filter.allows(level, &label) {
+            return;
+        }
+
         let log = Log {
             level,
             label,
@@ -916,7 +1264,15 @@ impl ExecutiveWorld for World {
         let _ = self.logs_sender.send(log).await;
     }
 
+    fn log_enabled(&self, level: LogLevel, label: &str) -> bool {
+        self.logs_filter.allows(level, label)
+    }
+
     async fn inject_log(&self, log: Log) -> Result<(), ()> {
+        if !self.logs_filter.allows(log.level, &log.label) {
+            return Ok(());
+        }
+
         self.logs_sender.send(log).await.map_err(|_| ())
     }
 
@@ -931,4 +1287,9 @@ impl ExecutiveWorld for World {
         let mut receiver = self.no_more_tracks_receiver.clone();
         let _ = receiver.next().await;
     }
+
+    async fn exit(&self, code: i32) {
+        self.exit_code.write().unwrap().get_or_insert(code);
+        self.end().await;
+    }
 }
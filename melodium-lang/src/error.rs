@@ -92,6 +92,9 @@ pub enum ScriptErrorKind {
     TypeForbidden {
         text: PositionnedString,
     },
+    VariabilityForbidden {
+        text: PositionnedString,
+    },
     ConnectionMustTransmit {
         from: PositionnedString,
         to: PositionnedString,
@@ -157,6 +160,22 @@ pub enum ScriptErrorKind {
         text: PositionnedString,
         root: String,
     },
+    NotDerivableTrait {
+        text: PositionnedString,
+    },
+    FieldMissingTrait {
+        text: PositionnedString,
+        r#trait: String,
+    },
+    /// Field type is not known, and so taken as generic, but generics cannot be used in data.
+    GenericFieldForbidden {
+        text: PositionnedString,
+    },
+    /// A function associated with data gets the same name as another function.
+    FunctionNameCollision {
+        text: PositionnedString,
+        name: String,
+    },
     /// The error comes from logic.
     Logic {
         error: LogicError,
@@ -167,6 +186,64 @@ pub enum ScriptErrorKind {
     },
 }
 
+impl ScriptErrorKind {
+    /// Gives the text the error is located at, if any.
+    pub fn positionned_string(&self) -> Option<PositionnedString> {
+        match self {
+            ScriptErrorKind::Word { word, .. } => Some(word.into()),
+            ScriptErrorKind::EndOfScript => None,
+            ScriptErrorKind::DescriptionElementExpected { word, .. } => Some(word.into()),
+            ScriptErrorKind::DeclarationExpected { word, .. } => Some(word.into()),
+            ScriptErrorKind::InvalidRoot { text, .. } => Some(text.clone()),
+            ScriptErrorKind::AlreadyUsedName { text } => Some(text.clone()),
+            ScriptErrorKind::InvalidType { text } => Some(text.clone()),
+            ScriptErrorKind::InvalidStructure { text } => Some(text.clone()),
+            ScriptErrorKind::UnimportedElement { text } => Some(text.clone()),
+            ScriptErrorKind::AlreadyDeclared { text } => Some(text.clone()),
+            ScriptErrorKind::AlreadyAssigned { text } => Some(text.clone()),
+            ScriptErrorKind::MissingType { text } => Some(text.clone()),
+            ScriptErrorKind::MissingValue { text } => Some(text.clone()),
+            ScriptErrorKind::DefaultForbidden { text } => Some(text.clone()),
+            ScriptErrorKind::DefaultForbiddenForGenerics { text, .. } => Some(text.clone()),
+            ScriptErrorKind::ConstDeclarationOnly { text } => Some(text.clone()),
+            ScriptErrorKind::FlowForbidden { text } => Some(text.clone()),
+            ScriptErrorKind::StructureForbidden { text } => Some(text.clone()),
+            ScriptErrorKind::TypeForbidden { text } => Some(text.clone()),
+            ScriptErrorKind::VariabilityForbidden { text } => Some(text.clone()),
+            ScriptErrorKind::ConnectionMustTransmit { from, .. } => Some(from.clone()),
+            ScriptErrorKind::TreatmentNotFound { text } => Some(text.clone()),
+            ScriptErrorKind::NameRequired { text } => Some(text.clone()),
+            ScriptErrorKind::UndeclaredModel { text } => Some(text.clone()),
+            ScriptErrorKind::UndeclaredParameter { text } => Some(text.clone()),
+            ScriptErrorKind::UndeclaredContext { text } => Some(text.clone()),
+            ScriptErrorKind::UndeclaredData { text } => Some(text.clone()),
+            ScriptErrorKind::ReferenceUnset { .. } => None,
+            ScriptErrorKind::InvalidBoolean { text } => Some(text.clone()),
+            ScriptErrorKind::InvalidNumber { text } => Some(text.clone()),
+            ScriptErrorKind::InvalidString { text } => Some(text.clone()),
+            ScriptErrorKind::InvalidCharacter { text } => Some(text.clone()),
+            ScriptErrorKind::InvalidByte { text } => Some(text.clone()),
+            ScriptErrorKind::ExecutiveRestitutionFailed { text, .. } => Some(text.clone()),
+            ScriptErrorKind::MissingFunctionParameter { text, .. } => Some(text.clone()),
+            ScriptErrorKind::MissingFunctionGeneric { text, .. } => Some(text.clone()),
+            ScriptErrorKind::MissingTreatmentGeneric { text } => Some(text.clone()),
+            ScriptErrorKind::InvalidGeneric { text } => Some(text.clone()),
+            ScriptErrorKind::InvalidTrait { text } => Some(text.clone()),
+            ScriptErrorKind::UnexistingDependency { text, .. } => Some(text.clone()),
+            ScriptErrorKind::NotDerivableTrait { text } => Some(text.clone()),
+            ScriptErrorKind::FieldMissingTrait { text, .. } => Some(text.clone()),
+            ScriptErrorKind::GenericFieldForbidden { text } => Some(text.clone()),
+            ScriptErrorKind::FunctionNameCollision { text, .. } => Some(text.clone()),
+            ScriptErrorKind::Logic { error } => error
+                .design_reference
+                .as_ref()
+                .and_then(|ptr| Arc::clone(ptr).downcast_arc::<PositionnedString>().ok())
+                .map(|ps| (*ps).clone()),
+            ScriptErrorKind::NoDescriptor { name } => Some(name.clone()),
+        }
+    }
+}
+
 impl Display for ScriptErrorKind {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
@@ -223,6 +300,7 @@ impl Display for ScriptErrorKind {
             ScriptErrorKind::FlowForbidden { text } => write!(f, "at line {} position {} '{}' cannot have flow specification", text.position.line_number, text.position.line_position, text.string),
             ScriptErrorKind::StructureForbidden { text } => write!(f, "at line {} position {} '{}' cannot have structure specification", text.position.line_number, text.position.line_position, text.string),
             ScriptErrorKind::TypeForbidden { text } => write!(f, "at line {} position {} '{}' cannot have type specification", text.position.line_number, text.position.line_position, text.string),
+            ScriptErrorKind::VariabilityForbidden { text } => write!(f, "at line {} position {} '{}' cannot have variability specification", text.position.line_number, text.position.line_position, text.string),
             ScriptErrorKind::ConnectionMustTransmit { from, to } => write!(f, "at line {} position {}, connection from '{}' to '{}' must transmit data", from.position.line_number, from.position.line_position, from.string, to.string),
             ScriptErrorKind::TreatmentNotFound { text } => write!(f, "at line {} position {} cannot find treatment '{}'", text.position.line_number, text.position.line_position, text.string),
             ScriptErrorKind::NameRequired { text } => write!(f, "at line {} position {} a name is required for assignation to '{}'", text.position.line_number, text.position.line_position, text.string),
@@ -243,6 +321,10 @@ impl Display for ScriptErrorKind {
             ScriptErrorKind::InvalidGeneric { text } => write!(f, "at line {} position {} '{}' is not a valid generic name", text.position.line_number, text.position.line_position, text.string),
             ScriptErrorKind::InvalidTrait { text } => write!(f, "at line {} position {} '{}' is not a valid trait", text.position.line_number, text.position.line_position, text.string),
             ScriptErrorKind::UnexistingDependency { text, root } => write!(f, "at line {} position {} '{root}' is not a dependency", text.position.line_number, text.position.line_position),
+            ScriptErrorKind::NotDerivableTrait { text } => write!(f, "at line {} position {} trait '{}' cannot be derived", text.position.line_number, text.position.line_position, text.string),
+            ScriptErrorKind::FieldMissingTrait { text, r#trait } => write!(f, "at line {} position {} field '{}' does not implement '{trait}'", text.position.line_number, text.position.line_position, text.string),
+            ScriptErrorKind::GenericFieldForbidden { text } => write!(f, "at line {} position {} field type '{}' is not a known data type, and generics cannot be used in data", text.position.line_number, text.position.line_position, text.string),
+            ScriptErrorKind::FunctionNameCollision { text, name } => write!(f, "at line {} position {} '{}' gets function '{name}', but this name is already given to another function", text.position.line_number, text.position.line_position, text.string),
             ScriptErrorKind::Logic { error } => {
                 if let Some(ps) = error
                     .design_reference
@@ -420,6 +502,13 @@ impl ScriptError {
         }
     }
 
+    pub fn variability_forbidden(id: u32, text: PositionnedString) -> Self {
+        Self {
+            id,
+            kind: ScriptErrorKind::VariabilityForbidden { text },
+        }
+    }
+
     pub fn connection_must_transmit_data(
         id: u32,
         from: PositionnedString,
@@ -564,6 +653,34 @@ impl ScriptError {
         }
     }
 
+    pub fn not_derivable_trait(id: u32, text: PositionnedString) -> Self {
+        Self {
+            id,
+            kind: ScriptErrorKind::NotDerivableTrait { text },
+        }
+    }
+
+    pub fn field_missing_trait(id: u32, text: PositionnedString, r#trait: String) -> Self {
+        Self {
+            id,
+            kind: ScriptErrorKind::FieldMissingTrait { text, r#trait },
+        }
+    }
+
+    pub fn generic_field_forbidden(id: u32, text: PositionnedString) -> Self {
+        Self {
+            id,
+            kind: ScriptErrorKind::GenericFieldForbidden { text },
+        }
+    }
+
+    pub fn function_name_collision(id: u32, text: PositionnedString, name: String) -> Self {
+        Self {
+            id,
+            kind: ScriptErrorKind::FunctionNameCollision { text, name },
+        }
+    }
+
     pub fn logic(id: u32, error: LogicError) -> Self {
         Self {
             id,
@@ -577,6 +694,11 @@ impl ScriptError {
             kind: ScriptErrorKind::NoDescriptor { name },
         }
     }
+
+    /// Gives the text the error is located at, if any.
+    pub fn positionned_string(&self) -> Option<PositionnedString> {
+        self.kind.positionned_string()
+    }
 }
 
 impl Display for ScriptError {
@@ -1,11 +1,15 @@
-use super::{model::Model, treatment::Treatment};
+use super::{
+    constant::Constant, data::Data, function::Function, model::Model, treatment::Treatment,
+};
 use crate::Path;
 use convert_case::{Case, Casing};
 use itertools::Itertools;
 use melodium_common::descriptor::{
     Collection, Entry, Identifier, ModelBuildMode, TreatmentBuildMode,
 };
-use melodium_engine::descriptor::{Model as ModelDescriptor, Treatment as TreatmentDescriptor};
+use melodium_engine::descriptor::{
+    Function as FunctionDescriptor, Model as ModelDescriptor, Treatment as TreatmentDescriptor,
+};
 use std::{collections::BTreeMap, sync::Arc};
 
 pub struct Area {
@@ -13,6 +17,9 @@ pub struct Area {
     collection: Arc<Collection>,
     owned_ids: Vec<Identifier>,
     uses_names: BTreeMap<Identifier, String>,
+    constants: Vec<Constant>,
+    data: Vec<Data>,
+    functions: Vec<Function>,
     models: Vec<Model>,
     treatments: Vec<Treatment>,
 }
@@ -26,11 +33,23 @@ impl Area {
             .collect::<Vec<_>>();
         owned_ids.sort();
 
+        let mut constants = Vec::new();
+        let mut data = Vec::new();
+        let mut functions = Vec::new();
         let mut models = Vec::new();
         let mut treatments = Vec::new();
 
         for id in &owned_ids {
             match collection.get(&id.into()).cloned().unwrap() {
+                Entry::Constant(descriptor) => constants.push(Constant::new(descriptor)),
+                Entry::Data(descriptor) => data.push(Data::new(descriptor)),
+                Entry::Function(function) => {
+                    if let Ok(designed) = function.downcast_arc::<FunctionDescriptor>() {
+                        if let Some(design) = designed.design() {
+                            functions.push(Function::new(designed, design));
+                        }
+                    }
+                }
                 Entry::Model(model) => {
                     let design = match model.clone().downcast_arc::<ModelDescriptor>() {
                         Ok(designed) => {
@@ -95,6 +114,13 @@ impl Area {
 
         let mut needs = Vec::new();
 
+        constants
+            .iter()
+            .for_each(|c| needs.extend(c.uses().clone()));
+        data.iter().for_each(|d| needs.extend(d.uses().clone()));
+        functions
+            .iter()
+            .for_each(|f| needs.extend(f.uses().clone()));
         models.iter().for_each(|m| needs.extend(m.uses().clone()));
         treatments
             .iter()
@@ -142,6 +168,9 @@ impl Area {
             collection,
             owned_ids,
             uses_names: names,
+            constants,
+            data,
+            functions,
             models,
             treatments,
         }
@@ -163,6 +192,18 @@ impl Area {
         &self.uses_names
     }
 
+    pub fn constants(&self) -> &Vec<Constant> {
+        &self.constants
+    }
+
+    pub fn data(&self) -> &Vec<Data> {
+        &self.data
+    }
+
+    pub fn functions(&self) -> &Vec<Function> {
+        &self.functions
+    }
+
     pub fn models(&self) -> &Vec<Model> {
         &self.models
     }
@@ -221,6 +262,18 @@ impl Area {
                 .collect_vec(),
         );
 
+        for constant in &self.constants {
+            result.push_str(&constant.implementation(&self.uses_names));
+        }
+
+        for data in &self.data {
+            result.push_str(&data.implementation(&self.uses_names));
+        }
+
+        for function in &self.functions {
+            result.push_str(&function.implementation(&self.uses_names));
+        }
+
         for model in &self.models {
             result.push_str(&model.implementation(&self.uses_names));
         }
@@ -254,13 +307,18 @@ impl Area {
     fn is_owned(path: &Path, collection: &Arc<Collection>, id: &Identifier) -> bool {
         if id.path() == path.path() {
             match collection.get(&id.into()) {
+                Some(Entry::Constant(_)) => true,
                 Some(Entry::Context(_)) => false,
-                Some(Entry::Function(_)) => false,
+                Some(Entry::Function(function)) => function
+                    .clone()
+                    .downcast_arc::<FunctionDescriptor>()
+                    .map(|designed| designed.design().is_some())
+                    .unwrap_or(false),
                 Some(Entry::Model(model)) => match model.build_mode() {
                     ModelBuildMode::Compiled(_) => false,
                     ModelBuildMode::Designed() => true,
                 },
-                Some(Entry::Data(_)) => false,
+                Some(Entry::Data(data)) => data.structure().is_some(),
                 Some(Entry::Treatment(treatment)) => match treatment.build_mode() {
                     TreatmentBuildMode::Compiled(_, _) => false,
                     TreatmentBuildMode::Source(_) => false,
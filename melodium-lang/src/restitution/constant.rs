@@ -0,0 +1,60 @@
+use crate::restitution::describe_type;
+use melodium_common::descriptor::{Constant as ConstantDescriptor, Identifier};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+pub struct Constant {
+    descriptor: Arc<dyn ConstantDescriptor>,
+    uses: Vec<Identifier>,
+}
+
+impl Constant {
+    pub fn new(descriptor: Arc<dyn ConstantDescriptor>) -> Self {
+        let uses = descriptor.uses();
+
+        Self { descriptor, uses }
+    }
+
+    pub fn descriptor(&self) -> &Arc<dyn ConstantDescriptor> {
+        &self.descriptor
+    }
+
+    pub fn uses(&self) -> &Vec<Identifier> {
+        &self.uses
+    }
+
+    pub fn implementation(&self, names: &BTreeMap<Identifier, String>) -> String {
+        let descriptor = &self.descriptor;
+
+        let mut implementation = if descriptor.documentation().trim().is_empty() {
+            String::new()
+        } else {
+            format!(
+                "/**\n{}\n*/\n",
+                descriptor
+                    .documentation()
+                    .lines()
+                    .map(|l| format!("\t{l}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        };
+
+        for (name, attribute) in descriptor.attributes() {
+            implementation.push_str("#[");
+            implementation.push_str(name);
+            implementation.push_str("(");
+            implementation.push_str(&attribute);
+            implementation.push_str(")]\n");
+        }
+
+        implementation.push_str(&format!(
+            "const {name}: {described_type} = {value}\n\n",
+            name = descriptor.identifier().name(),
+            described_type = describe_type(descriptor.described_type(), names),
+            value = descriptor.value(),
+        ));
+
+        implementation
+    }
+}
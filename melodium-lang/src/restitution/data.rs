@@ -0,0 +1,96 @@
+use crate::restitution::describe_type;
+use melodium_common::descriptor::{Data as DataDescriptor, DataStructure, Identifier};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+pub struct Data {
+    descriptor: Arc<dyn DataDescriptor>,
+    uses: Vec<Identifier>,
+}
+
+impl Data {
+    pub fn new(descriptor: Arc<dyn DataDescriptor>) -> Self {
+        let uses = descriptor.uses();
+
+        Self { descriptor, uses }
+    }
+
+    pub fn descriptor(&self) -> &Arc<dyn DataDescriptor> {
+        &self.descriptor
+    }
+
+    pub fn uses(&self) -> &Vec<Identifier> {
+        &self.uses
+    }
+
+    pub fn implementation(&self, names: &BTreeMap<Identifier, String>) -> String {
+        let descriptor = &self.descriptor;
+
+        let mut implementation = if descriptor.documentation().trim().is_empty() {
+            String::new()
+        } else {
+            format!(
+                "/**\n{}\n*/\n",
+                descriptor
+                    .documentation()
+                    .lines()
+                    .map(|l| format!("\t{l}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        };
+
+        for (name, attribute) in descriptor.attributes() {
+            implementation.push_str("#[");
+            implementation.push_str(name);
+            implementation.push_str("(");
+            implementation.push_str(&attribute);
+            implementation.push_str(")]\n");
+        }
+
+        let members = match descriptor.structure() {
+            None => return String::new(),
+            Some(DataStructure::Record(fields)) => {
+                implementation.push_str("data ");
+                fields
+                    .iter()
+                    .map(|(name, described_type)| {
+                        format!("{name}: {}", describe_type(described_type, names))
+                    })
+                    .collect::<Vec<_>>()
+            }
+            Some(DataStructure::Enumeration(variants)) => {
+                implementation.push_str("enum ");
+                variants.clone()
+            }
+        };
+        implementation.push_str(descriptor.identifier().name());
+
+        if !descriptor.implements().is_empty() {
+            implementation.push_str(": ");
+            implementation.push_str(
+                &descriptor
+                    .implements()
+                    .iter()
+                    .map(|data_trait| data_trait.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" + "),
+            );
+        }
+
+        implementation.push_str(" {\n");
+        implementation.push_str(
+            &members
+                .iter()
+                .map(|member| format!("    {member}"))
+                .collect::<Vec<_>>()
+                .join(",\n"),
+        );
+        if !members.is_empty() {
+            implementation.push_str("\n");
+        }
+        implementation.push_str("}\n\n");
+
+        implementation
+    }
+}
@@ -0,0 +1,659 @@
+use crate::text::{get_words, Kind, Script, Word, DECLARATIONS};
+use crate::ScriptResult;
+
+/// Indentation added for each nesting level.
+const INDENT: usize = 4;
+/// Indentation of treatment and model header lines, such as `input`, `output`, or `model` ones.
+const HEADER_INDENT: usize = 2;
+
+/// Formats script text canonically.
+///
+/// Script is parsed first, and the errors it contains are returned if it is not syntactically valid.
+/// Formatting gathers sorted `use` declarations at the top of the script, indents lines according to their nesting,
+/// normalizes spacing between words, and aligns the destinations of connections written on consecutive lines.
+/// Line breaks chosen by author are kept, as well as comments, documentation, and annotations.
+pub fn format_script(text: &str) -> ScriptResult<String> {
+    Script::build(text)
+        .and_then(|_| ScriptResult::new_success(format_words(&get_words(text).unwrap_or_default())))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkKind {
+    /// Comments or annotations standing by themselves.
+    Trivia,
+    Use,
+    /// Declaration that is not followed by a body, such as `const`.
+    Line,
+    /// Declaration having a body, `header` telling if it may have header lines before it.
+    Braced {
+        header: bool,
+    },
+}
+
+/// Part of script, being a declaration along with its leading comments and annotations, or standalone comments.
+struct Chunk<'a> {
+    kind: ChunkKind,
+    words: &'a [Word],
+    /// Index of the declaration keyword within words.
+    keyword: usize,
+    /// Tells if there were blank lines before chunk in original text.
+    blank_before: bool,
+}
+
+impl Chunk<'_> {
+    /// Tells if chunk is a declaration with a body spanning on several lines.
+    fn is_block(&self) -> bool {
+        matches!(self.kind, ChunkKind::Braced { .. })
+            && self.words.last().map(end_line) > self.words.get(self.keyword).map(end_line)
+    }
+}
+
+fn format_words(words: &[Word]) -> String {
+    let header_len = words
+        .iter()
+        .take_while(|word| word.kind == Some(Kind::Annotation) && word.text.starts_with("#!"))
+        .count();
+    let header = words[..header_len]
+        .iter()
+        .map(|word| word.text.trim_end().to_string())
+        .collect::<Vec<_>>();
+
+    let chunks = chunks(words, header_len);
+
+    let first_use = chunks.iter().position(|chunk| chunk.kind == ChunkKind::Use);
+    let mut uses = chunks
+        .iter()
+        .filter(|chunk| chunk.kind == ChunkKind::Use)
+        .map(render_use)
+        .collect::<Vec<_>>();
+    uses.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let uses = uses
+        .into_iter()
+        .flat_map(|(_, lines)| lines)
+        .collect::<Vec<_>>();
+
+    let others = chunks
+        .iter()
+        .enumerate()
+        .filter(|(_, chunk)| chunk.kind != ChunkKind::Use)
+        .collect::<Vec<_>>();
+
+    let mut blocks: Vec<Vec<String>> = Vec::new();
+    if !header.is_empty() {
+        blocks.push(header);
+    }
+
+    let mut previous: Option<&Chunk> = None;
+    let mut uses = Some(uses);
+    for (index, chunk) in others {
+        if first_use
+            .map(|first_use| index > first_use)
+            .unwrap_or(false)
+        {
+            if let Some(uses) = uses.take() {
+                blocks.push(uses);
+                previous = None;
+            }
+        }
+
+        let lines = render(chunk);
+        match (previous, blocks.last_mut()) {
+            (Some(previous), Some(block)) if !separated(previous, chunk) => block.extend(lines),
+            _ => blocks.push(lines),
+        }
+        previous = Some(chunk);
+    }
+    if let Some(uses) = uses.filter(|uses| !uses.is_empty()) {
+        blocks.push(uses);
+    }
+
+    let mut text = blocks
+        .into_iter()
+        .map(|block| block.join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    text.push('\n');
+    text
+}
+
+/// Tells if a blank line must be placed between chunks.
+///
+/// Declarations with body spanning on several lines are always separated from others.
+fn separated(previous: &Chunk, next: &Chunk) -> bool {
+    next.blank_before || previous.is_block() || next.is_block()
+}
+
+/// Splits words into chunks, starting at `start`.
+fn chunks(words: &[Word], start: usize) -> Vec<Chunk<'_>> {
+    let mut chunks = Vec::new();
+    let mut index = start;
+
+    while index < words.len() {
+        let trivia_start = index;
+        while index < words.len() && is_trivia(&words[index]) {
+            index += 1;
+        }
+
+        let mut group_start = trivia_start;
+        for position in trivia_start..index {
+            if position > group_start && blank_between(&words[position - 1], &words[position]) {
+                chunks.push(Chunk {
+                    kind: ChunkKind::Trivia,
+                    words: &words[group_start..position],
+                    keyword: position - group_start,
+                    blank_before: blank_before(words, group_start, start),
+                });
+                group_start = position;
+            }
+        }
+
+        if index == words.len() {
+            if group_start < index {
+                chunks.push(Chunk {
+                    kind: ChunkKind::Trivia,
+                    words: &words[group_start..index],
+                    keyword: index - group_start,
+                    blank_before: blank_before(words, group_start, start),
+                });
+            }
+            break;
+        }
+
+        if group_start < index && blank_between(&words[index - 1], &words[index]) {
+            chunks.push(Chunk {
+                kind: ChunkKind::Trivia,
+                words: &words[group_start..index],
+                keyword: index - group_start,
+                blank_before: blank_before(words, group_start, start),
+            });
+            group_start = index;
+        }
+
+        let kind = match words[index].text.as_str() {
+            "use" => ChunkKind::Use,
+            "treatment" | "model" => ChunkKind::Braced { header: true },
+            "data" | "enum" | "function" => ChunkKind::Braced { header: false },
+            _ => ChunkKind::Line,
+        };
+        let end = declaration_end(words, index, kind);
+
+        chunks.push(Chunk {
+            kind,
+            words: &words[group_start..end],
+            keyword: index - group_start,
+            blank_before: blank_before(words, group_start, start),
+        });
+        index = end;
+    }
+
+    chunks
+}
+
+/// Gives the index following the last word of declaration starting at `keyword`, including comments on its last line.
+fn declaration_end(words: &[Word], keyword: usize, kind: ChunkKind) -> usize {
+    let mut depth = 0usize;
+    let mut index = keyword + 1;
+
+    while index < words.len() {
+        let word = &words[index];
+        match kind {
+            ChunkKind::Braced { .. } => {
+                if is_opener(word) {
+                    depth += 1;
+                } else if is_closer(word) {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 && word.kind == Some(Kind::ClosingBrace) {
+                        index += 1;
+                        break;
+                    }
+                }
+            }
+            _ => {
+                if depth == 0
+                    && starts_line(&words[index - 1], word)
+                    && (is_trivia(word)
+                        || (word.kind == Some(Kind::Name)
+                            && DECLARATIONS.contains(&word.text.as_str())))
+                {
+                    return index;
+                }
+                if is_opener(word) {
+                    depth += 1;
+                } else if is_closer(word) {
+                    depth = depth.saturating_sub(1);
+                }
+            }
+        }
+        index += 1;
+    }
+
+    while index < words.len()
+        && words[index].kind == Some(Kind::Comment)
+        && !starts_line(&words[index - 1], &words[index])
+    {
+        index += 1;
+    }
+
+    index.min(words.len())
+}
+
+fn blank_before(words: &[Word], index: usize, start: usize) -> bool {
+    index > start && blank_between(&words[index - 1], &words[index])
+}
+
+/// Renders `use` chunk, giving sort key made of path steps and element, along with lines.
+fn render_use(chunk: &Chunk) -> ((Vec<String>, String), Vec<String>) {
+    let mut lines = if chunk.keyword > 0 {
+        render(&Chunk {
+            kind: ChunkKind::Trivia,
+            words: &chunk.words[..chunk.keyword],
+            keyword: chunk.keyword,
+            blank_before: false,
+        })
+    } else {
+        Vec::new()
+    };
+
+    let (comments, words): (Vec<&Word>, Vec<&Word>) = chunk.words[chunk.keyword + 1..]
+        .iter()
+        .partition(|word| is_trivia(word));
+
+    let separator = words
+        .iter()
+        .rposition(|word| word.kind == Some(Kind::Colon))
+        .unwrap_or(0);
+    let path = words[..separator.saturating_sub(1)]
+        .split(|word| word.kind == Some(Kind::Slash))
+        .map(|step| step.iter().map(|word| word.text.as_str()).collect())
+        .collect::<Vec<String>>();
+    let element = words[(separator + 1).min(words.len())..]
+        .iter()
+        .map(|word| word.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut line = format!("use {}::{element}", path.join("/"));
+    for comment in comments {
+        line.push(' ');
+        line.push_str(comment.text.trim_end());
+    }
+    lines.push(line);
+
+    ((path, element), lines)
+}
+
+/// Line of a chunk, once indented.
+struct Line<'a> {
+    words: Vec<&'a Word>,
+    indent: usize,
+    blank_before: bool,
+    /// Connection segments, if line is made of a connection chain only.
+    connection: Option<Vec<String>>,
+}
+
+/// Renders chunk lines.
+fn render(chunk: &Chunk) -> Vec<String> {
+    let mut lines: Vec<Line> = Vec::new();
+    let mut stack: Vec<(Kind, usize)> = Vec::new();
+
+    let mut index = 0;
+    while index < chunk.words.len() {
+        let line_start = index;
+        index += 1;
+        while index < chunk.words.len()
+            && !starts_line(&chunk.words[index - 1], &chunk.words[index])
+        {
+            index += 1;
+        }
+        let words = chunk.words[line_start..index].iter().collect::<Vec<_>>();
+        let first = words[0];
+
+        let indent = if is_closer(first) {
+            stack.last().map(|(_, indent)| *indent).unwrap_or(0)
+        } else if let Some((_, indent)) = stack.last() {
+            indent + INDENT
+        } else if chunk.kind == (ChunkKind::Braced { header: true })
+            && line_start > chunk.keyword
+            && first.kind != Some(Kind::OpeningBrace)
+        {
+            HEADER_INDENT
+        } else {
+            0
+        };
+
+        let in_body = chunk.kind == (ChunkKind::Braced { header: true })
+            && chunk.words[chunk.keyword].text == "treatment"
+            && stack.len() == 1
+            && stack[0].0 == Kind::OpeningBrace
+            && !is_closer(first);
+
+        let blank_before = line_start > 0
+            && blank_between(&chunk.words[line_start - 1], first)
+            && chunk.words[line_start - 1].kind != Some(Kind::OpeningBrace)
+            && first.kind != Some(Kind::ClosingBrace);
+
+        for word in &words {
+            if is_opener(word) {
+                stack.push((word.kind.unwrap(), indent));
+            } else if is_closer(word) {
+                stack.pop();
+            }
+        }
+
+        lines.push(Line {
+            connection: if in_body { connection(&words) } else { None },
+            words,
+            indent,
+            blank_before,
+        });
+    }
+
+    let mut rendered = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].connection.is_some() {
+            let group_start = index;
+            index += 1;
+            while index < lines.len()
+                && lines[index].connection.is_some()
+                && !lines[index].blank_before
+            {
+                index += 1;
+            }
+            if lines[group_start].blank_before {
+                rendered.push(String::new());
+            }
+            rendered.extend(render_connections(&lines[group_start..index]));
+        } else {
+            let line = &lines[index];
+            if line.blank_before {
+                rendered.push(String::new());
+            }
+            rendered.push(format!(
+                "{}{}",
+                " ".repeat(line.indent),
+                render_words(&line.words)
+            ));
+            index += 1;
+        }
+    }
+
+    rendered
+}
+
+/// Gives the segments of a connection chain, like `a.out -> b.in,out -> c.in`, if line is made only of it.
+///
+/// Comments ending line are given as last segment.
+fn connection(words: &[&Word]) -> Option<Vec<String>> {
+    let code_len = words
+        .iter()
+        .rposition(|word| word.kind != Some(Kind::Comment))
+        .map(|position| position + 1)
+        .unwrap_or(0);
+    let (code, comments) = words.split_at(code_len);
+
+    let mut segments = Vec::new();
+    for segment in code.split(|word| word.kind == Some(Kind::RightArrow)) {
+        let kinds = segment.iter().map(|word| word.kind).collect::<Vec<_>>();
+        let valid = matches!(
+            kinds.as_slice(),
+            [Some(Kind::Name), Some(Kind::Dot), Some(Kind::Name)]
+                | [
+                    Some(Kind::Name),
+                    Some(Kind::Dot),
+                    Some(Kind::Name),
+                    Some(Kind::Comma),
+                    Some(Kind::Name)
+                ]
+        );
+        if !valid {
+            return None;
+        }
+        segments.push(segment.iter().map(|word| word.text.as_str()).collect());
+    }
+
+    if segments.len() < 2 {
+        return None;
+    }
+
+    segments.push(
+        comments
+            .iter()
+            .map(|comment| format!(" {}", comment.text.trim_end()))
+            .collect(),
+    );
+    Some(segments)
+}
+
+/// Renders group of consecutive connection lines, aligning their destinations.
+///
+/// Lines that were indented more than the first one of the group are continuing a previous line,
+/// and start under the connection element they share with it.
+fn render_connections(lines: &[Line]) -> Vec<String> {
+    let original_indent = lines[0].words[0].position.line_position;
+
+    let mut anchors: Vec<(&str, usize)> = Vec::new();
+    let mut prefixes = Vec::new();
+    for line in lines {
+        let segments = line.connection.as_ref().unwrap();
+        let chain = &segments[..segments.len() - 1];
+
+        let mut start = line.indent;
+        if line.words[0].position.line_position > original_indent {
+            if let Some((_, column)) = anchors
+                .iter()
+                .rev()
+                .find(|(instance, _)| *instance == self::instance(&chain[0]))
+            {
+                start = *column;
+            }
+        }
+
+        let mut column = start;
+        for segment in &chain[..chain.len() - 1] {
+            anchors.push((instance(segment), column));
+            column += segment.chars().count() + 4;
+        }
+
+        let prefix = format!(
+            "{}{}",
+            " ".repeat(start),
+            chain[..chain.len() - 1].join(" -> ")
+        );
+        prefixes.push(prefix);
+    }
+
+    let destination = prefixes
+        .iter()
+        .map(|prefix| prefix.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 4;
+
+    lines
+        .iter()
+        .zip(prefixes)
+        .map(|(line, prefix)| {
+            let segments = line.connection.as_ref().unwrap();
+            let dashes = destination - prefix.chars().count() - 3;
+            format!(
+                "{prefix} {}> {}{}",
+                "-".repeat(dashes),
+                segments[segments.len() - 2],
+                segments[segments.len() - 1]
+            )
+        })
+        .collect()
+}
+
+fn instance(segment: &str) -> &str {
+    segment.split('.').next().unwrap_or_default()
+}
+
+/// Renders words of a line with canonical spacing.
+fn render_words(words: &[&Word]) -> String {
+    let connection_line = words.iter().any(|word| word.kind == Some(Kind::RightArrow))
+        && !words
+            .iter()
+            .any(|word| word.kind == Some(Kind::OpeningParenthesis));
+
+    let mut text = String::new();
+    for (index, word) in words.iter().enumerate() {
+        if index > 0 && spaced(words, index, connection_line) {
+            text.push(' ');
+        }
+        match word.kind {
+            Some(Kind::Comment) | Some(Kind::Annotation) => text.push_str(word.text.trim_end()),
+            _ => text.push_str(&word.text),
+        }
+    }
+    text
+}
+
+/// Tells if a space is needed between the word at `index` and the previous one.
+fn spaced(words: &[&Word], index: usize, connection_line: bool) -> bool {
+    let previous = words[index - 1].kind;
+    let word = words[index].kind;
+
+    if word == Some(Kind::Comment) {
+        return true;
+    }
+    if word == Some(Kind::Equal) || previous == Some(Kind::Equal) {
+        let equal = if word == Some(Kind::Equal) {
+            index
+        } else {
+            index - 1
+        };
+        return !compact_assignment(words, equal);
+    }
+
+    match (previous, word) {
+        (Some(Kind::OpeningBrace), Some(Kind::ClosingBrace)) => false,
+        (Some(Kind::OpeningBrace), _) | (_, Some(Kind::ClosingBrace)) => true,
+        (_, Some(Kind::OpeningBrace)) => true,
+        (Some(Kind::OpeningParenthesis), _)
+        | (Some(Kind::OpeningBracket), _)
+        | (Some(Kind::OpeningChevron), _)
+        | (Some(Kind::Dot), _)
+        | (Some(Kind::Slash), _)
+        | (_, Some(Kind::Slash))
+        | (Some(Kind::Colon), Some(Kind::Colon)) => false,
+        (_, Some(Kind::Comma))
+        | (_, Some(Kind::Dot))
+        | (_, Some(Kind::Colon))
+        | (_, Some(Kind::OpeningParenthesis))
+        | (_, Some(Kind::ClosingParenthesis))
+        | (_, Some(Kind::ClosingBracket))
+        | (_, Some(Kind::OpeningChevron))
+        | (_, Some(Kind::ClosingChevron)) => false,
+        (_, Some(Kind::OpeningBracket)) => matches!(
+            previous,
+            Some(Kind::Comma) | Some(Kind::Colon) | Some(Kind::RightArrow) | Some(Kind::Plus)
+        ),
+        (Some(Kind::Comma), _) => !connection_line,
+        _ => true,
+    }
+}
+
+/// Tells if `=` at index is the one of a parameter given within a list written on a single line, like `emit<string>(value="")`.
+fn compact_assignment(words: &[&Word], equal: usize) -> bool {
+    equal >= 2
+        && words[equal - 1].kind == Some(Kind::Name)
+        && matches!(
+            words[equal - 2].kind,
+            Some(Kind::OpeningParenthesis) | Some(Kind::OpeningBracket) | Some(Kind::Comma)
+        )
+}
+
+fn is_trivia(word: &Word) -> bool {
+    matches!(word.kind, Some(Kind::Comment) | Some(Kind::Annotation))
+}
+
+fn is_opener(word: &Word) -> bool {
+    matches!(
+        word.kind,
+        Some(Kind::OpeningParenthesis) | Some(Kind::OpeningBracket) | Some(Kind::OpeningBrace)
+    )
+}
+
+fn is_closer(word: &Word) -> bool {
+    matches!(
+        word.kind,
+        Some(Kind::ClosingParenthesis) | Some(Kind::ClosingBracket) | Some(Kind::ClosingBrace)
+    )
+}
+
+/// Gives the line number where word ends.
+fn end_line(word: &Word) -> usize {
+    word.position.line_number + word.text.matches('\n').count()
+}
+
+/// Tells if `word` is on a later line than `previous` one.
+fn starts_line(previous: &Word, word: &Word) -> bool {
+    word.position.line_number > end_line(previous)
+}
+
+/// Tells if there is at least one blank line between words.
+fn blank_between(previous: &Word, word: &Word) -> bool {
+    word.position.line_number > end_line(previous) + 1
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const SCRIPT: &str = "#!/usr/bin/env melodium
+#! name = format_test
+use std/flow::stream
+// Startup comment
+use std/engine/util::startup
+use std/flow::emit
+
+treatment main( var value:string=\"hello\" )
+    output values: Stream<string>
+{
+  startup()
+        emit<string>( value = value ) // emitter
+  stream<string>()
+
+  startup.trigger -> emit.trigger
+  emit.emit --> stream.block,stream -> Self.values
+}
+";
+
+    const FORMATTED: &str = "#!/usr/bin/env melodium
+#! name = format_test
+
+// Startup comment
+use std/engine/util::startup
+use std/flow::emit
+use std/flow::stream
+
+treatment main(var value: string = \"hello\")
+  output values: Stream<string>
+{
+    startup()
+    emit<string>(value=value) // emitter
+    stream<string>()
+
+    startup.trigger ------------------> emit.trigger
+    emit.emit -> stream.block,stream -> Self.values
+}
+";
+
+    #[test]
+    fn test_format_script() {
+        assert_eq!(format_script(SCRIPT).success().unwrap(), FORMATTED);
+    }
+
+    #[test]
+    fn test_format_is_stable() {
+        assert_eq!(format_script(FORMATTED).success().unwrap(), FORMATTED);
+    }
+
+    #[test]
+    fn test_format_invalid_script() {
+        assert!(format_script("treatment main(\n{\n}\n").is_failure());
+    }
+}
@@ -0,0 +1,124 @@
+use super::{describe_type, value::value};
+use melodium_common::descriptor::{
+    Attribuable, Documented, Function as FunctionDescriptor, Generics, Identified, Identifier,
+    OrderedParameterized,
+};
+use melodium_engine::descriptor::Function as DesignedFunction;
+use melodium_engine::designer::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+pub struct Function {
+    descriptor: Arc<DesignedFunction>,
+    design: Value,
+    uses: Vec<Identifier>,
+}
+
+impl Function {
+    pub fn new(descriptor: Arc<DesignedFunction>, design: Value) -> Self {
+        let mut uses = descriptor.uses();
+
+        uses.retain(|id| id != descriptor.identifier());
+
+        Self {
+            descriptor,
+            design,
+            uses,
+        }
+    }
+
+    pub fn descriptor(&self) -> &Arc<DesignedFunction> {
+        &self.descriptor
+    }
+
+    pub fn design(&self) -> &Value {
+        &self.design
+    }
+
+    pub fn uses(&self) -> &Vec<Identifier> {
+        &self.uses
+    }
+
+    pub fn implementation(&self, names: &BTreeMap<Identifier, String>) -> String {
+        let descriptor = &self.descriptor;
+
+        let mut implementation = if descriptor.documentation().trim().is_empty() {
+            String::new()
+        } else {
+            format!(
+                "/**\n{}\n*/\n",
+                descriptor
+                    .documentation()
+                    .lines()
+                    .map(|l| format!("\t{l}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        };
+
+        for (name, attribute) in descriptor.attributes() {
+            implementation.push_str("#[");
+            implementation.push_str(name);
+            implementation.push_str("(");
+            implementation.push_str(&attribute);
+            implementation.push_str(")]\n");
+        }
+
+        implementation.push_str("function ");
+        implementation.push_str(descriptor.identifier().name());
+
+        if !descriptor.generics().is_empty() {
+            implementation.push('<');
+
+            implementation.push_str(
+                &descriptor
+                    .generics()
+                    .iter()
+                    .map(|generic| {
+                        if generic.traits.is_empty() {
+                            generic.name.clone()
+                        } else {
+                            format!(
+                                "{}: {}",
+                                generic.name,
+                                generic
+                                    .traits
+                                    .iter()
+                                    .map(|tr| tr.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join(" + ")
+                            )
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+
+            implementation.push('>');
+        }
+
+        implementation.push_str("(");
+        implementation.push_str(
+            &descriptor
+                .parameters()
+                .iter()
+                .map(|param| {
+                    format!(
+                        "{name}: {param}",
+                        name = param.name(),
+                        param = describe_type(param.described_type(), names),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+        implementation.push_str(") -> ");
+        implementation.push_str(&describe_type(descriptor.return_type(), names));
+
+        implementation.push_str(" {\n    ");
+        implementation.push_str(&value(&self.design, names, 2));
+        implementation.push_str("\n}\n\n");
+
+        implementation
+    }
+}
@@ -1,11 +1,19 @@
-//! Provides restitution of Mélodium scripts from collection of elements.
+//! Provides restitution of Mélodium scripts from collection of elements, and canonical formatting of scripts text.
 
 mod area;
+mod constant;
+mod data;
+mod format;
+mod function;
 mod model;
 mod treatment;
 mod value;
 
 pub use area::Area;
+pub use constant::Constant;
+pub use data::Data;
+pub use format::format_script;
+pub use function::Function;
 pub use model::Model;
 pub use treatment::Treatment;
 
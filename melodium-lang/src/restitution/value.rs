@@ -26,6 +26,7 @@ pub fn value(value: &Value, names: &BTreeMap<Identifier, String>, level: usize)
                 .join(&format!(",\n{}", "    ".repeat(level)))
         ),
         Value::Variable(var) => var.clone(),
+        Value::Constant(constant) => names.get(constant.identifier()).unwrap().clone(),
         Value::Context(context, entry) => {
             format!(
                 "{name}[{entry}]",
@@ -90,11 +90,11 @@ impl AssignedParameter {
         let described_type_result = designer.described_type();
 
         if let Some(Some(described_type)) = described_type_result.success() {
-            let designed_value = self.value.read().unwrap().make_designed_value(
-                &designer,
-                &described_type,
-                collection,
-            );
+            let designed_value = self
+                .value
+                .read()
+                .unwrap()
+                .make_designed_value(&described_type, collection);
             designed_value
                 .and_then(|designed_value| ScriptResult::from(designer.set_value(designed_value)))
         } else {
@@ -80,12 +80,22 @@ pub trait Node {
 }
 
 /// Structure holding name and weak-counted reference to another element.
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug)]
 pub struct Reference<T> {
     pub name: String,
     pub reference: Option<Weak<RwLock<T>>>,
 }
 
+// Implemented manually as deriving it would require `T: Clone`, while only weak reference is cloned.
+impl<T> Clone for Reference<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            reference: self.reference.clone(),
+        }
+    }
+}
+
 impl<T> Reference<T> {
     pub fn new(name: String) -> Self {
         Self {
@@ -0,0 +1,267 @@
+//! Module dedicated to Constant semantic analysis.
+
+use super::common::Node;
+use super::declarative_element::{DeclarativeElement, DeclarativeElementType};
+use super::declared_parameter::DeclaredParameter;
+use super::r#type::Type;
+use super::script::Script;
+use super::value::Value;
+use crate::error::ScriptError;
+use crate::path::Path;
+use crate::text::Constant as TextConstant;
+use crate::ScriptResult;
+use melodium_common::descriptor::{Collection, Identifier, VersionReq};
+use melodium_engine::descriptor::Constant as ConstantDescriptor;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, Weak};
+
+/// Structure managing and describing semantic of a constant declaration.
+///
+/// It owns the whole [text constant](TextConstant).
+#[derive(Debug)]
+pub struct Constant {
+    pub text: TextConstant,
+
+    pub script: Weak<RwLock<Script>>,
+
+    pub name: String,
+    pub r#type: Option<Arc<RwLock<Type>>>,
+    pub value: Option<Arc<RwLock<Value>>>,
+
+    pub identifier: Option<Identifier>,
+    pub descriptor: RwLock<Option<Arc<ConstantDescriptor>>>,
+}
+
+impl Constant {
+    /// Create a new semantic constant, based on textual constant.
+    ///
+    /// * `script`: the parent script that "owns" this constant.
+    /// * `text`: the textual constant.
+    ///
+    /// # Note
+    /// Only parent-child relationships are made at this step. Other references can be made afterwards using the [Node trait](Node).
+    ///
+    pub fn new(script: Arc<RwLock<Script>>, text: TextConstant) -> ScriptResult<Arc<RwLock<Self>>> {
+        let constant = Arc::<RwLock<Self>>::new(RwLock::new(Self {
+            text: text.clone(),
+            script: Arc::downgrade(&script),
+            name: text.name.string.clone(),
+            r#type: None,
+            value: None,
+            identifier: None,
+            descriptor: RwLock::new(None),
+        }));
+        let mut result = ScriptResult::new_success(Arc::clone(&constant));
+
+        {
+            let borrowed_script = script.read().unwrap();
+
+            if borrowed_script.find_constant(&text.name.string).is_some() {
+                result = result.and_degrade_failure(ScriptResult::new_failure(
+                    ScriptError::already_used_name(236, text.name.clone()),
+                ));
+            }
+
+            if borrowed_script.find_use(&text.name.string).is_some() {
+                result = result.and_degrade_failure(ScriptResult::new_failure(
+                    ScriptError::already_used_name(237, text.name.clone()),
+                ));
+            }
+        }
+
+        if let Some(flow) = text
+            .r#type
+            .level_structure
+            .first()
+            .filter(|flow| flow.string.as_str() == "Block" || flow.string.as_str() == "Stream")
+        {
+            result = result.and_degrade_failure(ScriptResult::new_failure(
+                ScriptError::flow_forbidden(238, flow.clone()),
+            ));
+        } else if let Some(r#type) = result.merge_degrade_failure(Type::new(
+            Arc::clone(&constant) as Arc<RwLock<dyn DeclarativeElement>>,
+            text.r#type,
+        )) {
+            constant.write().unwrap().r#type = Some(Arc::new(RwLock::new(r#type)));
+        }
+
+        if let Some(value) = result.merge_degrade_failure(Value::new(
+            Arc::clone(&constant) as Arc<RwLock<dyn DeclarativeElement>>,
+            text.value,
+        )) {
+            constant.write().unwrap().value = Some(value);
+        }
+
+        result
+    }
+
+    pub fn make_descriptor(
+        &self,
+        collection: &Collection,
+    ) -> ScriptResult<Arc<ConstantDescriptor>> {
+        let r#type = self.r#type.as_ref().unwrap().read().unwrap();
+        r#type
+            .make_descriptor(collection)
+            .and_then(|(described_type, _)| {
+                if let Some(datatype) = described_type.to_datatype(&HashMap::new()) {
+                    self.value
+                        .as_ref()
+                        .unwrap()
+                        .read()
+                        .unwrap()
+                        .make_executive_value(&datatype, collection)
+                        .and_then(|value| ScriptResult::new_success((described_type, value)))
+                } else {
+                    ScriptResult::new_failure(ScriptError::undeclared_data(
+                        239,
+                        r#type.text.name.clone(),
+                    ))
+                }
+            })
+            .and_then(|(described_type, value)| {
+                let mut descriptor = ConstantDescriptor::new(
+                    self.identifier.as_ref().unwrap().clone(),
+                    described_type,
+                    value,
+                );
+
+                if let Some(annotations) = self.text.annotations.as_ref() {
+                    if let Some(doc) = &annotations.doc {
+                        descriptor.set_documentation(&doc.string);
+                    }
+
+                    for annotation in &annotations.annotations {
+                        if let Some((name, attribute)) = annotation.as_attribute() {
+                            descriptor.add_attribute(name, attribute);
+                        }
+                    }
+                }
+
+                let descriptor = descriptor.commit();
+
+                *self.descriptor.write().unwrap() = Some(descriptor.clone());
+
+                ScriptResult::new_success(descriptor)
+            })
+    }
+}
+
+impl DeclarativeElement for Constant {
+    fn declarative_element(&'_ self) -> DeclarativeElementType<'_> {
+        DeclarativeElementType::Constant(&self)
+    }
+
+    fn find_declared_parameter(&self, _name: &str) -> Option<&Arc<RwLock<DeclaredParameter>>> {
+        None
+    }
+}
+
+impl Node for Constant {
+    fn make_references(
+        &mut self,
+        path: &Path,
+        _versions: &HashMap<String, VersionReq>,
+    ) -> ScriptResult<()> {
+        self.identifier = path.to_identifier(&self.name);
+
+        ScriptResult::new_success(())
+    }
+
+    fn children(&self) -> Vec<Arc<RwLock<dyn Node>>> {
+        let mut children: Vec<Arc<RwLock<dyn Node>>> = Vec::new();
+
+        self.r#type
+            .iter()
+            .for_each(|t| children.push(Arc::clone(&t) as Arc<RwLock<dyn Node>>));
+        self.value
+            .iter()
+            .for_each(|v| children.push(Arc::clone(&v) as Arc<RwLock<dyn Node>>));
+
+        children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::semantic::Tree;
+    use crate::text::Script as TextScript;
+    use melodium_common::descriptor::{Constant as _, Version};
+    use melodium_common::executive::Value as ExecutiveValue;
+
+    fn tree(text: &str) -> ScriptResult<Tree> {
+        TextScript::build(text).and_then(|text| Tree::new(text, Version::new(0, 1, 0)))
+    }
+
+    fn error_ids<T>(result: &ScriptResult<T>) -> Vec<u32> {
+        let mut ids: Vec<u32> = result.errors().iter().map(|error| error.id).collect();
+        if let Some(failure) = result.failure() {
+            ids.push(failure.id);
+        }
+        ids
+    }
+
+    fn descriptors(text: &str) -> ScriptResult<HashMap<String, Arc<ConstantDescriptor>>> {
+        tree(text).and_then(|tree| {
+            let path = Path::new(Version::new(0, 1, 0), vec!["test".to_string()]);
+            let mut result = tree.make_references(&path, &HashMap::new());
+            let collection = Collection::new();
+            let mut descriptors = HashMap::new();
+            for (name, constant) in &tree.script.read().unwrap().constants {
+                if let Some(descriptor) = result
+                    .merge_degrade_failure(constant.read().unwrap().make_descriptor(&collection))
+                {
+                    descriptors.insert(name.clone(), descriptor);
+                }
+            }
+            result.and(ScriptResult::new_success(descriptors))
+        })
+    }
+
+    #[test]
+    fn test_constants_described() {
+        let descriptors = descriptors("const LIMIT: u32 = 1000\nconst NAME: string = \"melodium\"");
+        assert!(error_ids(&descriptors).is_empty());
+
+        let descriptors = descriptors.success().unwrap();
+        assert_eq!(
+            descriptors.get("LIMIT").unwrap().value(),
+            &ExecutiveValue::U32(1000)
+        );
+        assert_eq!(
+            descriptors.get("NAME").unwrap().value(),
+            &ExecutiveValue::String("melodium".to_string())
+        );
+    }
+
+    #[test]
+    fn test_constant_name_already_used() {
+        let tree = tree("const LIMIT: u32 = 1000\nconst LIMIT: u32 = 2000");
+        assert_eq!(error_ids(&tree), vec![236]);
+    }
+
+    #[test]
+    fn test_constant_name_already_imported() {
+        let tree = tree("use std/flow::emit\nconst emit: u32 = 1000");
+        assert_eq!(error_ids(&tree), vec![237]);
+    }
+
+    #[test]
+    fn test_constant_flow_forbidden() {
+        let tree = tree("const LIMIT: Stream<u32> = 1000");
+        assert_eq!(error_ids(&tree), vec![238]);
+    }
+
+    #[test]
+    fn test_constant_generic_type() {
+        let result = descriptors("const LIMIT: T = 1000");
+        assert_eq!(error_ids(&result), vec![239]);
+    }
+
+    #[test]
+    fn test_constant_value_mismatching_type() {
+        let result = descriptors("const LIMIT: u32 = \"melodium\"");
+        assert!(!error_ids(&result).is_empty());
+    }
+}
@@ -0,0 +1,423 @@
+//! Module dedicated to Data semantic analysis.
+
+use super::common::Node;
+use super::declarative_element::{DeclarativeElement, DeclarativeElementType};
+use super::declared_parameter::DeclaredParameter;
+use super::r#type::Type;
+use super::script::Script;
+use crate::error::ScriptError;
+use crate::path::Path;
+use crate::text::{Data as TextData, DataStructure as TextDataStructure, PositionnedString};
+use crate::ScriptResult;
+use melodium_common::descriptor::{Collection, DataStructure, DataTrait, Identifier, VersionReq};
+use melodium_engine::descriptor::Data as DataDescriptor;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock, Weak};
+
+/// Traits that can be derived by data declared in scripts.
+const DERIVABLE_TRAITS: &[DataTrait] = &[
+    DataTrait::PartialEquality,
+    DataTrait::Equality,
+    DataTrait::Hash,
+    DataTrait::ToString,
+    DataTrait::Display,
+    DataTrait::Serialize,
+];
+
+/// Structure managing and describing semantic of a data declaration.
+///
+/// It owns the whole [text data](TextData).
+#[derive(Debug)]
+pub struct Data {
+    pub text: TextData,
+
+    pub script: Weak<RwLock<Script>>,
+
+    pub name: String,
+    pub traits: Vec<(PositionnedString, DataTrait)>,
+    pub fields: Vec<(PositionnedString, Arc<RwLock<Type>>)>,
+    pub variants: Vec<String>,
+
+    pub identifier: Option<Identifier>,
+    pub descriptor: RwLock<Option<Arc<DataDescriptor>>>,
+}
+
+impl Data {
+    /// Create a new semantic data, based on textual data.
+    ///
+    /// * `script`: the parent script that "owns" this data.
+    /// * `text`: the textual data.
+    ///
+    /// # Note
+    /// Only parent-child relationships are made at this step. Other references can be made afterwards using the [Node trait](Node).
+    ///
+    pub fn new(script: Arc<RwLock<Script>>, text: TextData) -> ScriptResult<Arc<RwLock<Self>>> {
+        let data = Arc::<RwLock<Self>>::new(RwLock::new(Self {
+            text: text.clone(),
+            script: Arc::downgrade(&script),
+            name: text.name.string.clone(),
+            traits: Vec::new(),
+            fields: Vec::new(),
+            variants: Vec::new(),
+            identifier: None,
+            descriptor: RwLock::new(None),
+        }));
+        let mut result = ScriptResult::new_success(data.clone());
+
+        {
+            let borrowed_script = script.read().unwrap();
+
+            if borrowed_script.find_data(&text.name.string).is_some() {
+                result = result.and_degrade_failure(ScriptResult::new_failure(
+                    ScriptError::already_used_name(203, text.name.clone()),
+                ));
+            }
+
+            if borrowed_script.find_use(&text.name.string).is_some() {
+                result = result.and_degrade_failure(ScriptResult::new_failure(
+                    ScriptError::already_used_name(204, text.name.clone()),
+                ));
+            }
+        }
+
+        for text_trait in &text.traits {
+            match DataTrait::from_str(&text_trait.string) {
+                Ok(data_trait) if DERIVABLE_TRAITS.contains(&data_trait) => {
+                    let mut data = data.write().unwrap();
+                    data.traits.push((text_trait.clone(), data_trait));
+                    // Equality is only meaningful along with partial equality.
+                    if data_trait == DataTrait::Equality {
+                        data.traits
+                            .push((text_trait.clone(), DataTrait::PartialEquality));
+                    }
+                }
+                Ok(_) => {
+                    result = result.and_degrade_failure(ScriptResult::new_failure(
+                        ScriptError::not_derivable_trait(205, text_trait.clone()),
+                    ));
+                }
+                Err(_) => {
+                    result = result.and_degrade_failure(ScriptResult::new_failure(
+                        ScriptError::invalid_trait(206, text_trait.clone()),
+                    ));
+                }
+            }
+        }
+
+        match text.structure {
+            TextDataStructure::Record(fields) => {
+                for field in fields {
+                    if data
+                        .read()
+                        .unwrap()
+                        .fields
+                        .iter()
+                        .any(|(name, _)| name.string == field.name.string)
+                    {
+                        result = result.and_degrade_failure(ScriptResult::new_failure(
+                            ScriptError::already_declared(207, field.name.clone()),
+                        ));
+                        continue;
+                    }
+
+                    if let Some(flow) = field.r#type.level_structure.first().filter(|flow| {
+                        flow.string.as_str() == "Block" || flow.string.as_str() == "Stream"
+                    }) {
+                        result = result.and_degrade_failure(ScriptResult::new_failure(
+                            ScriptError::flow_forbidden(208, flow.clone()),
+                        ));
+                        continue;
+                    }
+
+                    if let Some(r#type) = result.merge_degrade_failure(Type::new(
+                        Arc::clone(&data) as Arc<RwLock<dyn DeclarativeElement>>,
+                        field.r#type,
+                    )) {
+                        data.write()
+                            .unwrap()
+                            .fields
+                            .push((field.name, Arc::new(RwLock::new(r#type))));
+                    }
+                }
+            }
+            TextDataStructure::Enumeration(variants) => {
+                for variant in variants {
+                    let mut data = data.write().unwrap();
+                    if data.variants.contains(&variant.string) {
+                        result = result.and_degrade_failure(ScriptResult::new_failure(
+                            ScriptError::already_declared(209, variant),
+                        ));
+                    } else {
+                        data.variants.push(variant.string);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Gives the names of functions associated with this data.
+    pub fn functions_names(&self) -> Vec<String> {
+        match self.text.structure {
+            TextDataStructure::Record(_) => DataDescriptor::record_functions_names(
+                &self.name,
+                &self
+                    .fields
+                    .iter()
+                    .map(|(name, _)| name.string.clone())
+                    .collect::<Vec<_>>(),
+            ),
+            TextDataStructure::Enumeration(_) => {
+                DataDescriptor::enumeration_functions_names(&self.name, &self.variants)
+            }
+        }
+    }
+
+    /// Gives the identifiers of functions associated with this data, once its identifier is known.
+    pub fn functions_identifiers(&self, identifier: &Identifier) -> Vec<Identifier> {
+        match self.text.structure {
+            TextDataStructure::Record(_) => DataDescriptor::record_functions_identifiers(
+                identifier,
+                &self
+                    .fields
+                    .iter()
+                    .map(|(name, _)| name.string.clone())
+                    .collect::<Vec<_>>(),
+            ),
+            TextDataStructure::Enumeration(_) => {
+                DataDescriptor::enumeration_functions_identifiers(identifier, &self.variants)
+            }
+        }
+    }
+
+    pub fn make_descriptor(&self, collection: &Collection) -> ScriptResult<Arc<DataDescriptor>> {
+        let mut result = ScriptResult::new_success(());
+
+        let structure = match self.text.structure {
+            TextDataStructure::Record(_) => {
+                let mut fields = Vec::new();
+                for (name, r#type) in &self.fields {
+                    let r#type = r#type.read().unwrap();
+                    if let Some((described_type, _)) =
+                        result.merge_degrade_failure(r#type.make_descriptor(collection))
+                    {
+                        if described_type.contains_generic() {
+                            result = result.and_degrade_failure(ScriptResult::new_failure(
+                                ScriptError::generic_field_forbidden(210, r#type.text.name.clone()),
+                            ));
+                            continue;
+                        }
+
+                        for (_, data_trait) in &self.traits {
+                            if !described_type.implements(data_trait) {
+                                result = result.and_degrade_failure(ScriptResult::new_failure(
+                                    ScriptError::field_missing_trait(
+                                        211,
+                                        name.clone(),
+                                        data_trait.to_string(),
+                                    ),
+                                ));
+                            }
+                        }
+
+                        fields.push((name.string.clone(), described_type));
+                    }
+                }
+                DataStructure::Record(fields)
+            }
+            TextDataStructure::Enumeration(_) => DataStructure::Enumeration(self.variants.clone()),
+        };
+
+        result.and_then(|_| {
+            let mut descriptor =
+                DataDescriptor::new(self.identifier.as_ref().unwrap().clone(), structure);
+
+            if let Some(annotations) = self.text.annotations.as_ref() {
+                if let Some(doc) = &annotations.doc {
+                    descriptor.set_documentation(&doc.string);
+                }
+
+                for annotation in &annotations.annotations {
+                    if let Some((name, attribute)) = annotation.as_attribute() {
+                        descriptor.add_attribute(name, attribute);
+                    }
+                }
+            }
+
+            for (_, data_trait) in &self.traits {
+                descriptor.add_implementation(*data_trait);
+            }
+
+            let descriptor = descriptor.commit();
+
+            *self.descriptor.write().unwrap() = Some(descriptor.clone());
+
+            ScriptResult::new_success(descriptor)
+        })
+    }
+}
+
+impl DeclarativeElement for Data {
+    fn declarative_element(&'_ self) -> DeclarativeElementType<'_> {
+        DeclarativeElementType::Data(&self)
+    }
+
+    fn find_declared_parameter(&self, _name: &str) -> Option<&Arc<RwLock<DeclaredParameter>>> {
+        None
+    }
+}
+
+impl Node for Data {
+    fn children(&self) -> Vec<Arc<RwLock<dyn Node>>> {
+        self.fields
+            .iter()
+            .map(|(_, r#type)| Arc::clone(r#type) as Arc<RwLock<dyn Node>>)
+            .collect()
+    }
+
+    fn make_references(
+        &mut self,
+        path: &Path,
+        _versions: &HashMap<String, VersionReq>,
+    ) -> ScriptResult<()> {
+        self.identifier = path.to_identifier(&self.name);
+
+        ScriptResult::new_success(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::semantic::Tree;
+    use crate::text::Script as TextScript;
+    use melodium_common::descriptor::Version;
+
+    fn tree(text: &str) -> ScriptResult<Tree> {
+        TextScript::build(text).and_then(|text| Tree::new(text, Version::new(0, 1, 0)))
+    }
+
+    fn error_ids<T>(result: &ScriptResult<T>) -> Vec<u32> {
+        let mut ids: Vec<u32> = result.errors().iter().map(|error| error.id).collect();
+        if let Some(failure) = result.failure() {
+            ids.push(failure.id);
+        }
+        ids
+    }
+
+    fn descriptors(text: &str) -> ScriptResult<()> {
+        tree(text).and_then(|tree| {
+            let path = Path::new(Version::new(0, 1, 0), vec!["test".to_string()]);
+            let mut result = tree.make_references(&path, &HashMap::new());
+            let collection = Collection::new();
+            for data in tree.script.read().unwrap().data.values() {
+                result.merge_degrade_failure(data.read().unwrap().make_descriptor(&collection));
+            }
+            result
+        })
+    }
+
+    #[test]
+    fn test_record_and_enumeration_accepted() {
+        let tree = tree(
+            "data Point: PartialEquality { x: f64, y: f64 }\nenum Level: Display { Low, High }",
+        );
+        assert!(error_ids(&tree).is_empty());
+
+        let tree = tree.success().unwrap();
+        let script = tree.script.read().unwrap();
+        let point = script.find_data("Point").unwrap().read().unwrap();
+        assert_eq!(point.fields.len(), 2);
+        assert_eq!(
+            point.functions_names(),
+            vec!["|point", "|point_x", "|point_y"]
+        );
+        let level = script.find_data("Level").unwrap().read().unwrap();
+        assert_eq!(level.variants, vec!["Low", "High"]);
+        assert_eq!(level.functions_names(), vec!["|level_low", "|level_high"]);
+    }
+
+    #[test]
+    fn test_acronyms_kept_in_functions_names() {
+        let tree = tree("enum HTTPServer { TLSOnly, Plain }")
+            .to_success()
+            .unwrap();
+        let script = tree.script.read().unwrap();
+        let data = script.find_data("HTTPServer").unwrap().read().unwrap();
+        assert_eq!(
+            data.functions_names(),
+            vec!["|http_server_tls_only", "|http_server_plain"]
+        );
+    }
+
+    #[test]
+    fn test_data_names_already_used() {
+        assert_eq!(
+            error_ids(&tree("data Point { x: f64 }\nenum Point { A }")),
+            vec![203]
+        );
+    }
+
+    #[test]
+    fn test_fields_and_variants_declared_twice() {
+        assert_eq!(
+            error_ids(&tree(
+                "data Point { x: f64, x: f64 }\nenum Level { Low, Low }"
+            )),
+            vec![207, 209]
+        );
+    }
+
+    #[test]
+    fn test_flow_forbidden_in_field() {
+        assert_eq!(error_ids(&tree("data Point { x: Stream<f64> }")), vec![208]);
+    }
+
+    #[test]
+    fn test_traits_not_derivable_or_invalid() {
+        assert_eq!(
+            error_ids(&tree("data Point: Float + Unknown { x: f64 }")),
+            vec![205, 206]
+        );
+    }
+
+    #[test]
+    fn test_functions_names_collision() {
+        assert_eq!(
+            error_ids(&tree("data Point { x: f64 }\ndata PointX { y: f64 }")),
+            vec![241]
+        );
+        assert_eq!(
+            error_ids(&tree(
+                "enum Level { Low }\nfunction |level_low() -> u8 { 0 }"
+            )),
+            vec![241]
+        );
+    }
+
+    #[test]
+    fn test_field_missing_trait() {
+        assert_eq!(
+            error_ids(&descriptors("data Values: Hash { x: f64 }")),
+            vec![211]
+        );
+    }
+
+    #[test]
+    fn test_generic_field_forbidden() {
+        let result = descriptors("data Wrapper { x: T }");
+        assert_eq!(error_ids(&result), vec![210]);
+        assert!(matches!(
+            result.failure().unwrap().kind,
+            crate::error::ScriptErrorKind::GenericFieldForbidden { .. }
+        ));
+    }
+
+    #[test]
+    fn test_descriptors_made() {
+        assert!(error_ids(&descriptors("data Point: Display { x: f64, name: string }")).is_empty());
+    }
+}
@@ -1,7 +1,10 @@
 //! Module dedicated to DeclarativeElement trait definition.
 
 use super::common::Node;
+use super::constant::Constant;
+use super::data::Data;
 use super::declared_parameter::DeclaredParameter;
+use super::function::Function;
 use super::model::Model;
 use super::treatment::Treatment;
 use std::fmt::Debug;
@@ -21,6 +24,9 @@ pub trait DeclarativeElement: Node + Debug + Send + Sync {
 /// Enum listing possible declarative elements.
 #[derive(Debug)]
 pub enum DeclarativeElementType<'a> {
+    Constant(&'a Constant),
+    Data(&'a Data),
+    Function(&'a Function),
     Model(&'a Model),
     Treatment(&'a Treatment),
     None,
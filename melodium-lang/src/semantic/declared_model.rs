@@ -161,7 +161,7 @@ impl Node for DeclaredModel {
                     name: reference.name.clone(),
                     reference: Some(Arc::downgrade(model)),
                 });
-            } else if let Some(r#use) = borrowed_script.find_use(&reference.name) {
+            } else if let Some(r#use) = borrowed_script.refer_use(&reference.name) {
                 self.refers = RefersTo::Use(Reference {
                     name: reference.name.clone(),
                     reference: Some(Arc::downgrade(r#use)),
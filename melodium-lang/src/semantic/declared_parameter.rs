@@ -76,7 +76,25 @@ impl DeclaredParameter {
                     variability = Variability::Var;
                 }
             }
-            DeclarativeElementType::None => variability = Variability::Var,
+            DeclarativeElementType::Function(_) => {
+                if let Some(text_variability) = &text.variability {
+                    result = result.and_degrade_failure(ScriptResult::new_failure(
+                        ScriptError::variability_forbidden(228, text_variability.clone()),
+                    ));
+                }
+                if let Some(text_value) = &text.value {
+                    result = result.and_degrade_failure(ScriptResult::new_failure(
+                        ScriptError::default_forbidden(
+                            229,
+                            text_value.get_positionned_string().clone(),
+                        ),
+                    ));
+                }
+                variability = Variability::Var;
+            }
+            DeclarativeElementType::Constant(_)
+            | DeclarativeElementType::Data(_)
+            | DeclarativeElementType::None => variability = Variability::Var,
         }
 
         let value = if let Some(value) = text.value.as_ref().cloned() {
@@ -126,7 +144,7 @@ impl DeclaredParameter {
                     if let Some(datatype) = described_type.to_datatype(&HashMap::new()) {
                         val.read()
                             .unwrap()
-                            .make_executive_value(&datatype)
+                            .make_executive_value(&datatype, collection)
                             .and_then(|val| {
                                 ScriptResult::new_success((described_type, flow, Some(val)))
                             })
@@ -170,6 +188,12 @@ impl DeclaredParameter {
 
 impl Node for DeclaredParameter {
     fn children(&self) -> Vec<Arc<RwLock<dyn Node>>> {
-        vec![Arc::clone(&self.r#type) as Arc<RwLock<dyn Node>>]
+        let mut children = vec![Arc::clone(&self.r#type) as Arc<RwLock<dyn Node>>];
+
+        if let Some(value) = &self.value {
+            children.push(Arc::clone(value) as Arc<RwLock<dyn Node>>);
+        }
+
+        children
     }
 }
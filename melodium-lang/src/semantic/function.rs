@@ -0,0 +1,400 @@
+//! Module dedicated to Function semantic analysis.
+
+use super::common::Node;
+use super::declarative_element::{DeclarativeElement, DeclarativeElementType};
+use super::declared_parameter::DeclaredParameter;
+use super::r#type::{Type, TypeFlow};
+use super::script::Script;
+use super::value::Value;
+use super::DeclaredGeneric;
+use crate::error::ScriptError;
+use crate::path::Path;
+use crate::text::FunctionDeclaration as TextFunction;
+use crate::ScriptResult;
+use core::str::FromStr;
+use melodium_common::descriptor::{Collection, DataTrait, Generic, Identifier, VersionReq};
+use melodium_engine::descriptor::Function as FunctionDescriptor;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, Weak};
+
+/// Structure managing and describing semantic of a function declaration.
+///
+/// It owns the whole [text function declaration](TextFunction).
+#[derive(Debug)]
+pub struct Function {
+    pub text: TextFunction,
+
+    pub script: Weak<RwLock<Script>>,
+
+    pub name: String,
+
+    pub generics: Vec<Arc<RwLock<DeclaredGeneric>>>,
+    pub parameters: Vec<Arc<RwLock<DeclaredParameter>>>,
+    pub return_type: Option<Arc<RwLock<Type>>>,
+    pub body: Option<Arc<RwLock<Value>>>,
+
+    pub identifier: Option<Identifier>,
+    pub descriptor: RwLock<Option<Arc<FunctionDescriptor>>>,
+}
+
+impl Function {
+    /// Create a new semantic function, based on textual function declaration.
+    ///
+    /// * `script`: the parent script that "owns" this function.
+    /// * `text`: the textual function declaration.
+    ///
+    /// # Note
+    /// Only parent-child relationships are made at this step. Other references can be made afterwards using the [Node trait](Node).
+    ///
+    pub fn new(script: Arc<RwLock<Script>>, text: TextFunction) -> ScriptResult<Arc<RwLock<Self>>> {
+        let function = Arc::<RwLock<Self>>::new(RwLock::new(Self {
+            text: text.clone(),
+            script: Arc::downgrade(&script),
+            name: text.name.string.clone(),
+            generics: Vec::new(),
+            parameters: Vec::new(),
+            return_type: None,
+            body: None,
+            identifier: None,
+            descriptor: RwLock::new(None),
+        }));
+        let mut result = ScriptResult::new_success(Arc::clone(&function));
+
+        {
+            let borrowed_script = script.read().unwrap();
+
+            if borrowed_script.find_function(&text.name.string).is_some() {
+                result = result.and_degrade_failure(ScriptResult::new_failure(
+                    ScriptError::already_used_name(222, text.name.clone()),
+                ));
+            }
+
+            if borrowed_script.find_use(&text.name.string).is_some() {
+                result = result.and_degrade_failure(ScriptResult::new_failure(
+                    ScriptError::already_used_name(223, text.name.clone()),
+                ));
+            }
+        }
+
+        for g in &text.generics {
+            if let Some(generic) = result.merge_degrade_failure(DeclaredGeneric::new(
+                Arc::clone(&function) as Arc<RwLock<dyn DeclarativeElement>>,
+                g.clone(),
+            )) {
+                function.write().unwrap().generics.push(generic);
+            }
+        }
+
+        for p in text.parameters {
+            if let Some(declared_parameter) = result.merge_degrade_failure(DeclaredParameter::new(
+                Arc::clone(&function) as Arc<RwLock<dyn DeclarativeElement>>,
+                p,
+            )) {
+                function
+                    .write()
+                    .unwrap()
+                    .parameters
+                    .push(declared_parameter);
+            }
+        }
+
+        if let Some(return_type) = result.merge_degrade_failure(Type::new(
+            Arc::clone(&function) as Arc<RwLock<dyn DeclarativeElement>>,
+            text.return_type.clone(),
+        )) {
+            if return_type.flow != TypeFlow::Block {
+                result = result.and_degrade_failure(ScriptResult::new_failure(
+                    ScriptError::flow_forbidden(
+                        224,
+                        text.return_type
+                            .level_structure
+                            .first()
+                            .unwrap_or(&text.return_type.name)
+                            .clone(),
+                    ),
+                ));
+            } else {
+                function.write().unwrap().return_type = Some(Arc::new(RwLock::new(return_type)));
+            }
+        }
+
+        if let Some(body) = result.merge_degrade_failure(Value::new(
+            Arc::clone(&function) as Arc<RwLock<dyn DeclarativeElement>>,
+            text.body,
+        )) {
+            function.write().unwrap().body = Some(body);
+        }
+
+        result
+    }
+
+    pub fn make_descriptor(
+        &self,
+        collection: &Collection,
+    ) -> ScriptResult<Arc<FunctionDescriptor>> {
+        let mut result = ScriptResult::new_success(());
+
+        let return_type = if let Some((return_type, _)) = result.merge_degrade_failure(
+            self.return_type
+                .as_ref()
+                .unwrap()
+                .read()
+                .unwrap()
+                .make_descriptor(collection),
+        ) {
+            return_type
+        } else {
+            return result.and(ScriptResult::new_failure(ScriptError::no_descriptor(
+                225,
+                self.text.name.clone(),
+            )));
+        };
+
+        let mut descriptor = FunctionDescriptor::new_designed(
+            self.identifier.as_ref().unwrap().clone(),
+            return_type,
+        );
+
+        if let Some(annotations) = self.text.annotations.as_ref() {
+            if let Some(doc) = &annotations.doc {
+                descriptor.set_documentation(&doc.string);
+            }
+
+            for annotation in &annotations.annotations {
+                if let Some((name, attribute)) = annotation.as_attribute() {
+                    descriptor.add_attribute(name, attribute);
+                }
+            }
+        }
+
+        for rc_generic in &self.generics {
+            let borrowed_generic = rc_generic.read().unwrap();
+
+            let mut traits = Vec::new();
+            for trait_index in 0..borrowed_generic.traits.len() {
+                match DataTrait::from_str(&borrowed_generic.traits[trait_index]) {
+                    Ok(dt) => traits.push(dt),
+                    Err(_) => {
+                        return ScriptResult::new_failure(ScriptError::invalid_trait(
+                            226,
+                            borrowed_generic.text.traits[trait_index].clone(),
+                        ))
+                    }
+                }
+            }
+
+            descriptor.add_generic(Generic::new(borrowed_generic.name.clone(), traits));
+        }
+
+        for rc_parameter in &self.parameters {
+            let borrowed_parameter = rc_parameter.read().unwrap();
+            if let Some(parameter_descriptor) =
+                result.merge_degrade_failure(borrowed_parameter.make_descriptor(collection))
+            {
+                descriptor.add_parameter(parameter_descriptor);
+            }
+        }
+
+        result.and_then(|_| {
+            let descriptor = descriptor.commit();
+
+            *self.descriptor.write().unwrap() = Some(descriptor.clone());
+
+            ScriptResult::new_success(descriptor)
+        })
+    }
+
+    pub fn make_design(&self, collection: &Arc<Collection>) -> ScriptResult<()> {
+        let borrowed_descriptor = self.descriptor.read().unwrap();
+        let descriptor = if let Some(descriptor) = &*borrowed_descriptor {
+            descriptor
+        } else {
+            return ScriptResult::new_failure(ScriptError::no_descriptor(
+                227,
+                self.text.name.clone(),
+            ));
+        };
+
+        self.body
+            .as_ref()
+            .unwrap()
+            .read()
+            .unwrap()
+            .make_designed_value(
+                melodium_common::descriptor::Function::return_type(&**descriptor),
+                collection,
+            )
+            .and_then(|value| {
+                ScriptResult::from(descriptor.set_design(value, Some(self.text.name.into_ref())))
+            })
+    }
+}
+
+impl DeclarativeElement for Function {
+    fn declarative_element(&'_ self) -> DeclarativeElementType<'_> {
+        DeclarativeElementType::Function(&self)
+    }
+
+    /// Search for a parameter.
+    fn find_declared_parameter(&self, name: &str) -> Option<&Arc<RwLock<DeclaredParameter>>> {
+        self.parameters
+            .iter()
+            .find(|&p| p.read().unwrap().name == name)
+    }
+}
+
+impl Node for Function {
+    fn make_references(
+        &mut self,
+        path: &Path,
+        _versions: &HashMap<String, VersionReq>,
+    ) -> ScriptResult<()> {
+        self.identifier = path.to_identifier(&self.name);
+
+        ScriptResult::new_success(())
+    }
+
+    fn children(&self) -> Vec<Arc<RwLock<dyn Node>>> {
+        let mut children: Vec<Arc<RwLock<dyn Node>>> = Vec::new();
+
+        self.generics
+            .iter()
+            .for_each(|g| children.push(Arc::clone(&g) as Arc<RwLock<dyn Node>>));
+        self.parameters
+            .iter()
+            .for_each(|p| children.push(Arc::clone(&p) as Arc<RwLock<dyn Node>>));
+        self.return_type
+            .iter()
+            .for_each(|t| children.push(Arc::clone(&t) as Arc<RwLock<dyn Node>>));
+        self.body
+            .iter()
+            .for_each(|b| children.push(Arc::clone(&b) as Arc<RwLock<dyn Node>>));
+
+        children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::error::ScriptErrorKind;
+    use crate::semantic::Tree;
+    use crate::text::Script as TextScript;
+    use melodium_common::descriptor::{DataType, Entry, Function as _, Version};
+    use melodium_common::executive::Value as ExecutiveValue;
+
+    fn tree(text: &str) -> ScriptResult<Tree> {
+        TextScript::build(text).and_then(|text| Tree::new(text, Version::new(0, 1, 0)))
+    }
+
+    fn error_ids<T>(result: &ScriptResult<T>) -> Vec<u32> {
+        let mut ids: Vec<u32> = result.errors().iter().map(|error| error.id).collect();
+        if let Some(failure) = result.failure() {
+            ids.push(failure.id);
+        }
+        ids
+    }
+
+    fn logic_ids<T>(result: &ScriptResult<T>) -> Vec<u32> {
+        result
+            .errors()
+            .iter()
+            .chain(result.failure())
+            .filter_map(|error| match &error.kind {
+                ScriptErrorKind::Logic { error } => Some(error.id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn designs(text: &str) -> ScriptResult<HashMap<String, Arc<FunctionDescriptor>>> {
+        tree(text).and_then(|tree| {
+            let path = Path::new(Version::new(0, 1, 0), vec!["test".to_string()]);
+            let mut result = tree.make_references(&path, &HashMap::new());
+            let mut collection = Collection::new();
+            let mut descriptors = HashMap::new();
+            for (name, function) in &tree.script.read().unwrap().functions {
+                if let Some(descriptor) = result
+                    .merge_degrade_failure(function.read().unwrap().make_descriptor(&collection))
+                {
+                    collection.insert(Entry::Function(descriptor.clone()));
+                    descriptors.insert(name.clone(), descriptor);
+                }
+            }
+            let collection = Arc::new(collection);
+            for function in tree.script.read().unwrap().functions.values() {
+                result.merge_degrade_failure(function.read().unwrap().make_design(&collection));
+            }
+            result.and(ScriptResult::new_success(descriptors))
+        })
+    }
+
+    #[test]
+    fn test_designed_functions_evaluated() {
+        let descriptors = designs(
+            "function |same<T>(value: T) -> T { value }\nfunction |answer() -> u32 { |same<u32>(42) }",
+        );
+        assert!(error_ids(&descriptors).is_empty());
+
+        let descriptors = descriptors.success().unwrap();
+        let answer = descriptors.get("|answer").unwrap();
+        assert_eq!(
+            answer.function()(HashMap::new(), Vec::new()),
+            ExecutiveValue::U32(42)
+        );
+        let same = descriptors.get("|same").unwrap();
+        assert_eq!(
+            same.function()(
+                HashMap::from([("T".to_string(), DataType::String)]),
+                vec![ExecutiveValue::String("melodium".to_string())]
+            ),
+            ExecutiveValue::String("melodium".to_string())
+        );
+    }
+
+    #[test]
+    fn test_function_name_already_used() {
+        let tree = tree("function |answer() -> u32 { 42 }\nfunction |answer() -> u32 { 43 }");
+        assert_eq!(error_ids(&tree), vec![222]);
+    }
+
+    #[test]
+    fn test_function_parameter_name_already_used() {
+        let tree = tree("function |add(a: u32, a: u32) -> u32 { a }");
+        assert_eq!(error_ids(&tree), vec![134]);
+    }
+
+    #[test]
+    fn test_function_name_already_imported() {
+        let tree = tree("use std/conv::|to_string\nfunction |to_string() -> string { \"\" }");
+        assert_eq!(error_ids(&tree), vec![223]);
+    }
+
+    #[test]
+    fn test_function_return_flow_forbidden() {
+        let tree = tree("function |answer() -> Stream<u32> { 42 }");
+        assert_eq!(error_ids(&tree), vec![224]);
+    }
+
+    #[test]
+    fn test_function_invalid_trait() {
+        let result = designs("function |same<T: Unknown>(value: T) -> T { value }");
+        assert!(error_ids(&result).contains(&226));
+    }
+
+    #[test]
+    fn test_function_calling_itself() {
+        let result = designs("function |loop(value: u32) -> u32 { |loop(value) }");
+        assert_eq!(logic_ids(&result), vec![245]);
+    }
+
+    #[test]
+    fn test_functions_calling_each_other() {
+        let result = designs(
+            "function |ping(value: u32) -> u32 { |pong(value) }\nfunction |pong(value: u32) -> u32 { |ping(value) }",
+        );
+        assert!(!logic_ids(&result).is_empty());
+        assert!(logic_ids(&result).iter().all(|id| *id == 245));
+    }
+}
@@ -3,7 +3,9 @@
 use super::assigned_generic::AssignedGeneric;
 use super::common::Node;
 use super::common::Reference;
+use super::data::Data;
 use super::declarative_element::{DeclarativeElement, DeclarativeElementType};
+use super::function::Function;
 use super::r#use::Use;
 use super::value::Value;
 use crate::error::ScriptError;
@@ -19,6 +21,8 @@ use std::sync::{Arc, RwLock, Weak};
 pub enum RefersTo {
     Unknown(Reference<()>),
     Use(Reference<Use>),
+    Data(Reference<Data>),
+    Function(Reference<Function>),
 }
 
 /// Structure managing and describing semantic of a function call.
@@ -80,7 +84,7 @@ impl FunctionCall {
 impl Node for FunctionCall {
     fn make_references(
         &mut self,
-        _path: &Path,
+        path: &Path,
         _versions: &HashMap<String, VersionReq>,
     ) -> ScriptResult<()> {
         if let RefersTo::Unknown(reference) = &self.r#type {
@@ -92,13 +96,16 @@ impl Node for FunctionCall {
                 .unwrap()
                 .declarative_element()
             {
+                DeclarativeElementType::Constant(c) => c.script.upgrade().unwrap(),
+                DeclarativeElementType::Data(d) => d.script.upgrade().unwrap(),
+                DeclarativeElementType::Function(f) => f.script.upgrade().unwrap(),
                 DeclarativeElementType::Model(m) => m.script.upgrade().unwrap(),
                 DeclarativeElementType::Treatment(t) => t.script.upgrade().unwrap(),
                 DeclarativeElementType::None => return ScriptResult::new_success(()),
             };
             let borrowed_script = rc_script.read().unwrap();
 
-            if let Some(r#use) = borrowed_script.find_use(&reference.name) {
+            if let Some(r#use) = borrowed_script.refer_use(&reference.name) {
                 self.type_identifier = r#use.read().unwrap().identifier.as_ref().cloned();
 
                 self.r#type = RefersTo::Use(Reference {
@@ -106,8 +113,36 @@ impl Node for FunctionCall {
                     reference: Some(Arc::downgrade(r#use)),
                 });
             }
-            // Add here when and if functions can be scripted to found them in local script file.
-            else {
+            // Functions associated with data declared in the same script.
+            else if let Some((data, identifier)) =
+                borrowed_script.data.values().find_map(|data| {
+                    let borrowed_data = data.read().unwrap();
+                    path.to_identifier(&borrowed_data.name)
+                        .map(|identifier| borrowed_data.functions_identifiers(&identifier))
+                        .and_then(|identifiers| {
+                            identifiers
+                                .into_iter()
+                                .find(|identifier| identifier.name() == reference.name)
+                        })
+                        .map(|identifier| (data, identifier))
+                })
+            {
+                self.type_identifier = Some((&identifier).into());
+
+                self.r#type = RefersTo::Data(Reference {
+                    name: reference.name.clone(),
+                    reference: Some(Arc::downgrade(data)),
+                });
+            }
+            // Functions declared in the same script.
+            else if let Some(function) = borrowed_script.find_function(&reference.name) {
+                self.type_identifier = path.to_identifier_requirement(&reference.name);
+
+                self.r#type = RefersTo::Function(Reference {
+                    name: reference.name.clone(),
+                    reference: Some(Arc::downgrade(function)),
+                });
+            } else {
                 return ScriptResult::new_failure(ScriptError::unimported_element(
                     133,
                     self.text.name.clone(),
@@ -9,10 +9,13 @@ mod assigned_model;
 mod assigned_parameter;
 mod common;
 mod connection;
+mod constant;
+mod data;
 mod declarative_element;
 mod declared_generic;
 mod declared_model;
 mod declared_parameter;
+mod function;
 mod function_call;
 mod input;
 mod model;
@@ -33,10 +36,13 @@ pub use assigned_model::AssignedModel;
 pub use assigned_parameter::AssignedParameter;
 pub use common::{Node, Reference, Tree};
 pub use connection::Connection;
+pub use constant::Constant;
+pub use data::Data;
 pub use declarative_element::{DeclarativeElement, DeclarativeElementType, NoneDeclarativeElement};
 pub use declared_generic::DeclaredGeneric;
 pub use declared_model::DeclaredModel;
 pub use declared_parameter::DeclaredParameter;
+pub use function::Function;
 pub use function_call::FunctionCall;
 pub use input::Input;
 pub use model::Model;
@@ -313,7 +313,7 @@ impl Node for Model {
                     name: r#type.name.clone(),
                     reference: Some(Arc::downgrade(model)),
                 });
-            } else if let Some(r#use) = borrowed_script.find_use(&r#type.name) {
+            } else if let Some(r#use) = borrowed_script.refer_use(&r#type.name) {
                 self.r#type = RefersTo::Use(Reference {
                     name: r#type.name.clone(),
                     reference: Some(Arc::downgrade(r#use)),
@@ -155,7 +155,7 @@ impl Node for ModelInstanciation {
             let rc_script = borrowed_treatment.script.upgrade().unwrap();
             let borrowed_script = rc_script.read().unwrap();
 
-            let r#use = borrowed_script.find_use(&reference.name);
+            let r#use = borrowed_script.refer_use(&reference.name);
             if r#use.is_some() {
                 let r#use = r#use.unwrap();
 
@@ -1,26 +1,33 @@
 //! Module dedicated to Script semantic analysis.
 
 use melodium_common::descriptor::Version;
+use melodium_engine::lint::{Lint, LintWarning};
 
 use super::common::Node;
+use super::constant::Constant;
+use super::data::Data;
+use super::function::Function;
 use super::model::Model;
 use super::r#use::Use;
 use super::treatment::Treatment;
 use crate::text::Script as TextScript;
-use crate::ScriptResult;
+use crate::{ScriptError, ScriptResult};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 /// Structure managing and describing semantic of a script.
 ///
 /// Matches the concept of a script file content.
-/// It owns the whole [text script](TextScript), as well as references to semantical contained [Uses](Use), [Models](Model), and [Treatments](Treatment).
+/// It owns the whole [text script](TextScript), as well as references to semantical contained [Uses](Use), [Constants](Constant), [Data], [Functions](Function), [Models](Model), and [Treatments](Treatment).
 /// There is a logical coherence equivalent to the one expressed in the text script, but this coherence, as in the text, may be _incomplete_ or _broken_.
 #[derive(Debug)]
 pub struct Script {
     pub text: TextScript,
 
     pub uses: Vec<Arc<RwLock<Use>>>,
+    pub constants: HashMap<String, Arc<RwLock<Constant>>>,
+    pub data: HashMap<String, Arc<RwLock<Data>>>,
+    pub functions: HashMap<String, Arc<RwLock<Function>>>,
     pub models: HashMap<String, Arc<RwLock<Model>>>,
     pub treatments: HashMap<String, Arc<RwLock<Treatment>>>,
 }
@@ -38,6 +45,9 @@ impl Script {
         let script = Arc::<RwLock<Self>>::new(RwLock::new(Self {
             text: text.clone(),
             uses: Vec::new(),
+            constants: HashMap::new(),
+            data: HashMap::new(),
+            functions: HashMap::new(),
             models: HashMap::new(),
             treatments: HashMap::new(),
         }));
@@ -53,6 +63,63 @@ impl Script {
             }
         }
 
+        for c in text.constants {
+            if let Some(constant) =
+                result.merge_degrade_failure(Constant::new(Arc::clone(&script), c.clone()))
+            {
+                let name = constant.read().unwrap().name.clone();
+                script.write().unwrap().constants.insert(name, constant);
+            }
+        }
+
+        for d in text.data {
+            if let Some(data) =
+                result.merge_degrade_failure(Data::new(Arc::clone(&script), d.clone()))
+            {
+                let name = data.read().unwrap().name.clone();
+                script.write().unwrap().data.insert(name, data);
+            }
+        }
+
+        for f in text.functions {
+            if let Some(function) =
+                result.merge_degrade_failure(Function::new(Arc::clone(&script), f.clone()))
+            {
+                let name = function.read().unwrap().name.clone();
+                script.write().unwrap().functions.insert(name, function);
+            }
+        }
+
+        // Functions associated with data must not share names with any other function.
+        {
+            let borrowed_script = script.read().unwrap();
+            let mut names: Vec<String> = borrowed_script.functions.keys().cloned().collect();
+            let mut checked_data = Vec::new();
+            for text_data in &borrowed_script.text.data {
+                if checked_data.contains(&&text_data.name.string) {
+                    continue;
+                }
+                checked_data.push(&text_data.name.string);
+
+                if let Some(data) = borrowed_script.find_data(&text_data.name.string) {
+                    let data = data.read().unwrap();
+                    for name in data.functions_names() {
+                        if names.contains(&name) {
+                            result = result.and_degrade_failure(ScriptResult::new_failure(
+                                ScriptError::function_name_collision(
+                                    241,
+                                    data.text.name.clone(),
+                                    name,
+                                ),
+                            ));
+                        } else {
+                            names.push(name);
+                        }
+                    }
+                }
+            }
+        }
+
         for m in text.models {
             if let Some(model) =
                 result.merge_degrade_failure(Model::new(Arc::clone(&script), m.clone()))
@@ -83,6 +150,73 @@ impl Script {
             .find(|&u| u.read().unwrap().r#as == element_as)
     }
 
+    /// Search for an element imported through a use, marking that use as referenced.
+    /// This search using the `as` property.
+    ///
+    pub fn refer_use(&self, element_as: &str) -> Option<&Arc<RwLock<Use>>> {
+        let r#use = self.find_use(element_as);
+        if let Some(r#use) = r#use {
+            r#use.write().unwrap().referenced = true;
+        }
+        r#use
+    }
+
+    /// Gives lint warnings about the script, designated as `name` in warnings.
+    pub fn lint(&self, name: &str) -> Vec<LintWarning> {
+        self.uses
+            .iter()
+            .filter_map(|r#use| {
+                let r#use = r#use.read().unwrap();
+                let allowed = r#use
+                    .text
+                    .annotations
+                    .as_ref()
+                    .map(|annotations| {
+                        Lint::UnusedUse.is_allowed_by(
+                            &annotations
+                                .annotations
+                                .iter()
+                                .filter_map(|annotation| annotation.as_attribute())
+                                .collect(),
+                        )
+                    })
+                    .unwrap_or(false);
+
+                if r#use.referenced || allowed {
+                    None
+                } else {
+                    Some(LintWarning::unused_use(
+                        name.to_string(),
+                        r#use.r#as.clone(),
+                        Some(
+                            r#use
+                                .text
+                                .r#as
+                                .as_ref()
+                                .unwrap_or(&r#use.text.element)
+                                .into_ref(),
+                        ),
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    /// Search for a constant.
+    pub fn find_constant(&self, name: &str) -> Option<&Arc<RwLock<Constant>>> {
+        self.constants.get(name)
+    }
+
+    /// Search for a data.
+    pub fn find_data(&self, name: &str) -> Option<&Arc<RwLock<Data>>> {
+        self.data.get(name)
+    }
+
+    /// Search for a function.
+    pub fn find_function(&self, name: &str) -> Option<&Arc<RwLock<Function>>> {
+        self.functions.get(name)
+    }
+
     /// Search for a model.
     pub fn find_model(&self, name: &str) -> Option<&Arc<RwLock<Model>>> {
         self.models.get(name)
@@ -101,6 +235,15 @@ impl Node for Script {
         self.uses
             .iter()
             .for_each(|u| children.push(Arc::clone(&u) as Arc<RwLock<dyn Node>>));
+        self.constants
+            .iter()
+            .for_each(|(_, c)| children.push(Arc::clone(&c) as Arc<RwLock<dyn Node>>));
+        self.data
+            .iter()
+            .for_each(|(_, d)| children.push(Arc::clone(&d) as Arc<RwLock<dyn Node>>));
+        self.functions
+            .iter()
+            .for_each(|(_, f)| children.push(Arc::clone(&f) as Arc<RwLock<dyn Node>>));
         self.models
             .iter()
             .for_each(|(_, m)| children.push(Arc::clone(&m) as Arc<RwLock<dyn Node>>));
@@ -136,6 +136,16 @@ impl TreatmentInstanciation {
 
         let treatment_desc = designer.descriptor();
 
+        if let Some(annotations) = &self.text.annotations {
+            for (name, attribute) in annotations
+                .annotations
+                .iter()
+                .filter_map(|annotation| annotation.as_attribute())
+            {
+                designer.add_attribute(name, attribute);
+            }
+        }
+
         for i in 0..treatment_desc.generics().len() {
             let desc_generic = &treatment_desc.generics()[i];
 
@@ -235,7 +245,7 @@ impl Node for TreatmentInstanciation {
             let rc_script = borrowed_treatment.script.upgrade().unwrap();
             let borrowed_script = rc_script.read().unwrap();
 
-            let r#use = borrowed_script.find_use(&reference.name);
+            let r#use = borrowed_script.refer_use(&reference.name);
             if r#use.is_some() {
                 let r#use = r#use.unwrap();
 
@@ -1,6 +1,6 @@
 //! Module for Type identification and structure semantic analysis.
 
-use super::{DeclarativeElement, DeclarativeElementType, Node, Reference, Use};
+use super::{Data, DeclarativeElement, DeclarativeElementType, Node, Reference, Use};
 use crate::text::PositionnedString;
 use crate::{text::Type as TextType, ScriptResult};
 use crate::{Path, ScriptError};
@@ -41,6 +41,8 @@ enum RefersTo {
     Implicit(Reference<()>),
     // Designates data type imported through `use`
     Use(Reference<Use>),
+    // Designates data type declared in the same script
+    Data(Reference<Data>),
 }
 
 /// Enum for type identification.
@@ -165,6 +167,20 @@ impl TypeContent {
                         }
                         _ => None,
                     }),
+                RefersTo::Data(local) => local
+                    .reference
+                    .as_ref()
+                    .and_then(|weak| {
+                        weak.upgrade().and_then(|arc| {
+                            arc.read().ok().and_then(|data| data.identifier.clone())
+                        })
+                    })
+                    .and_then(|identifier| match collection.get(&(&identifier).into()) {
+                        Some(Entry::Data(data)) => {
+                            Some(DescribedTypeDescriptor::Data(Box::new(Arc::clone(data))))
+                        }
+                        _ => None,
+                    }),
             },
         }
     }
@@ -228,6 +244,7 @@ impl fmt::Display for TypeContent {
                 match refer {
                     RefersTo::Implicit(implicit) => &implicit.name,
                     RefersTo::Use(external) => &external.name,
+                    RefersTo::Data(local) => &local.name,
                 }
             ),
         }
@@ -249,13 +266,24 @@ impl Node for TypeContent {
                     .unwrap()
                     .declarative_element()
                 {
+                    DeclarativeElementType::Constant(c) => c.script.upgrade().unwrap(),
+                    DeclarativeElementType::Data(d) => d.script.upgrade().unwrap(),
+                    DeclarativeElementType::Function(f) => f.script.upgrade().unwrap(),
                     DeclarativeElementType::Model(m) => m.script.upgrade().unwrap(),
                     DeclarativeElementType::Treatment(t) => t.script.upgrade().unwrap(),
                     DeclarativeElementType::None => return ScriptResult::new_success(()),
                 };
                 let borrowed_script = rc_script.read().unwrap();
 
-                if let Some(r#use) = borrowed_script.find_use(&reference.name) {
+                if let Some(data) = borrowed_script.find_data(&reference.name) {
+                    let reference = RefersTo::Data(Reference {
+                        name: reference.name.clone(),
+                        reference: Some(Arc::downgrade(data)),
+                    });
+
+                    *self = TypeContent::Other((scope.clone(), reference));
+                    ScriptResult::new_success(())
+                } else if let Some(r#use) = borrowed_script.refer_use(&reference.name) {
                     let reference = RefersTo::Use(Reference {
                         name: reference.name.clone(),
                         reference: Some(Arc::downgrade(r#use)),
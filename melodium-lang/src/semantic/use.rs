@@ -23,6 +23,9 @@ pub struct Use {
     pub r#as: String,
 
     pub identifier: Option<IdentifierRequirement>,
+
+    /// Tells if the used element is referenced somewhere in the script.
+    pub referenced: bool,
 }
 
 impl Use {
@@ -72,6 +75,7 @@ impl Use {
                 r#as: r#as.string.clone(),
                 text,
                 identifier: None,
+                referenced: false,
             })))
         })
     }
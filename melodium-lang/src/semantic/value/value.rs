@@ -13,10 +13,11 @@ use crate::ScriptResult;
 use descape::UnescapeExt;
 use melodium_common::descriptor::Collection;
 use melodium_common::descriptor::DescribedType;
+use melodium_common::descriptor::IdentifierRequirement;
 use melodium_common::descriptor::VersionReq;
 use melodium_common::descriptor::{DataType, Entry};
 use melodium_common::executive::Value as ExecutiveValue;
-use melodium_engine::designer::{Parameter as ParameterDesigner, Value as ValueDesigner};
+use melodium_engine::designer::Value as ValueDesigner;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock, Weak};
 
@@ -220,6 +221,9 @@ impl Value {
             ValueContent::Byte(b) => {
                 content = ValueContent::Byte(*b);
             }
+            ValueContent::Constant(identifier) => {
+                content = ValueContent::Constant(identifier.clone());
+            }
             ValueContent::Name(n) => {
                 let param = borrowed_host.find_declared_parameter(&n.name);
                 if param.is_some() {
@@ -227,6 +231,9 @@ impl Value {
                         name: n.name.clone(),
                         reference: Some(Arc::downgrade(&param.unwrap())),
                     });
+                } else if let Some(identifier) = Self::find_constant(&*borrowed_host, &n.name, path)
+                {
+                    content = ValueContent::Constant(identifier);
                 } else {
                     let ps = match &self.text {
                         TextValue::Name(ps) => ps.clone(),
@@ -290,8 +297,37 @@ impl Value {
         ScriptResult::new_success(content)
     }
 
-    pub fn make_executive_value(&self, datatype: &DataType) -> ScriptResult<ExecutiveValue> {
-        match self.content.make_executive_value(datatype) {
+    /// Search for a constant declared in the script of the host, or imported through a use.
+    fn find_constant(
+        host: &dyn DeclarativeElement,
+        name: &str,
+        path: &Path,
+    ) -> Option<IdentifierRequirement> {
+        let rc_script = match host.declarative_element() {
+            DeclarativeElementType::Constant(c) => c.script.upgrade().unwrap(),
+            DeclarativeElementType::Data(d) => d.script.upgrade().unwrap(),
+            DeclarativeElementType::Function(f) => f.script.upgrade().unwrap(),
+            DeclarativeElementType::Model(m) => m.script.upgrade().unwrap(),
+            DeclarativeElementType::Treatment(t) => t.script.upgrade().unwrap(),
+            DeclarativeElementType::None => return None,
+        };
+        let borrowed_script = rc_script.read().unwrap();
+
+        if borrowed_script.find_constant(name).is_some() {
+            path.to_identifier_requirement(name)
+        } else if let Some(r#use) = borrowed_script.refer_use(name) {
+            r#use.read().unwrap().identifier.clone()
+        } else {
+            None
+        }
+    }
+
+    pub fn make_executive_value(
+        &self,
+        datatype: &DataType,
+        collection: &Collection,
+    ) -> ScriptResult<ExecutiveValue> {
+        match Self::build_executive_value(&self.content, datatype, collection) {
             Ok(value) => ScriptResult::new_success(value),
             Err(err) => ScriptResult::new_failure(ScriptError::executive_restitution_failed(
                 154,
@@ -301,15 +337,56 @@ impl Value {
         }
     }
 
+    /// Builds executive value, resolving constants through the collection.
+    fn build_executive_value(
+        content: &ValueContent,
+        datatype: &DataType,
+        collection: &Collection,
+    ) -> Result<ExecutiveValue, String> {
+        match (content, datatype) {
+            (ValueContent::Constant(identifier), datatype) => match collection.get(identifier) {
+                Some(Entry::Constant(constant))
+                    if constant
+                        .described_type()
+                        .to_datatype(&HashMap::new())
+                        .as_ref()
+                        == Some(datatype) =>
+                {
+                    Ok(constant.value().clone())
+                }
+                Some(Entry::Constant(constant)) => Err(format!(
+                    "{datatype} value expected, constant '{}' is {}.",
+                    identifier.name(),
+                    constant.described_type()
+                )),
+                _ => Err(format!(
+                    "Constant '{}' is not available.",
+                    identifier.name()
+                )),
+            },
+            (ValueContent::Array(vec), DataType::Vec(inner_type)) => {
+                let mut arr: Vec<ExecutiveValue> = Vec::with_capacity(vec.len());
+                for val in vec {
+                    arr.push(Self::build_executive_value(val, inner_type, collection)?);
+                }
+                Ok(ExecutiveValue::Vec(arr))
+            }
+            (content, DataType::Option(inner_type)) if !matches!(content, ValueContent::Void) => {
+                Ok(ExecutiveValue::Option(Some(Box::new(
+                    Self::build_executive_value(content, inner_type, collection)?,
+                ))))
+            }
+            (content, datatype) => content.make_executive_value(datatype),
+        }
+    }
+
     pub fn make_designed_value(
         &self,
-        designer: &ParameterDesigner,
         described_type: &DescribedType,
         collection: &Collection,
     ) -> ScriptResult<ValueDesigner> {
         Self::build_designed_value(
             &self.content,
-            designer,
             described_type,
             collection,
             self.text.get_positionned_string(),
@@ -318,7 +395,6 @@ impl Value {
 
     fn build_designed_value(
         content: &ValueContent,
-        designer: &ParameterDesigner,
         described_type: &DescribedType,
         collection: &Collection,
         positioned_string: &PositionnedString,
@@ -327,28 +403,30 @@ impl Value {
             ValueContent::Name(decl_param) => {
                 ScriptResult::new_success(ValueDesigner::Variable(decl_param.name.clone()))
             }
+            ValueContent::Constant(identifier) => {
+                if let Some(Entry::Constant(constant)) = collection.get(identifier) {
+                    ScriptResult::new_success(ValueDesigner::Constant(Arc::clone(constant)))
+                } else {
+                    ScriptResult::new_failure(ScriptError::unimported_element(
+                        240,
+                        positioned_string.clone(),
+                    ))
+                }
+            }
             ValueContent::ContextReference((context, name)) => {
-                if let Some(Entry::Context(context)) = designer
-                    .scope()
-                    .upgrade()
-                    .unwrap()
-                    .read()
-                    .unwrap()
-                    .collection()
-                    .get(
-                        &context
-                            .reference
-                            .as_ref()
-                            .unwrap()
-                            .upgrade()
-                            .unwrap()
-                            .read()
-                            .unwrap()
-                            .type_identifier
-                            .as_ref()
-                            .unwrap(),
-                    )
-                {
+                if let Some(Entry::Context(context)) = collection.get(
+                    &context
+                        .reference
+                        .as_ref()
+                        .unwrap()
+                        .upgrade()
+                        .unwrap()
+                        .read()
+                        .unwrap()
+                        .type_identifier
+                        .as_ref()
+                        .unwrap(),
+                ) {
                     ScriptResult::new_success(ValueDesigner::Context(
                         Arc::clone(context),
                         name.clone(),
@@ -363,14 +441,8 @@ impl Value {
             ValueContent::Function(func) => {
                 let borrowed_func = func.read().unwrap();
 
-                if let Some(Entry::Function(func_descriptor)) = designer
-                    .scope()
-                    .upgrade()
-                    .unwrap()
-                    .read()
-                    .unwrap()
-                    .collection()
-                    .get(&borrowed_func.type_identifier.as_ref().unwrap())
+                if let Some(Entry::Function(func_descriptor)) =
+                    collection.get(&borrowed_func.type_identifier.as_ref().unwrap())
                 {
                     let mut result = ScriptResult::new_success(());
                     let mut generics = HashMap::new();
@@ -408,13 +480,9 @@ impl Value {
                                 .as_defined(&generics)
                                 .unwrap_or_else(|| desc_param.described_type().clone());
 
-                            if let Some(param) =
-                                result.merge_degrade_failure(borrowed_param.make_designed_value(
-                                    designer,
-                                    &described_type,
-                                    collection,
-                                ))
-                            {
+                            if let Some(param) = result.merge_degrade_failure(
+                                borrowed_param.make_designed_value(&described_type, collection),
+                            ) {
                                 params.push(param);
                             }
                         } else {
@@ -448,7 +516,6 @@ impl Value {
                     for val in array {
                         let val = Self::build_designed_value(
                             val,
-                            designer,
                             &inner_type,
                             collection,
                             positioned_string,
@@ -470,13 +537,7 @@ impl Value {
                     }
                     ScriptResult::new_success(ValueDesigner::Array(vector))
                 } else if let DescribedType::Option(inner_type) = described_type {
-                    Self::build_designed_value(
-                        content,
-                        designer,
-                        inner_type,
-                        collection,
-                        positioned_string,
-                    )
+                    Self::build_designed_value(content, inner_type, collection, positioned_string)
                 } else {
                     ScriptResult::new_failure(ScriptError::invalid_type(
                         182,
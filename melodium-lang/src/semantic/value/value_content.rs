@@ -2,7 +2,7 @@ use super::super::common::Reference;
 use super::super::declared_parameter::DeclaredParameter;
 use super::super::function_call::FunctionCall;
 use super::super::requirement::Requirement;
-use melodium_common::descriptor::DataType;
+use melodium_common::descriptor::{DataType, IdentifierRequirement};
 use melodium_common::executive::Value as ExecutiveValue;
 use std::convert::TryFrom;
 use std::sync::{Arc, RwLock};
@@ -22,6 +22,8 @@ pub enum ValueContent {
     Array(Vec<ValueContent>),
     /// Named value, referring to a parameter of the hosting treatment.
     Name(Reference<DeclaredParameter>),
+    /// Constant, referring to a constant declared in the script or imported through a use.
+    Constant(IdentifierRequirement),
     /// Context reference, referring to a requirement of the hosting treatment, and an inner element.
     ContextReference((Reference<Requirement>, String)),
     /// Function, refering to a function call.
@@ -15,12 +15,14 @@ pub struct Annotation {
 impl Annotation {
     pub fn as_attribute(&self) -> Option<(String, Attribute)> {
         lazy_static! {
-            static ref REGEX_CONTEXT: Regex = Regex::new(r"^#\[(\w+)\((.*)\)\]").unwrap();
+            static ref REGEX_CONTEXT: Regex = Regex::new(r"^#\[(\w+)(?:\((.*)\))?\]").unwrap();
         }
         if let Some(cap) = REGEX_CONTEXT.captures(&self.text.string) {
             Some((
                 cap.get(1).unwrap().as_str().to_string(),
-                cap.get(2).unwrap().as_str().to_string(),
+                cap.get(2)
+                    .map(|attribute| attribute.as_str().to_string())
+                    .unwrap_or_default(),
             ))
         } else {
             None
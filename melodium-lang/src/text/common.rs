@@ -291,3 +291,66 @@ pub fn parse_generics(
 
     Ok(generics)
 }
+
+/// Keywords that can start a declaration at script level.
+pub const DECLARATIONS: &[&str] = &[
+    "use",
+    "const",
+    "data",
+    "enum",
+    "function",
+    "model",
+    "treatment",
+];
+
+/// Tells if word is a declaration keyword placed at the very beginning of a line.
+///
+/// Such words are considered as resynchronisation points when recovering from syntax errors.
+pub fn is_declaration_start(word: &Word) -> bool {
+    word.kind == Some(Kind::Name)
+        && word.position.line_position == 0
+        && DECLARATIONS.contains(&word.text.as_str())
+}
+
+/// Skip words until the next declaration start, after a syntax error occured.
+///
+/// * `iter`: Iterator over words list, after return next() is the declaration keyword (or end of script).
+pub fn skip_to_declaration(iter: &mut Windows<Word>) {
+    while let Some(w) = iter.clone().next().map(|s| &s[0]) {
+        if is_declaration_start(w) {
+            break;
+        }
+        iter.next();
+    }
+}
+
+/// Skip words of an element within a treatment body, after a syntax error occured.
+///
+/// Skipping stops before the first name at same nesting level on a following line, before the closing brace ending the body,
+/// or before any declaration start.
+///
+/// * `iter`: Iterator over words list, next() being expected to be the first word of the erroneous element.
+pub fn skip_to_body_element(iter: &mut Windows<Word>) {
+    let line = match iter.next().map(|s| &s[0]) {
+        Some(w) => w.position.line_number,
+        None => return,
+    };
+    let mut depth: usize = 0;
+
+    while let Some(w) = iter.clone().next().map(|s| &s[0]) {
+        if is_declaration_start(w) {
+            break;
+        }
+        match w.kind {
+            Some(Kind::OpeningParenthesis)
+            | Some(Kind::OpeningBracket)
+            | Some(Kind::OpeningBrace) => depth += 1,
+            Some(Kind::ClosingParenthesis) | Some(Kind::ClosingBracket) if depth > 0 => depth -= 1,
+            Some(Kind::ClosingBrace) if depth > 0 => depth -= 1,
+            Some(Kind::ClosingBrace) => break,
+            Some(Kind::Name) if depth == 0 && w.position.line_number > line => break,
+            _ => {}
+        }
+        iter.next();
+    }
+}
@@ -0,0 +1,132 @@
+//! Module dedicated to [Constant] parsing.
+
+use core::slice::Windows;
+use std::collections::HashMap;
+
+use super::r#type::Type;
+use super::word::{Kind, Word};
+use super::{CommentsAnnotations, PositionnedString, Value};
+use crate::ScriptError;
+
+/// Structure describing a textual constant declaration.
+///
+/// It owns the name, type, and value of the constant.
+#[derive(Clone, Debug)]
+pub struct Constant {
+    pub annotations: Option<CommentsAnnotations>,
+    pub name: PositionnedString,
+    pub r#type: Type,
+    pub value: Value,
+}
+
+impl Constant {
+    /// Build a constant by parsing words.
+    ///
+    /// * `iter`: Iterator over words list, next() being expected to be the name of the constant.
+    ///
+    pub fn build(
+        mut iter: &mut Windows<Word>,
+        mut self_annotations: Option<CommentsAnnotations>,
+        global_annotations: &mut HashMap<Word, CommentsAnnotations>,
+    ) -> Result<Self, ScriptError> {
+        let name: PositionnedString = iter
+            .next()
+            .map(|s| &s[0])
+            .ok_or_else(|| ScriptError::end_of_script(230))
+            .and_then(|w| {
+                if w.kind != Some(Kind::Name) {
+                    Err(ScriptError::word(231, w.clone(), &[Kind::Name]))
+                } else {
+                    Ok(w.into())
+                }
+            })?;
+
+        iter.next()
+            .map(|s| &s[0])
+            .ok_or_else(|| ScriptError::end_of_script(232))
+            .and_then(|w| {
+                if w.kind != Some(Kind::Colon) {
+                    Err(ScriptError::word(233, w.clone(), &[Kind::Colon]))
+                } else {
+                    Ok(())
+                }
+            })?;
+
+        let (r#type, next_word) = Type::build(&mut iter, global_annotations)?;
+        match next_word.kind {
+            Some(Kind::Equal) => {
+                // Consuming '='
+                iter.next();
+            }
+            None => return Err(ScriptError::end_of_script(234)),
+            _ => return Err(ScriptError::word(235, next_word, &[Kind::Equal])),
+        }
+
+        let value = Value::build_from_first_item(&mut iter, global_annotations)?;
+
+        if let Some(doc) = self_annotations.as_mut().and_then(|sa| sa.doc.as_mut()) {
+            doc.remove_indent();
+        }
+
+        Ok(Self {
+            annotations: self_annotations,
+            name,
+            r#type,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::word::*;
+    use super::*;
+
+    fn words(text: &str) -> Vec<Word> {
+        let mut words = get_words(text).unwrap();
+        words.push(Word::default());
+        words
+    }
+
+    #[test]
+    fn test_well_catching_constant() {
+        let words = words("LIMIT: u32 = 1000");
+        let mut iter = words.windows(2);
+
+        let constant = Constant::build(&mut iter, None, &mut HashMap::new()).unwrap();
+
+        assert_eq!(constant.name.string, "LIMIT");
+        assert_eq!(constant.r#type.name.string, "u32");
+    }
+
+    #[test]
+    fn test_constant_without_name() {
+        let words = words("|limit: u32 = 1000");
+        let mut iter = words.windows(2);
+
+        let error = Constant::build(&mut iter, None, &mut HashMap::new()).unwrap_err();
+
+        assert_eq!(error.id, 231);
+    }
+
+    #[test]
+    fn test_constant_without_type() {
+        let words = words("LIMIT = 1000");
+        let mut iter = words.windows(2);
+
+        let error = Constant::build(&mut iter, None, &mut HashMap::new()).unwrap_err();
+
+        assert_eq!(error.id, 233);
+    }
+
+    #[test]
+    fn test_constant_without_value() {
+        let words = words("LIMIT: u32 1000");
+        let mut iter = words.windows(2);
+
+        let error = Constant::build(&mut iter, None, &mut HashMap::new()).unwrap_err();
+
+        assert_eq!(error.id, 235);
+    }
+}
@@ -0,0 +1,324 @@
+//! Module dedicated to [Data] parsing.
+
+use core::slice::Windows;
+use std::collections::HashMap;
+
+use super::generic::Generic;
+use super::r#type::Type;
+use super::word::{Kind, Word};
+use super::{CommentsAnnotations, PositionnedString};
+use crate::ScriptError;
+
+/// Structure describing a textual data declaration.
+///
+/// It owns a name, traits to derive, and a structure, being either fields (`data`) or variants (`enum`).
+#[derive(Clone, Debug)]
+pub struct Data {
+    pub annotations: Option<CommentsAnnotations>,
+    pub name: PositionnedString,
+    pub traits: Vec<PositionnedString>,
+    pub structure: DataStructure,
+}
+
+/// Structure of a textual data declaration, being fields for records or variants for enumerations.
+#[derive(Clone, Debug)]
+pub enum DataStructure {
+    Record(Vec<DataField>),
+    Enumeration(Vec<PositionnedString>),
+}
+
+/// Structure describing a textual data field.
+#[derive(Clone, Debug)]
+pub struct DataField {
+    pub annotations: Option<CommentsAnnotations>,
+    pub name: PositionnedString,
+    pub r#type: Type,
+}
+
+impl Data {
+    /// Build a record data by parsing words.
+    ///
+    /// * `iter`: Iterator over words list, next() being expected to be the name.
+    ///
+    pub fn build_record(
+        mut iter: &mut Windows<Word>,
+        self_annotations: Option<CommentsAnnotations>,
+        global_annotations: &mut HashMap<Word, CommentsAnnotations>,
+    ) -> Result<Self, ScriptError> {
+        let (name, traits) = Self::build_head(iter)?;
+
+        let mut fields = Vec::new();
+        loop {
+            match iter.next().map(|s| &s[0]) {
+                Some(w) if w.kind == Some(Kind::ClosingBrace) => break,
+                Some(w) if w.kind == Some(Kind::Name) => {
+                    iter.next()
+                        .map(|s| &s[0])
+                        .ok_or_else(|| ScriptError::end_of_script(189))
+                        .and_then(|w| {
+                            if w.kind != Some(Kind::Colon) {
+                                Err(ScriptError::word(190, w.clone(), &[Kind::Colon]))
+                            } else {
+                                Ok(())
+                            }
+                        })?;
+
+                    let (r#type, next_word) = Type::build(&mut iter, global_annotations)?;
+                    fields.push(DataField {
+                        annotations: global_annotations.remove(w),
+                        name: w.into(),
+                        r#type,
+                    });
+
+                    match next_word.kind {
+                        Some(Kind::Comma) | Some(Kind::ClosingBrace) => {}
+                        None => return Err(ScriptError::end_of_script(191)),
+                        _ => {
+                            return Err(ScriptError::word(
+                                192,
+                                next_word,
+                                &[Kind::Comma, Kind::ClosingBrace],
+                            ))
+                        }
+                    }
+                    if iter.next().map(|s| s[0].kind) == Some(Some(Kind::ClosingBrace)) {
+                        break;
+                    }
+                }
+                Some(w) => {
+                    return Err(ScriptError::word(
+                        193,
+                        w.clone(),
+                        &[Kind::Name, Kind::ClosingBrace],
+                    ))
+                }
+                None => return Err(ScriptError::end_of_script(194)),
+            }
+        }
+
+        Ok(Self::finish(
+            self_annotations,
+            name,
+            traits,
+            DataStructure::Record(fields),
+        ))
+    }
+
+    /// Build an enumeration data by parsing words.
+    ///
+    /// * `iter`: Iterator over words list, next() being expected to be the name.
+    ///
+    pub fn build_enumeration(
+        iter: &mut Windows<Word>,
+        self_annotations: Option<CommentsAnnotations>,
+    ) -> Result<Self, ScriptError> {
+        let (name, traits) = Self::build_head(iter)?;
+
+        let mut variants = Vec::new();
+        loop {
+            match iter.next().map(|s| &s[0]) {
+                Some(w) if w.kind == Some(Kind::ClosingBrace) => break,
+                Some(w) if w.kind == Some(Kind::Name) => {
+                    variants.push(w.into());
+
+                    match iter.next().map(|s| &s[0]) {
+                        Some(w) if w.kind == Some(Kind::Comma) => continue,
+                        Some(w) if w.kind == Some(Kind::ClosingBrace) => break,
+                        Some(w) => {
+                            return Err(ScriptError::word(
+                                195,
+                                w.clone(),
+                                &[Kind::Comma, Kind::ClosingBrace],
+                            ))
+                        }
+                        None => return Err(ScriptError::end_of_script(196)),
+                    }
+                }
+                Some(w) => {
+                    return Err(ScriptError::word(
+                        197,
+                        w.clone(),
+                        &[Kind::Name, Kind::ClosingBrace],
+                    ))
+                }
+                None => return Err(ScriptError::end_of_script(198)),
+            }
+        }
+
+        Ok(Self::finish(
+            self_annotations,
+            name,
+            traits,
+            DataStructure::Enumeration(variants),
+        ))
+    }
+
+    /// Parse name, traits, and opening brace.
+    fn build_head(
+        mut iter: &mut Windows<Word>,
+    ) -> Result<(PositionnedString, Vec<PositionnedString>), ScriptError> {
+        let name = iter
+            .next()
+            .map(|s| &s[0])
+            .ok_or_else(|| ScriptError::end_of_script(199))
+            .and_then(|w| {
+                if w.kind != Some(Kind::Name) {
+                    Err(ScriptError::word(200, w.clone(), &[Kind::Name]))
+                } else {
+                    Ok(w.into())
+                }
+            })?;
+
+        let mut traits = Vec::new();
+        let mut next = iter.next().map(|s| &s[0]);
+        if let Some(w) = next {
+            if w.kind == Some(Kind::Colon) {
+                traits = Generic::parse_traits(&mut iter)?;
+                next = iter.next().map(|s| &s[0]);
+            }
+        }
+
+        match next {
+            Some(w) if w.kind == Some(Kind::OpeningBrace) => Ok((name, traits)),
+            Some(w) => Err(ScriptError::word(
+                201,
+                w.clone(),
+                &[Kind::Colon, Kind::OpeningBrace],
+            )),
+            None => Err(ScriptError::end_of_script(202)),
+        }
+    }
+
+    fn finish(
+        mut self_annotations: Option<CommentsAnnotations>,
+        name: PositionnedString,
+        traits: Vec<PositionnedString>,
+        structure: DataStructure,
+    ) -> Self {
+        if let Some(doc) = self_annotations.as_mut().and_then(|sa| sa.doc.as_mut()) {
+            doc.remove_indent();
+        }
+
+        Self {
+            annotations: self_annotations,
+            name,
+            traits,
+            structure,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::word::*;
+    use super::*;
+
+    fn words(text: &str) -> Vec<Word> {
+        let mut words = get_words(text).unwrap();
+        words.push(Word::default());
+        words
+    }
+
+    #[test]
+    fn test_well_catching_record() {
+        let words = words("Point: PartialEquality + Display { x: f64, y: Vec<f64> }");
+        let mut iter = words.windows(2);
+
+        let data = Data::build_record(&mut iter, None, &mut HashMap::new()).unwrap();
+
+        assert_eq!(data.name.string, "Point");
+        assert_eq!(
+            data.traits
+                .iter()
+                .map(|t| t.string.as_str())
+                .collect::<Vec<_>>(),
+            vec!["PartialEquality", "Display"]
+        );
+        match data.structure {
+            DataStructure::Record(fields) => {
+                assert_eq!(fields.len(), 2);
+                assert_eq!(fields[0].name.string, "x");
+                assert_eq!(fields[0].r#type.name.string, "f64");
+                assert_eq!(fields[1].name.string, "y");
+                assert_eq!(fields[1].r#type.level_structure[0].string, "Vec");
+                assert_eq!(fields[1].r#type.name.string, "f64");
+            }
+            DataStructure::Enumeration(_) => panic!("Record expected"),
+        }
+    }
+
+    #[test]
+    fn test_well_catching_record_with_trailing_comma() {
+        let words = words("Point { x: f64, }");
+        let mut iter = words.windows(2);
+
+        let data = Data::build_record(&mut iter, None, &mut HashMap::new()).unwrap();
+
+        assert!(data.traits.is_empty());
+        match data.structure {
+            DataStructure::Record(fields) => assert_eq!(fields.len(), 1),
+            DataStructure::Enumeration(_) => panic!("Record expected"),
+        }
+    }
+
+    #[test]
+    fn test_well_catching_enumeration() {
+        let words = words("Level: Display { Low, Medium, High }");
+        let mut iter = words.windows(2);
+
+        let data = Data::build_enumeration(&mut iter, None).unwrap();
+
+        assert_eq!(data.name.string, "Level");
+        match data.structure {
+            DataStructure::Enumeration(variants) => assert_eq!(
+                variants
+                    .iter()
+                    .map(|v| v.string.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["Low", "Medium", "High"]
+            ),
+            DataStructure::Record(_) => panic!("Enumeration expected"),
+        }
+    }
+
+    #[test]
+    fn test_record_field_without_type() {
+        let words = words("Point { x f64 }");
+        let mut iter = words.windows(2);
+
+        let error = Data::build_record(&mut iter, None, &mut HashMap::new()).unwrap_err();
+
+        assert_eq!(error.id, 190);
+    }
+
+    #[test]
+    fn test_record_fields_without_separator() {
+        let words = words("Point { x: f64 y: f64 }");
+        let mut iter = words.windows(2);
+
+        let error = Data::build_record(&mut iter, None, &mut HashMap::new()).unwrap_err();
+
+        assert_eq!(error.id, 192);
+    }
+
+    #[test]
+    fn test_enumeration_variants_without_separator() {
+        let words = words("Level { Low Medium }");
+        let mut iter = words.windows(2);
+
+        let error = Data::build_enumeration(&mut iter, None).unwrap_err();
+
+        assert_eq!(error.id, 195);
+    }
+
+    #[test]
+    fn test_data_without_body() {
+        let words = words("Level Low");
+        let mut iter = words.windows(2);
+
+        let error = Data::build_enumeration(&mut iter, None).unwrap_err();
+
+        assert_eq!(error.id, 201);
+    }
+}
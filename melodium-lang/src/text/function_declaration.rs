@@ -0,0 +1,205 @@
+//! Module dedicated to [FunctionDeclaration] parsing.
+
+use core::slice::Windows;
+use std::collections::HashMap;
+
+use super::common::{parse_generics, parse_parameters_declarations};
+use super::parameter::Parameter;
+use super::r#type::Type;
+use super::word::{Kind, Word};
+use super::{CommentsAnnotations, Generic, PositionnedString, Value};
+use crate::ScriptError;
+
+/// Structure describing a textual function declaration.
+///
+/// It owns the name, generics, parameters, and return type of the function, as well as the value being its body.
+/// There is no logical dependency between them at this point.
+#[derive(Clone, Debug)]
+pub struct FunctionDeclaration {
+    pub annotations: Option<CommentsAnnotations>,
+    pub name: PositionnedString,
+    pub generics: Vec<Generic>,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Type,
+    pub body: Value,
+}
+
+impl FunctionDeclaration {
+    /// Build a function declaration by parsing words.
+    ///
+    /// * `iter`: Iterator over words list, next() being expected to be the name of the function.
+    ///
+    pub fn build(
+        mut iter: &mut Windows<Word>,
+        mut self_annotations: Option<CommentsAnnotations>,
+        global_annotations: &mut HashMap<Word, CommentsAnnotations>,
+    ) -> Result<Self, ScriptError> {
+        let name: PositionnedString = iter
+            .next()
+            .map(|s| &s[0])
+            .ok_or_else(|| ScriptError::end_of_script(212))
+            .and_then(|w| {
+                if w.kind != Some(Kind::Function) {
+                    Err(ScriptError::word(213, w.clone(), &[Kind::Function]))
+                } else {
+                    Ok(w.into())
+                }
+            })?;
+
+        let mut generics = Vec::new();
+        let parameters;
+        loop {
+            match iter.next().map(|s| &s[0]) {
+                Some(w) if w.kind == Some(Kind::OpeningChevron) && generics.is_empty() => {
+                    generics = parse_generics(&mut iter, global_annotations)?;
+                }
+                Some(w) if w.kind == Some(Kind::OpeningParenthesis) => {
+                    parameters = parse_parameters_declarations(&mut iter, global_annotations)?;
+                    break;
+                }
+                Some(w) => {
+                    return Err(ScriptError::word(
+                        214,
+                        w.clone(),
+                        &[Kind::OpeningChevron, Kind::OpeningParenthesis],
+                    ))
+                }
+                None => return Err(ScriptError::end_of_script(215)),
+            }
+        }
+
+        iter.next()
+            .map(|s| &s[0])
+            .ok_or_else(|| ScriptError::end_of_script(216))
+            .and_then(|w| {
+                if w.kind != Some(Kind::RightArrow) {
+                    Err(ScriptError::word(217, w.clone(), &[Kind::RightArrow]))
+                } else {
+                    Ok(())
+                }
+            })?;
+
+        let (return_type, next_word) = Type::build(&mut iter, global_annotations)?;
+        match next_word.kind {
+            Some(Kind::OpeningBrace) => {
+                // Consuming '{'
+                iter.next();
+            }
+            None => return Err(ScriptError::end_of_script(218)),
+            _ => return Err(ScriptError::word(219, next_word, &[Kind::OpeningBrace])),
+        }
+
+        let body = Value::build_from_first_item(&mut iter, global_annotations)?;
+
+        iter.next()
+            .map(|s| &s[0])
+            .ok_or_else(|| ScriptError::end_of_script(220))
+            .and_then(|w| {
+                if w.kind != Some(Kind::ClosingBrace) {
+                    Err(ScriptError::word(221, w.clone(), &[Kind::ClosingBrace]))
+                } else {
+                    Ok(())
+                }
+            })?;
+
+        if let Some(doc) = self_annotations.as_mut().and_then(|sa| sa.doc.as_mut()) {
+            doc.remove_indent();
+        }
+
+        Ok(Self {
+            annotations: self_annotations,
+            name,
+            generics,
+            parameters,
+            return_type,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::word::*;
+    use super::*;
+
+    fn words(text: &str) -> Vec<Word> {
+        let mut words = get_words(text).unwrap();
+        words.push(Word::default());
+        words
+    }
+
+    #[test]
+    fn test_well_catching_function() {
+        let words = words("|scale<N: Float>(value: N, factor: N) -> N { |mult<N>(value, factor) }");
+        let mut iter = words.windows(2);
+
+        let function = FunctionDeclaration::build(&mut iter, None, &mut HashMap::new()).unwrap();
+
+        assert_eq!(function.name.string, "|scale");
+        assert_eq!(function.generics.len(), 1);
+        assert_eq!(function.generics[0].r#type.name.string, "N");
+        assert_eq!(function.generics[0].traits[0].string, "Float");
+        assert_eq!(
+            function
+                .parameters
+                .iter()
+                .map(|p| p.name.string.as_str())
+                .collect::<Vec<_>>(),
+            vec!["value", "factor"]
+        );
+        assert_eq!(function.return_type.name.string, "N");
+    }
+
+    #[test]
+    fn test_well_catching_function_without_parameters() {
+        let words = words("|answer() -> u32 { 42 }");
+        let mut iter = words.windows(2);
+
+        let function = FunctionDeclaration::build(&mut iter, None, &mut HashMap::new()).unwrap();
+
+        assert!(function.generics.is_empty());
+        assert!(function.parameters.is_empty());
+        assert_eq!(function.return_type.name.string, "u32");
+    }
+
+    #[test]
+    fn test_function_name_without_pipe() {
+        let words = words("answer() -> u32 { 42 }");
+        let mut iter = words.windows(2);
+
+        let error = FunctionDeclaration::build(&mut iter, None, &mut HashMap::new()).unwrap_err();
+
+        assert_eq!(error.id, 213);
+    }
+
+    #[test]
+    fn test_function_without_return_type() {
+        let words = words("|answer() { 42 }");
+        let mut iter = words.windows(2);
+
+        let error = FunctionDeclaration::build(&mut iter, None, &mut HashMap::new()).unwrap_err();
+
+        assert_eq!(error.id, 217);
+    }
+
+    #[test]
+    fn test_function_without_body() {
+        let words = words("|answer() -> u32 42");
+        let mut iter = words.windows(2);
+
+        let error = FunctionDeclaration::build(&mut iter, None, &mut HashMap::new()).unwrap_err();
+
+        assert_eq!(error.id, 219);
+    }
+
+    #[test]
+    fn test_function_body_with_several_values() {
+        let words = words("|answer() -> u32 { 42 43 }");
+        let mut iter = words.windows(2);
+
+        let error = FunctionDeclaration::build(&mut iter, None, &mut HashMap::new()).unwrap_err();
+
+        assert_eq!(error.id, 221);
+    }
+}
@@ -69,7 +69,9 @@ impl Generic {
         })
     }
 
-    fn parse_traits(iter: &mut Windows<Word>) -> Result<Vec<PositionnedString>, ScriptError> {
+    pub(super) fn parse_traits(
+        iter: &mut Windows<Word>,
+    ) -> Result<Vec<PositionnedString>, ScriptError> {
         let mut traits = Vec::new();
         while let Some((trait_name, nw)) = iter.next().map(|s| (&s[0], &s[1])) {
             if trait_name.kind == Some(Kind::Name) {
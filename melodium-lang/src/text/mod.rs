@@ -8,7 +8,10 @@
 mod annotation;
 mod common;
 mod connection;
+mod constant;
+mod data;
 mod function;
+mod function_declaration;
 mod generic;
 mod instanciation;
 mod model;
@@ -22,8 +25,12 @@ mod value;
 mod word;
 
 pub use annotation::{Annotation, CommentsAnnotations};
+pub use common::DECLARATIONS;
 pub use connection::Connection;
+pub use constant::Constant;
+pub use data::{Data, DataField, DataStructure};
 pub use function::Function;
+pub use function_declaration::FunctionDeclaration;
 pub use generic::Generic;
 pub use instanciation::Instanciation;
 pub use model::Model;
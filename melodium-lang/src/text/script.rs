@@ -1,23 +1,30 @@
 //! Module dedicated to [Script] parsing.
 
 use super::annotation::Annotation;
+use super::common::{skip_to_declaration, DECLARATIONS};
+use super::constant::Constant;
+use super::data::Data;
+use super::function_declaration::FunctionDeclaration;
 use super::model::Model;
 use super::r#use::Use;
 use super::treatment::Treatment;
 use super::word::{get_words, Kind, Position, Word};
 use super::{CommentsAnnotations, PositionnedString};
-use crate::ScriptError;
+use crate::{ScriptError, ScriptResult};
 use std::collections::HashMap;
 
 /// Structure managing and describing textual script.
 ///
-/// It owns the whole script text, as well as parsed attributes, including [Use]s, [Annotation]s, [Model]s, and [Treatment]s.
+/// It owns the whole script text, as well as parsed attributes, including [Use]s, [Annotation]s, [Constant]s, [Data], [FunctionDeclaration]s, [Model]s, and [Treatment]s.
 /// There is no logical coherence involved there, only syntax analysis and parsing.
 #[derive(Clone, Debug)]
 pub struct Script {
     pub text: String,
     pub uses: Vec<Use>,
     pub annotations: Vec<Annotation>,
+    pub constants: Vec<Constant>,
+    pub data: Vec<Data>,
+    pub functions: Vec<FunctionDeclaration>,
     pub models: Vec<Model>,
     pub treatments: Vec<Treatment>,
 }
@@ -32,18 +39,22 @@ impl Script {
     ///
     /// # Note
     /// It doesn't check any logic, only syntax analysis and parsing.
+    /// Syntax errors don't stop parsing, which resumes at the next declaration, so every error of the script is reported.
     ///
-    pub fn build(text: &str) -> Result<Self, ScriptError> {
+    pub fn build(text: &str) -> ScriptResult<Self> {
         let mut uses = Vec::new();
+        let mut constants = Vec::new();
+        let mut data = Vec::new();
+        let mut functions = Vec::new();
         let mut models = Vec::new();
         let mut treatments = Vec::new();
 
         let words = get_words(text);
         if let Err(err_words) = words {
             if let Some(err_word) = err_words.last() {
-                return Err(ScriptError::word(19, err_word.clone(), &[]));
+                return ScriptResult::new_failure(ScriptError::word(19, err_word.clone(), &[]));
             } else {
-                return Err(ScriptError::end_of_script(20));
+                return ScriptResult::new_failure(ScriptError::end_of_script(20));
             }
         }
 
@@ -131,46 +142,142 @@ impl Script {
 
         let words = words;
 
+        let mut result = ScriptResult::new_success(());
         let mut iter = words.windows(2);
         loop {
-            match iter.next().map(|s| &s[0]) {
+            let declaration_start = iter.clone();
+            let status: ScriptResult<()> = match iter.next().map(|s| &s[0]) {
                 Some(w) if w.kind == Some(Kind::Name) => match w.text.as_str() {
-                    "use" => uses.push(Use::build(&mut iter)?),
-                    "model" => models.push(Model::build(
+                    "use" => Use::build(&mut iter, annotated_items.remove(&w))
+                        .map(|r#use| uses.push(r#use))
+                        .into(),
+                    "const" => {
+                        Constant::build(&mut iter, annotated_items.remove(&w), &mut annotated_items)
+                            .map(|constant| constants.push(constant))
+                            .into()
+                    }
+                    "data" => Data::build_record(
                         &mut iter,
                         annotated_items.remove(&w),
                         &mut annotated_items,
-                    )?),
-                    "treatment" => treatments.push(Treatment::build(
+                    )
+                    .map(|record| data.push(record))
+                    .into(),
+                    "enum" => Data::build_enumeration(&mut iter, annotated_items.remove(&w))
+                        .map(|enumeration| data.push(enumeration))
+                        .into(),
+                    "function" => FunctionDeclaration::build(
                         &mut iter,
                         annotated_items.remove(&w),
                         &mut annotated_items,
-                    )?),
-                    _ => {
-                        return Err(ScriptError::declaration_expected(
-                            51,
-                            w.clone(),
-                            &["use", "model", "treatment"],
-                        ))
+                    )
+                    .map(|function| functions.push(function))
+                    .into(),
+                    "model" => {
+                        Model::build(&mut iter, annotated_items.remove(&w), &mut annotated_items)
+                            .map(|model| models.push(model))
+                            .into()
                     }
-                },
-                Some(w) => {
-                    return Err(ScriptError::word(
-                        52,
+                    "treatment" => Treatment::build(
+                        &mut iter,
+                        annotated_items.remove(&w),
+                        &mut annotated_items,
+                    )
+                    .and_then(|treatment| {
+                        treatments.push(treatment);
+                        ScriptResult::new_success(())
+                    }),
+                    _ => ScriptResult::new_failure(ScriptError::declaration_expected(
+                        51,
                         w.clone(),
-                        &[Kind::Annotation, Kind::Name],
-                    ))
-                }
+                        DECLARATIONS,
+                    )),
+                },
+                Some(w) => ScriptResult::new_failure(ScriptError::word(
+                    52,
+                    w.clone(),
+                    &[Kind::Annotation, Kind::Name],
+                )),
                 None => break,
+            };
+
+            // In case of syntax error, parsing resumes at next declaration, so all errors get reported.
+            if status.is_failure() {
+                iter = declaration_start;
+                iter.next();
+                skip_to_declaration(&mut iter);
             }
+            result.merge_degrade_failure(status);
         }
 
-        Ok(Self {
-            text: text.to_string(),
-            uses,
-            annotations,
-            models,
-            treatments,
+        result.and_then(|_| {
+            ScriptResult::new_success(Self {
+                text: text.to_string(),
+                uses,
+                annotations,
+                constants,
+                data,
+                functions,
+                models,
+                treatments,
+            })
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn error_ids<T>(result: &ScriptResult<T>) -> Vec<u32> {
+        let mut ids: Vec<u32> = result.errors().iter().map(|error| error.id).collect();
+        if let Some(failure) = result.failure() {
+            ids.push(failure.id);
+        }
+        ids
+    }
+
+    #[test]
+    fn test_all_declarations_parsed() {
+        let script = Script::build(
+            "use std/flow::emit
+const LIMIT: u32 = 3
+data Point: ToString { x: u32 }
+enum Level { Low, High }
+function |limit() -> u32 { LIMIT }
+model Pool(size: u32) : Base {}
+treatment main()
+{
+    emit<u32>(value=|limit())
+}",
+        )
+        .to_success()
+        .unwrap();
+
+        assert_eq!(script.uses.len(), 1);
+        assert_eq!(script.constants.len(), 1);
+        assert_eq!(script.data.len(), 2);
+        assert_eq!(script.functions.len(), 1);
+        assert_eq!(script.models.len(), 1);
+        assert_eq!(script.treatments.len(), 1);
+    }
+
+    #[test]
+    fn test_several_errors_reported() {
+        let result = Script::build(
+            "use std/flow::emit
+unknown declaration
+const : u32 = 3
+function |limit() { 3 }
+treatment main()
+{
+    emit<u32>(value=)
+    third emit<u32>
+}
+treatment valid() {}",
+        );
+
+        assert_eq!(error_ids(&result), vec![51, 231, 217, 2, 50]);
+    }
+}
@@ -4,7 +4,8 @@ use core::slice::Windows;
 use std::collections::HashMap;
 
 use super::common::{
-    parse_configuration_declarations, parse_generics, parse_parameters_declarations,
+    is_declaration_start, parse_configuration_declarations, parse_generics,
+    parse_parameters_declarations, skip_to_body_element,
 };
 use super::connection::Connection;
 use super::instanciation::Instanciation;
@@ -12,7 +13,7 @@ use super::parameter::Parameter;
 use super::requirement::Requirement;
 use super::word::{Kind, Word};
 use super::{CommentsAnnotations, Generic, PositionnedString};
-use crate::ScriptError;
+use crate::{ScriptError, ScriptResult};
 
 /// Structure describing a textual treatment.
 ///
@@ -37,7 +38,51 @@ impl Treatment {
     ///
     /// * `iter`: Iterator over words list, next() being expected to be the name of the treatment.
     ///
+    /// Syntax errors occuring within treatment body are all reported, parsing resuming at next element of body.
+    ///
     pub fn build(
+        iter: &mut Windows<Word>,
+        self_annotations: Option<CommentsAnnotations>,
+        global_annotations: &mut HashMap<Word, CommentsAnnotations>,
+    ) -> ScriptResult<Self> {
+        let mut treatment =
+            match Self::build_declaration(iter, self_annotations, global_annotations) {
+                Ok(treatment) => treatment,
+                Err(err) => return ScriptResult::new_failure(err),
+            };
+
+        let mut result = ScriptResult::new_success(());
+        let mut body = Body::default();
+
+        loop {
+            let element_start = iter.clone();
+            match body.build_element(iter, global_annotations) {
+                Ok(true) => break,
+                Ok(false) => {}
+                Err(err) => {
+                    result.merge_degrade_failure(ScriptResult::<()>::new_failure(err));
+
+                    // Resuming parsing at next element of body, if any.
+                    *iter = element_start;
+                    skip_to_body_element(iter);
+                    body.reset_chain();
+
+                    match iter.clone().next().map(|s| &s[0]) {
+                        Some(w) if !is_declaration_start(w) => {}
+                        _ => break,
+                    }
+                }
+            }
+        }
+
+        treatment.treatments = body.treatments;
+        treatment.connections = body.connections;
+
+        result.and_then(|_| ScriptResult::new_success(treatment))
+    }
+
+    /// Build treatment declaration, up to the opening brace of its body.
+    fn build_declaration(
         mut iter: &mut Windows<Word>,
         mut self_annotations: Option<CommentsAnnotations>,
         global_annotations: &mut HashMap<Word, CommentsAnnotations>,
@@ -193,188 +238,258 @@ impl Treatment {
             }
         }
 
-        let mut treatments = Vec::new();
-        let mut connections = Vec::new();
+        if let Some(doc) = self_annotations.as_mut().and_then(|sa| sa.doc.as_mut()) {
+            doc.remove_indent();
+        }
 
-        /*
-            We prepare variables able to tell if the last built element were a connection.
-            Because connections are chainable, and that possibility of chain depends on
-            the type of last connection, we have to keep track on (1) was the last
-            connection including data transmission, or (2) was it only a chain of treatments.
-        */
-        let mut last_connection_name_end_point: Option<PositionnedString> = None;
-        let mut may_be_connection_data_out = false; //  (1)
-        let mut may_be_connection_end_point = false; // (2)
+        Ok(Self {
+            annotations: self_annotations,
+            name,
+            generics,
+            configuration,
+            parameters,
+            models,
+            requirements,
+            inputs,
+            outputs,
+            treatments: Vec::new(),
+            connections: Vec::new(),
+        })
+    }
+}
 
-        loop {
-            // Those are convenience variables, in case we're not continuing a connection chain,
-            // reused in "else" block later.
-            let element_name: PositionnedString;
-            let mut element_annotations = None;
-            let determinant;
+/// Body of a treatment being parsed.
+#[derive(Default)]
+struct Body {
+    treatments: Vec<Instanciation>,
+    connections: Vec<Connection>,
 
-            match iter.next().map(|s| &s[0]) {
-                // In case a continuation of connection with data transmission (1) is possible,
-                // we check if word is a comma.
-                Some(w) if w.kind == Some(Kind::Comma) && may_be_connection_data_out => {
-                    let connection = Connection::build_from_name_data_out(
-                        element_annotations,
-                        last_connection_name_end_point.unwrap(),
-                        &mut iter,
-                    )?;
-                    last_connection_name_end_point = Some(connection.name_end_point.clone());
-                    connections.push(connection);
+    /*
+        We prepare variables able to tell if the last built element were a connection.
+        Because connections are chainable, and that possibility of chain depends on
+        the type of last connection, we have to keep track on (1) was the last
+        connection including data transmission, or (2) was it only a chain of treatments.
+    */
+    last_connection_name_end_point: Option<PositionnedString>,
+    may_be_connection_data_out: bool,  //  (1)
+    may_be_connection_end_point: bool, // (2)
+}
 
-                    // Redundant assignation, as will stay as true
-                    // may_be_connection_data_out = true;
+impl Body {
+    /// Build next element of body, returning `true` if closing brace ending the body have been reached.
+    fn build_element(
+        &mut self,
+        mut iter: &mut Windows<Word>,
+        global_annotations: &mut HashMap<Word, CommentsAnnotations>,
+    ) -> Result<bool, ScriptError> {
+        // Those are convenience variables, in case we're not continuing a connection chain,
+        // reused in "else" block later.
+        let element_name: PositionnedString;
+        let mut element_annotations = None;
+        let determinant;
 
-                    // And nothing is to do later in that iteration.
-                    continue;
-                }
-                // In case a continuation of connection that only chain treatments (2) is possible,
-                // we check if word is a right arrow '-->'.
-                Some(w) if w.kind == Some(Kind::RightArrow) && may_be_connection_end_point => {
-                    // So it means we expect continuing a connection that only chains treatments (2).
-                    let connection = Connection::build_from_name_end_point(
-                        element_annotations,
-                        last_connection_name_end_point.unwrap(),
-                        &mut iter,
-                    )?;
-                    last_connection_name_end_point = Some(connection.name_end_point.clone());
-                    connections.push(connection);
+        match iter.next().map(|s| &s[0]) {
+            // In case a continuation of connection with data transmission (1) is possible,
+            // we check if word is a comma.
+            Some(w) if w.kind == Some(Kind::Comma) && self.may_be_connection_data_out => {
+                let connection = Connection::build_from_name_data_out(
+                    element_annotations,
+                    self.last_connection_name_end_point.take().unwrap(),
+                    &mut iter,
+                )?;
+                self.last_connection_name_end_point = Some(connection.name_end_point.clone());
+                self.connections.push(connection);
 
-                    // Redundant assignation, as will stay as true
-                    // may_be_connection_end_point = true;
+                // Redundant assignation, as will stay as true
+                // self.may_be_connection_data_out = true;
 
-                    // And nothing is to do later in that iteration.
-                    continue;
-                }
-                Some(w) => {
-                    // We're not continuing a connection, so resetting those ones.
-                    last_connection_name_end_point = None;
-                    may_be_connection_data_out = false;
-                    may_be_connection_end_point = false;
-
-                    // If we're not continuing a connection, word have to be the name of an element.
-                    if w.kind == Some(Kind::Name) {
-                        element_name = w.into();
-                        element_annotations = global_annotations.remove(w);
-
-                        // And the next word is determinant of what can follow.
-                        determinant = iter.next();
-                    }
-                    // Or a closing brace, ending the treatment.
-                    else if w.kind == Some(Kind::ClosingBrace) {
-                        break;
-                    } else {
-                        return Err(ScriptError::word(
-                            48,
-                            w.clone(),
-                            &[Kind::Name, Kind::ClosingBrace],
-                        ));
-                    }
-                }
-                None => return Err(ScriptError::end_of_script(49)),
+                // And nothing is to do later in that iteration.
+                return Ok(false);
             }
+            // In case a continuation of connection that only chain treatments (2) is possible,
+            // we check if word is a right arrow '-->'.
+            Some(w) if w.kind == Some(Kind::RightArrow) && self.may_be_connection_end_point => {
+                // So it means we expect continuing a connection that only chains treatments (2).
+                let connection = Connection::build_from_name_end_point(
+                    element_annotations,
+                    self.last_connection_name_end_point.take().unwrap(),
+                    &mut iter,
+                )?;
+                self.last_connection_name_end_point = Some(connection.name_end_point.clone());
+                self.connections.push(connection);
 
-            match determinant.map(|s| &s[0]) {
-                // If determinant is ':', '<', '[', or '(', we are in a treatment declaration.
-                Some(w) if w.kind == Some(Kind::Colon) => {
-                    treatments.push(Instanciation::build_from_type(
-                        element_annotations,
-                        element_name.clone(),
-                        &mut iter,
-                        global_annotations,
-                    )?)
-                }
-                Some(w) if w.kind == Some(Kind::OpeningChevron) => {
-                    treatments.push(Instanciation::build_from_generics(
-                        element_annotations,
-                        element_name.clone(),
-                        element_name.clone(),
-                        &mut iter,
-                        global_annotations,
-                    )?)
+                // Redundant assignation, as will stay as true
+                // self.may_be_connection_end_point = true;
+
+                // And nothing is to do later in that iteration.
+                return Ok(false);
+            }
+            Some(w) => {
+                // We're not continuing a connection, so resetting those ones.
+                self.reset_chain();
+
+                // If we're not continuing a connection, word have to be the name of an element.
+                if w.kind == Some(Kind::Name) {
+                    element_name = w.into();
+                    element_annotations = global_annotations.remove(w);
+
+                    // And the next word is determinant of what can follow.
+                    determinant = iter.next();
                 }
-                Some(w) if w.kind == Some(Kind::OpeningBracket) => {
-                    treatments.push(Instanciation::build_from_configuration(
-                        element_annotations,
-                        element_name.clone(),
-                        element_name.clone(),
-                        Vec::new(),
-                        &mut iter,
-                        global_annotations,
-                    )?)
+                // Or a closing brace, ending the treatment.
+                else if w.kind == Some(Kind::ClosingBrace) {
+                    return Ok(true);
+                } else {
+                    return Err(ScriptError::word(
+                        48,
+                        w.clone(),
+                        &[Kind::Name, Kind::ClosingBrace],
+                    ));
                 }
-                Some(w) if w.kind == Some(Kind::OpeningParenthesis) => {
-                    treatments.push(Instanciation::build_from_parameters(
+            }
+            None => return Err(ScriptError::end_of_script(49)),
+        }
+
+        match determinant.map(|s| &s[0]) {
+            // If determinant is ':', '<', '[', or '(', we are in a treatment declaration.
+            Some(w) if w.kind == Some(Kind::Colon) => {
+                self.treatments.push(Instanciation::build_from_type(
+                    element_annotations,
+                    element_name.clone(),
+                    &mut iter,
+                    global_annotations,
+                )?)
+            }
+            Some(w) if w.kind == Some(Kind::OpeningChevron) => {
+                self.treatments.push(Instanciation::build_from_generics(
+                    element_annotations,
+                    element_name.clone(),
+                    element_name.clone(),
+                    &mut iter,
+                    global_annotations,
+                )?)
+            }
+            Some(w) if w.kind == Some(Kind::OpeningBracket) => {
+                self.treatments
+                    .push(Instanciation::build_from_configuration(
                         element_annotations,
                         element_name.clone(),
                         element_name.clone(),
                         Vec::new(),
-                        Vec::new(),
                         &mut iter,
                         global_annotations,
                     )?)
-                }
-                // If determinant is a dot '.', we are in a connection declaration, with data transmission (1).
-                Some(w) if w.kind == Some(Kind::Dot) => {
-                    let connection = Connection::build_from_name_data_out(
-                        element_annotations,
-                        element_name,
-                        &mut iter,
-                    )?;
-                    last_connection_name_end_point = Some(connection.name_end_point.clone());
-                    connections.push(connection);
-                    // We remind that next iteration may be a continuation of connections.
-                    may_be_connection_data_out = true;
-                }
-                // If determinant is an arrow '-->', we are in a connection declaration, without data transmission (2).
-                Some(w) if w.kind == Some(Kind::RightArrow) => {
-                    let connection = Connection::build_from_name_end_point(
-                        element_annotations,
-                        element_name,
-                        &mut iter,
-                    )?;
-                    last_connection_name_end_point = Some(connection.name_end_point.clone());
-                    connections.push(connection);
-                    // We remind that next iteration may be a continuation of connections.
-                    may_be_connection_end_point = true;
-                }
-                Some(w) => {
-                    return Err(ScriptError::word(
-                        50,
-                        w.clone(),
-                        &[
-                            Kind::Colon,
-                            Kind::OpeningChevron,
-                            Kind::OpeningBracket,
-                            Kind::OpeningParenthesis,
-                            Kind::Dot,
-                            Kind::RightArrow,
-                        ],
-                    ))
-                }
-                None => return Err(ScriptError::end_of_script(51)),
             }
+            Some(w) if w.kind == Some(Kind::OpeningParenthesis) => {
+                self.treatments.push(Instanciation::build_from_parameters(
+                    element_annotations,
+                    element_name.clone(),
+                    element_name.clone(),
+                    Vec::new(),
+                    Vec::new(),
+                    &mut iter,
+                    global_annotations,
+                )?)
+            }
+            // If determinant is a dot '.', we are in a connection declaration, with data transmission (1).
+            Some(w) if w.kind == Some(Kind::Dot) => {
+                let connection = Connection::build_from_name_data_out(
+                    element_annotations,
+                    element_name,
+                    &mut iter,
+                )?;
+                self.last_connection_name_end_point = Some(connection.name_end_point.clone());
+                self.connections.push(connection);
+                // We remind that next iteration may be a continuation of connections.
+                self.may_be_connection_data_out = true;
+            }
+            // If determinant is an arrow '-->', we are in a connection declaration, without data transmission (2).
+            Some(w) if w.kind == Some(Kind::RightArrow) => {
+                let connection = Connection::build_from_name_end_point(
+                    element_annotations,
+                    element_name,
+                    &mut iter,
+                )?;
+                self.last_connection_name_end_point = Some(connection.name_end_point.clone());
+                self.connections.push(connection);
+                // We remind that next iteration may be a continuation of connections.
+                self.may_be_connection_end_point = true;
+            }
+            Some(w) => {
+                return Err(ScriptError::word(
+                    50,
+                    w.clone(),
+                    &[
+                        Kind::Colon,
+                        Kind::OpeningChevron,
+                        Kind::OpeningBracket,
+                        Kind::OpeningParenthesis,
+                        Kind::Dot,
+                        Kind::RightArrow,
+                    ],
+                ))
+            }
+            None => return Err(ScriptError::end_of_script(51)),
         }
 
-        if let Some(doc) = self_annotations.as_mut().and_then(|sa| sa.doc.as_mut()) {
-            doc.remove_indent();
+        Ok(false)
+    }
+
+    /// Forget about any connection chain.
+    fn reset_chain(&mut self) {
+        self.last_connection_name_end_point = None;
+        self.may_be_connection_data_out = false;
+        self.may_be_connection_end_point = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::word::*;
+    use super::*;
+
+    fn words(text: &str) -> Vec<Word> {
+        let mut words = get_words(text).unwrap();
+        words.push(Word::default());
+        words
+    }
+
+    fn error_ids<T>(result: &ScriptResult<T>) -> Vec<u32> {
+        let mut ids: Vec<u32> = result.errors().iter().map(|error| error.id).collect();
+        if let Some(failure) = result.failure() {
+            ids.push(failure.id);
         }
+        ids
+    }
 
-        Ok(Self {
-            annotations: self_annotations,
-            name,
-            generics,
-            configuration,
-            parameters,
-            models,
-            requirements,
-            inputs,
-            outputs,
-            treatments,
-            connections,
-        })
+    #[test]
+    fn test_well_catching_treatment() {
+        let words = words(
+            "main()\n  input trigger: Block<void>\n{\n    emit<u32>(value=3)\n    Self.trigger -> emit.trigger\n}",
+        );
+        let mut iter = words.windows(2);
+
+        let treatment = Treatment::build(&mut iter, None, &mut HashMap::new())
+            .to_success()
+            .unwrap();
+
+        assert_eq!(treatment.name.string, "main");
+        assert_eq!(treatment.inputs.len(), 1);
+        assert_eq!(treatment.treatments.len(), 1);
+        assert_eq!(treatment.connections.len(), 1);
+    }
+
+    #[test]
+    fn test_several_body_errors_reported() {
+        let words = words(
+            "main()\n{\n    first: emit<u32>(value=)\n    second: stream<u32>()\n    third emit<u32>\n    first.emit -> second.block\n}\ntreatment other() {}",
+        );
+        let mut iter = words.windows(2);
+
+        let result = Treatment::build(&mut iter, None, &mut HashMap::new());
+
+        assert_eq!(error_ids(&result), vec![2, 50]);
+        assert_eq!(iter.next().map(|s| s[0].text.as_str()), Some("treatment"));
     }
 }
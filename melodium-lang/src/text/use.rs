@@ -3,7 +3,7 @@
 use core::slice::Windows;
 
 use super::word::*;
-use super::PositionnedString;
+use super::{CommentsAnnotations, PositionnedString};
 use crate::ScriptError;
 
 /// Structure describing a textual use.
@@ -11,6 +11,7 @@ use crate::ScriptError;
 /// It owns the path, as vector of strings (which were separated by slashes `/`), the used element name, and optionally the alias. There is no logical nor existence check at this point.
 #[derive(Clone, Debug)]
 pub struct Use {
+    pub annotations: Option<CommentsAnnotations>,
     pub path: Vec<PositionnedString>,
     pub element: PositionnedString,
     pub r#as: Option<PositionnedString>,
@@ -20,8 +21,12 @@ impl Use {
     /// Build use by parsing words.
     ///
     /// * `iter`: Iterator over words list, next() being expected to be the beginning of the path.
+    /// * `annotations`: annotations preceding the use.
     ///
-    pub fn build(iter: &mut Windows<Word>) -> Result<Self, ScriptError> {
+    pub fn build(
+        iter: &mut Windows<Word>,
+        annotations: Option<CommentsAnnotations>,
+    ) -> Result<Self, ScriptError> {
         let mut path = Vec::new();
         let element;
         let use_as;
@@ -110,6 +115,7 @@ impl Use {
         }
 
         Ok(Self {
+            annotations,
             path,
             element,
             r#as: use_as,
@@ -11,6 +11,7 @@ use melodium_common::descriptor::{
     Collection, ContentError as CommonContentError, Identifier, IdentifierRequirement, Status,
     Version, VersionReq,
 };
+use melodium_engine::lint::LintWarning;
 #[cfg(feature = "script")]
 use melodium_lang::{error::ScriptErrors, ScriptError};
 use std::{
@@ -97,6 +98,14 @@ impl Content {
         }
     }
 
+    pub fn lint(&self) -> Vec<LintWarning> {
+        match &self.content {
+            #[cfg(feature = "script")]
+            ContentType::Script(script) => script.lint(),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn try_lock(&'_ self) -> Result<MutexGuard<'_, ()>, ()> {
         match self.descriptors_building.try_lock() {
             Ok(guard) => Ok(guard),
@@ -1,6 +1,8 @@
 use melodium_common::descriptor::{
-    Collection, Entry, Identifier, IdentifierRequirement, Model, Treatment, Version, VersionReq,
+    Collection, Constant, Data, Entry, Function, Identifier, IdentifierRequirement, Model,
+    Treatment, Version, VersionReq,
 };
+use melodium_engine::lint::LintWarning;
 pub use melodium_lang::ScriptResult;
 use melodium_lang::{semantic::Tree as SemanticTree, text::Script as TextScript, Path};
 use std::{
@@ -29,8 +31,8 @@ impl Script {
         version: &Version,
         dependencies_versions: &HashMap<String, VersionReq>,
     ) -> ScriptResult<Self> {
-        match TextScript::build(&text) {
-            Ok(text) => SemanticTree::new(text, version.clone())
+        TextScript::build(&text).and_then(|text| {
+            SemanticTree::new(text, version.clone())
                 .and_then(|tree| {
                     tree.make_references(
                         &Path::new(
@@ -51,9 +53,8 @@ impl Script {
                         semantic: tree,
                         build_level: Mutex::new(ScriptBuildLevel::None),
                     })
-                }),
-            Err(err) => ScriptResult::new_failure(err),
-        }
+                })
+        })
     }
 
     pub fn build_level(&self) -> ScriptBuildLevel {
@@ -82,6 +83,23 @@ impl Script {
     pub fn provide(&self) -> Vec<Identifier> {
         let mut identifiers = Vec::new();
 
+        for (_, constant) in &self.semantic.script.read().unwrap().constants {
+            let constant = constant.read().unwrap();
+            identifiers.push(constant.identifier.as_ref().unwrap().clone());
+        }
+
+        for (_, data) in &self.semantic.script.read().unwrap().data {
+            let data = data.read().unwrap();
+            let identifier = data.identifier.as_ref().unwrap();
+            identifiers.extend(data.functions_identifiers(identifier));
+            identifiers.push(identifier.clone());
+        }
+
+        for (_, function) in &self.semantic.script.read().unwrap().functions {
+            let function = function.read().unwrap();
+            identifiers.push(function.identifier.as_ref().unwrap().clone());
+        }
+
         for (_, model) in &self.semantic.script.read().unwrap().models {
             let model = model.read().unwrap();
             identifiers.push(model.identifier.as_ref().unwrap().clone());
@@ -95,9 +113,96 @@ impl Script {
         identifiers
     }
 
+    pub fn lint(&self) -> Vec<LintWarning> {
+        self.semantic.script.read().unwrap().lint(&self.path)
+    }
+
     pub fn make_descriptors(&self, collection: &mut Collection) -> ScriptResult<()> {
         let mut result = ScriptResult::new_success(());
 
+        // Data may have fields of other data declared in the same script, so descriptors are made
+        // as long as some get available, and only remaining failures are reported.
+        let mut pending: Vec<_> = self
+            .semantic
+            .script
+            .read()
+            .unwrap()
+            .data
+            .values()
+            .cloned()
+            .collect();
+        loop {
+            let pending_count = pending.len();
+            let mut failures = Vec::new();
+            for data in std::mem::take(&mut pending) {
+                let status = data.read().unwrap().make_descriptor(collection);
+                if status.is_success() {
+                    if let Some(descriptor) = result.merge_degrade_failure(status) {
+                        for function in descriptor.functions() {
+                            collection.insert(Entry::Function(function as Arc<dyn Function>));
+                        }
+                        collection.insert(Entry::Data(descriptor as Arc<dyn Data>));
+                    }
+                } else {
+                    failures.push(status);
+                    pending.push(data);
+                }
+            }
+
+            if pending.is_empty() {
+                break;
+            } else if pending.len() == pending_count {
+                for failure in failures {
+                    result.merge_degrade_failure(failure);
+                }
+                break;
+            }
+        }
+
+        // Constants may refer to other constants of the same script, so they are made the same way.
+        let mut pending: Vec<_> = self
+            .semantic
+            .script
+            .read()
+            .unwrap()
+            .constants
+            .values()
+            .cloned()
+            .collect();
+        loop {
+            let pending_count = pending.len();
+            let mut failures = Vec::new();
+            for constant in std::mem::take(&mut pending) {
+                let status = constant.read().unwrap().make_descriptor(collection);
+                if status.is_success() {
+                    if let Some(descriptor) = result.merge_degrade_failure(status) {
+                        collection.insert(Entry::Constant(descriptor as Arc<dyn Constant>));
+                    }
+                } else {
+                    failures.push(status);
+                    pending.push(constant);
+                }
+            }
+
+            if pending.is_empty() {
+                break;
+            } else if pending.len() == pending_count {
+                for failure in failures {
+                    result.merge_degrade_failure(failure);
+                }
+                break;
+            }
+        }
+
+        for (_, function) in &self.semantic.script.read().unwrap().functions {
+            let function = function.read().unwrap();
+            if let Some(function) =
+                result.merge_degrade_failure(function.make_descriptor(collection))
+            {
+                collection.insert(Entry::Function(function as Arc<dyn Function>));
+            }
+        }
+
         for (_, model) in &self.semantic.script.read().unwrap().models {
             let model = model.read().unwrap();
             if let Some(model) = result.merge_degrade_failure(model.make_descriptor(collection)) {
@@ -124,6 +229,11 @@ impl Script {
     pub fn make_design(&self, collection: &Arc<Collection>) -> ScriptResult<()> {
         let mut result = ScriptResult::new_success(());
 
+        for (_, function) in &self.semantic.script.read().unwrap().functions {
+            let function = function.read().unwrap();
+            result = result.and_degrade_failure(function.make_design(collection));
+        }
+
         for (_, model) in &self.semantic.script.read().unwrap().models {
             let model = model.read().unwrap();
             result = result.and_degrade_failure(model.make_design(collection));
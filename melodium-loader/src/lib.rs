@@ -12,6 +12,7 @@ pub const TRIPLE: &str = env!("TARGET");
 pub const LIB_ROOT_FILENAME: &str = "lib-root.mel";
 
 pub use compo::Compo;
+pub use content::ContentError;
 pub use loader::Loader;
 pub use loading_config::LoadingConfig;
 pub use package::PackageInfo;
@@ -5,6 +5,7 @@ use melodium_common::descriptor::{
     Collection, Identifier, IdentifierRequirement, LoadingError, LoadingResult,
     Package as CommonPackage, PackageRequirement,
 };
+use melodium_engine::lint::LintWarning;
 use semver::Version;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, OnceLock, RwLock};
@@ -110,6 +111,16 @@ impl PackageInfo for CorePackage {
         static MAP: OnceLock<HashMap<String, Identifier>> = OnceLock::new();
         MAP.get_or_init(|| HashMap::new())
     }
+
+    fn lint(&self) -> Vec<LintWarning> {
+        let contents = self.contents.read().unwrap();
+        let mut contents = contents.iter().collect::<Vec<_>>();
+        contents.sort_by_key(|(path, _)| *path);
+        contents
+            .into_iter()
+            .flat_map(|(_, content)| content.lint())
+            .collect()
+    }
 }
 
 impl PackageTrait for CorePackage {
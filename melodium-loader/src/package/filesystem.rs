@@ -7,6 +7,7 @@ use melodium_common::descriptor::{
     Collection, Identifier, IdentifierRequirement, LoadingError, LoadingResult, PackageRequirement,
     Version, VersionReq,
 };
+use melodium_engine::lint::LintWarning;
 use std::collections::HashMap;
 use std::fs::{metadata, read, read_to_string};
 use std::path::{Path, PathBuf};
@@ -224,6 +225,16 @@ impl PackageInfo for FsPackage {
     fn entrypoints(&self) -> &HashMap<String, Identifier> {
         &self.entrypoints
     }
+
+    fn lint(&self) -> Vec<LintWarning> {
+        let contents = self.contents.read().unwrap();
+        let mut contents = contents.iter().collect::<Vec<_>>();
+        contents.sort_by_key(|(path, _)| *path);
+        contents
+            .into_iter()
+            .flat_map(|(_, content)| content.lint())
+            .collect()
+    }
 }
 
 impl PackageTrait for FsPackage {
@@ -6,6 +6,7 @@ use melodium_common::descriptor::{
     Collection, Identifier, IdentifierRequirement, LoadingError, LoadingResult, PackageRequirement,
     Version,
 };
+use melodium_engine::lint::LintWarning;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -129,6 +130,15 @@ impl PackageInfo for MappedPackage {
     fn entrypoints(&self) -> &HashMap<String, Identifier> {
         &self.entrypoints
     }
+
+    fn lint(&self) -> Vec<LintWarning> {
+        let mut contents = self.contents.iter().collect::<Vec<_>>();
+        contents.sort_by_key(|(path, _)| *path);
+        contents
+            .into_iter()
+            .flat_map(|(_, content)| content.lint())
+            .collect()
+    }
 }
 
 impl PackageTrait for MappedPackage {
@@ -3,6 +3,7 @@ use core::fmt::Debug;
 use melodium_common::descriptor::{
     Collection, Identifier, IdentifierRequirement, LoadingResult, PackageRequirement,
 };
+use melodium_engine::lint::LintWarning;
 use semver::Version;
 use std::{collections::HashMap, sync::Arc};
 
@@ -65,6 +66,18 @@ impl PackageInfo for Package {
             Package::Jeu(pkg) => pkg.entrypoints(),
         }
     }
+
+    fn lint(&self) -> Vec<LintWarning> {
+        match self {
+            Package::Core(pkg) => pkg.lint(),
+            Package::Raw(pkg) => pkg.lint(),
+            Package::Map(pkg) => pkg.lint(),
+            #[cfg(feature = "filesystem")]
+            Package::Fs(pkg) => pkg.lint(),
+            #[cfg(feature = "jeu")]
+            Package::Jeu(pkg) => pkg.lint(),
+        }
+    }
 }
 
 impl PackageTrait for Package {
@@ -138,6 +151,10 @@ pub trait PackageInfo: Debug {
     fn version(&self) -> &Version;
     fn requirements(&self) -> &Vec<PackageRequirement>;
     fn entrypoints(&self) -> &HashMap<String, Identifier>;
+    /**
+     * Gives lint warnings about the package contents loaded so far.
+     */
+    fn lint(&self) -> Vec<LintWarning>;
 }
 
 pub trait PackageTrait: Debug + PackageInfo {
@@ -6,6 +6,7 @@ use melodium_common::descriptor::{
     Collection, Identifier, IdentifierRequirement, LoadingError, LoadingResult, PackageRequirement,
     VersionReq,
 };
+use melodium_engine::lint::LintWarning;
 use semver::Version;
 use std::{collections::HashMap, sync::Arc};
 
@@ -149,6 +150,10 @@ impl PackageInfo for RawPackage {
     fn entrypoints(&self) -> &HashMap<String, Identifier> {
         &self.entrypoints
     }
+
+    fn lint(&self) -> Vec<LintWarning> {
+        self.content.lint()
+    }
 }
 
 impl PackageTrait for RawPackage {
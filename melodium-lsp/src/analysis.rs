@@ -0,0 +1,112 @@
+use crate::document::Document;
+use crate::package::Package;
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range, Url};
+use melodium::LoadingConfig;
+use melodium_common::descriptor::{Collection, LoadingError, Status};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Result of the analysis of a document.
+pub struct Analysis {
+    /// Diagnostics for each file concerned by the analysis.
+    pub diagnostics: HashMap<Url, Vec<Diagnostic>>,
+    /// Collection of every loaded element, if loading succeeded.
+    pub collection: Option<Arc<Collection>>,
+}
+
+/// Analyses the file, through its whole package if it belongs to one.
+///
+/// `documents` are the open documents, whose content takes precedence over the one on disk.
+pub fn analyse(file: &Path, documents: &HashMap<PathBuf, String>) -> Analysis {
+    let package = Package::find(file);
+
+    let mut config = LoadingConfig::new();
+    let result = match &package {
+        Some(package) => {
+            config
+                .search_locations
+                .extend(package.root.parent().map(|parent| parent.to_path_buf()));
+
+            melodium::load_mapped_all_elements(package.mapping(documents), config)
+        }
+        None => {
+            config
+                .search_locations
+                .extend(file.parent().map(|parent| parent.to_path_buf()));
+
+            let text = documents
+                .get(file)
+                .cloned()
+                .or_else(|| fs::read_to_string(file).ok())
+                .unwrap_or_default();
+
+            melodium::load_raw_all_elements(Arc::new(text.into_bytes()), config)
+        }
+    };
+
+    let (collection, failure, errors) = match result {
+        Status::Success { success, errors } => (Some(success.1), None, errors),
+        Status::Failure { failure, errors } => (None, Some(failure), errors),
+    };
+
+    let mut diagnostics = HashMap::new();
+    if let Ok(url) = Url::from_file_path(file) {
+        diagnostics.insert(url, Vec::new());
+    }
+
+    for error in errors.iter().chain(failure.iter()) {
+        let (error_file, diagnostic) = diagnostic(error, file, package.as_ref(), documents);
+        if let Ok(url) = Url::from_file_path(error_file) {
+            let file_diagnostics = diagnostics.entry(url).or_insert_with(Vec::new);
+            // Same error can be reported through several loading steps.
+            if !file_diagnostics.contains(&diagnostic) {
+                file_diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    Analysis {
+        diagnostics,
+        collection,
+    }
+}
+
+/// Makes diagnostic from loading error, giving the file it concerns.
+fn diagnostic(
+    error: &LoadingError,
+    file: &Path,
+    package: Option<&Package>,
+    documents: &HashMap<PathBuf, String>,
+) -> (PathBuf, Diagnostic) {
+    let loading_diagnostic = melodium::diagnostic::Diagnostic::from_loading_error(error);
+
+    let error_file = match (&loading_diagnostic.content, package) {
+        (Some(content), Some(package)) => package.content_file(content),
+        _ => file.to_path_buf(),
+    };
+
+    let range = match &loading_diagnostic.range {
+        Some(range) => {
+            let text = documents
+                .get(&error_file)
+                .cloned()
+                .or_else(|| fs::read_to_string(&error_file).ok())
+                .unwrap_or_default();
+            Document { text, script: None }.diagnostic_range(range)
+        }
+        None => Range::new(Position::new(0, 0), Position::new(0, 0)),
+    };
+
+    let diagnostic = Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(loading_diagnostic.id)),
+        source: Some("melodium".to_string()),
+        message: loading_diagnostic.message,
+        ..Default::default()
+    };
+
+    (error_file, diagnostic)
+}
@@ -0,0 +1,128 @@
+use lsp_types::{Position as LspPosition, Range};
+use melodium_lang::text::{get_words, Kind, Position, PositionnedString, Script, Word};
+
+/// Document opened in editor.
+pub struct Document {
+    pub text: String,
+    /// Last script that could be parsed without error, kept to navigate through document while it is being edited.
+    pub script: Option<Script>,
+}
+
+impl Document {
+    pub fn new(text: String) -> Self {
+        let mut document = Self { text, script: None };
+        document.parse();
+        document
+    }
+
+    pub fn update(&mut self, text: String) {
+        self.text = text;
+        self.parse();
+    }
+
+    fn parse(&mut self) {
+        if let Some(script) = Script::build(&self.text).to_success() {
+            self.script = Some(script);
+        }
+    }
+
+    /// Converts Mélodium position (lines starting at 1, bytes within line) into LSP position (lines starting at 0, UTF-16 units within line).
+    pub fn lsp_position(&self, position: &Position) -> LspPosition {
+        let line_index = position.line_number.saturating_sub(1);
+        let line = self.text.lines().nth(line_index).unwrap_or_default();
+        let byte = position.line_position.min(line.len());
+        let character = line
+            .get(..byte)
+            .map(|before| before.encode_utf16().count())
+            .unwrap_or(byte);
+
+        LspPosition::new(line_index as u32, character as u32)
+    }
+
+    /// Converts range of Mélodium diagnostic (lines and columns starting at 1) into LSP range.
+    pub fn diagnostic_range(&self, range: &melodium::diagnostic::Range) -> Range {
+        let position = |position: &melodium::diagnostic::Position| Position {
+            absolute_position: 0,
+            line_number: position.line,
+            line_position: position.column.saturating_sub(1),
+        };
+
+        Range::new(
+            self.lsp_position(&position(&range.start)),
+            self.lsp_position(&position(&range.end)),
+        )
+    }
+
+    /// Gives the range covered by the text.
+    pub fn range(&self, text: &PositionnedString) -> Range {
+        let start = self.lsp_position(&text.position);
+
+        let end = match text.string.rfind('\n') {
+            Some(last_line_start) => Position {
+                absolute_position: text.position.absolute_position + text.string.len(),
+                line_number: text.position.line_number + text.string.matches('\n').count(),
+                line_position: text.string.len() - last_line_start - 1,
+            },
+            None => Position {
+                absolute_position: text.position.absolute_position + text.string.len(),
+                line_number: text.position.line_number,
+                line_position: text.position.line_position + text.string.len(),
+            },
+        };
+
+        Range::new(start, self.lsp_position(&end))
+    }
+
+    /// Converts LSP position into byte offset within text.
+    pub fn offset(&self, position: &LspPosition) -> Option<usize> {
+        let mut offset = 0;
+        for (index, line) in self.text.split_inclusive('\n').enumerate() {
+            if index == position.line as usize {
+                let mut units = 0;
+                for (byte, character) in line.char_indices() {
+                    if units >= position.character as usize {
+                        return Some(offset + byte);
+                    }
+                    units += character.len_utf16();
+                }
+                return Some(offset + line.trim_end_matches(['\r', '\n']).len());
+            }
+            offset += line.len();
+        }
+
+        if position.line as usize == self.text.split_inclusive('\n').count() {
+            Some(self.text.len())
+        } else {
+            None
+        }
+    }
+
+    /// Gives the meaningful words of the document, ignoring comments.
+    ///
+    /// Words are given up to the first one that cannot be recognized.
+    pub fn words(&self) -> Vec<Word> {
+        get_words(&self.text)
+            .unwrap_or_else(|words| words)
+            .into_iter()
+            .filter(|word| word.kind != Some(Kind::Comment))
+            .collect()
+    }
+
+    /// Gives the index of the word at position, along with all the words of the document.
+    pub fn word_at(&self, position: &LspPosition) -> Option<(usize, Vec<Word>)> {
+        let offset = self.offset(position)?;
+        let words = self.words();
+
+        words
+            .iter()
+            .position(|word| {
+                word.position.absolute_position <= offset
+                    && offset <= word.position.absolute_position + word.text.len()
+                    && matches!(
+                        word.kind,
+                        Some(Kind::Name) | Some(Kind::Context) | Some(Kind::Function)
+                    )
+            })
+            .map(|index| (index, words))
+    }
+}
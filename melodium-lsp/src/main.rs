@@ -0,0 +1,20 @@
+mod analysis;
+mod document;
+mod navigation;
+mod package;
+mod server;
+
+use lsp_server::Connection;
+use server::Server;
+
+fn main() -> anyhow::Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(Server::capabilities())?;
+    connection.initialize(capabilities)?;
+
+    Server::new(connection)?.run()?;
+
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,451 @@
+use crate::document::Document;
+use crate::package::{standalone_name, Package};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, DocumentSymbol, Hover, HoverContents, Location,
+    MarkupContent, MarkupKind, Position, SymbolKind, Url,
+};
+use melodium_common::descriptor::{Collection, Entry, Treatment};
+use melodium_lang::text::{Instanciation, Kind, Parameter, PositionnedString, Script, Use, Word};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Element designated by a name within a script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Designation {
+    pub path: Vec<String>,
+    pub name: String,
+}
+
+/// Context of a document to navigate through.
+pub struct Context<'a> {
+    pub file: &'a Path,
+    pub document: &'a Document,
+    pub package: Option<Package>,
+    pub script_path: Vec<String>,
+    pub collection: &'a Arc<Collection>,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(file: &'a Path, document: &'a Document, collection: &'a Arc<Collection>) -> Self {
+        let package = Package::find(file);
+        let script_path = package
+            .as_ref()
+            .and_then(|package| package.script_path(file))
+            .or_else(|| standalone_name(&document.text).map(|name| vec![name]))
+            .unwrap_or_else(|| {
+                vec![file
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default()]
+            });
+
+        Self {
+            file,
+            document,
+            package,
+            script_path,
+            collection,
+        }
+    }
+
+    /// Gives hover information for the word at position.
+    pub fn hover(&self, position: &Position) -> Option<Hover> {
+        let (index, words) = self.document.word_at(position)?;
+        let word = &words[index];
+
+        let contents = match port_context(&words, index) {
+            Some((instance, destination)) => {
+                let treatment = self.treatment(instance, word.position.absolute_position)?;
+                let (inputs, outputs) = (treatment.inputs(), treatment.outputs());
+                let description = if destination == (instance.text == "Self") {
+                    outputs
+                        .get(&word.text)
+                        .map(|output| format!("output {output}"))
+                } else {
+                    inputs.get(&word.text).map(|input| format!("input {input}"))
+                }?;
+
+                format!("```melodium\n{description}\n```")
+            }
+            None => {
+                let designation = self.resolve(&word.text, word.position.absolute_position)?;
+                let entry = self.entry(&designation)?;
+
+                let mut contents = format!("```melodium\n{}\n```", signature(&entry));
+                let documentation = documentation(&entry);
+                if !documentation.trim().is_empty() {
+                    contents.push_str("\n\n");
+                    contents.push_str(documentation);
+                }
+                contents
+            }
+        };
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: contents,
+            }),
+            range: Some(self.document.range(&word.into())),
+        })
+    }
+
+    /// Gives the location where the element at position is declared.
+    pub fn definition(
+        &self,
+        position: &Position,
+        documents: &HashMap<PathBuf, String>,
+    ) -> Option<Location> {
+        let (index, words) = self.document.word_at(position)?;
+        let word = &words[index];
+
+        let designation = self.resolve(&word.text, word.position.absolute_position)?;
+
+        let file = if designation.path == self.script_path {
+            self.file.to_path_buf()
+        } else {
+            let package = match &self.package {
+                Some(package) if package.name == designation.path[0] => Some(package.clone()),
+                Some(package) => package.sibling(&designation.path[0]),
+                None => self
+                    .file
+                    .parent()
+                    .and_then(|directory| Package::locate(directory, &designation.path[0])),
+            }?;
+            package.file(&designation.path)
+        };
+
+        let document = match documents.get(&file) {
+            Some(text) => Document::new(text.clone()),
+            None => Document::new(fs::read_to_string(&file).ok()?),
+        };
+        let name = declarations(document.script.as_ref()?)
+            .into_iter()
+            .find(|(name, _)| name.string == designation.name)
+            .map(|(name, _)| name)?;
+
+        Some(Location::new(
+            Url::from_file_path(&file).ok()?,
+            document.range(name),
+        ))
+    }
+
+    /// Gives inputs or outputs that can be used at position, if it follows `.` in a connection.
+    pub fn completion(&self, position: &Position) -> Option<Vec<CompletionItem>> {
+        let offset = self.document.offset(position)?;
+        let words: Vec<Word> = self
+            .document
+            .words()
+            .into_iter()
+            .filter(|word| word.position.absolute_position + word.text.len() <= offset)
+            .collect();
+
+        let dot = match words.last()?.kind {
+            Some(Kind::Dot) => words.len() - 1,
+            Some(Kind::Name)
+                if words.len() >= 2 && words[words.len() - 2].kind == Some(Kind::Dot) =>
+            {
+                words.len() - 2
+            }
+            _ => return None,
+        };
+        let (instance, destination) = connection_end(&words, dot)?;
+
+        let treatment = self.treatment(instance, offset)?;
+
+        let items = if destination == (instance.text == "Self") {
+            treatment
+                .outputs()
+                .values()
+                .map(|output| CompletionItem {
+                    label: output.name().to_string(),
+                    kind: Some(CompletionItemKind::FIELD),
+                    detail: Some(format!("output {output}")),
+                    ..Default::default()
+                })
+                .collect()
+        } else {
+            treatment
+                .inputs()
+                .values()
+                .map(|input| CompletionItem {
+                    label: input.name().to_string(),
+                    kind: Some(CompletionItemKind::FIELD),
+                    detail: Some(format!("input {input}")),
+                    ..Default::default()
+                })
+                .collect()
+        };
+
+        Some(items)
+    }
+
+    /// Gives the symbols declared in the document.
+    pub fn symbols(&self) -> Vec<DocumentSymbol> {
+        let script = match &self.document.script {
+            Some(script) => script,
+            None => return Vec::new(),
+        };
+
+        let mut symbols = Vec::new();
+        for (name, kind) in declarations(script) {
+            let children =
+                script
+                    .treatments
+                    .iter()
+                    .find(|treatment| &treatment.name == name && kind == SymbolKind::CLASS)
+                    .map(|treatment| {
+                        let mut children = Vec::new();
+                        children.extend(
+                            treatment.models.iter().map(|model| {
+                                self.instanciation_symbol(model, SymbolKind::PROPERTY)
+                            }),
+                        );
+                        children.extend(
+                            treatment
+                                .inputs
+                                .iter()
+                                .chain(treatment.outputs.iter())
+                                .map(|port| self.port_symbol(port)),
+                        );
+                        children.extend(treatment.treatments.iter().map(|instance| {
+                            self.instanciation_symbol(instance, SymbolKind::VARIABLE)
+                        }));
+                        children
+                    });
+
+            symbols.push(self.symbol(name, None, kind, children));
+        }
+
+        symbols
+    }
+
+    fn instanciation_symbol(
+        &self,
+        instanciation: &Instanciation,
+        kind: SymbolKind,
+    ) -> DocumentSymbol {
+        self.symbol(
+            &instanciation.name,
+            Some(instanciation.r#type.string.clone()),
+            kind,
+            None,
+        )
+    }
+
+    fn port_symbol(&self, port: &Parameter) -> DocumentSymbol {
+        self.symbol(
+            &port.name,
+            port.r#type.as_ref().map(|r#type| {
+                let mut text = r#type
+                    .level_structure
+                    .iter()
+                    .map(|level| format!("{}<", level.string))
+                    .collect::<String>();
+                text.push_str(&r#type.name.string);
+                text.push_str(&">".repeat(r#type.level_structure.len()));
+                text
+            }),
+            SymbolKind::FIELD,
+            None,
+        )
+    }
+
+    #[allow(deprecated)]
+    fn symbol(
+        &self,
+        name: &PositionnedString,
+        detail: Option<String>,
+        kind: SymbolKind,
+        children: Option<Vec<DocumentSymbol>>,
+    ) -> DocumentSymbol {
+        let range = self.document.range(name);
+        DocumentSymbol {
+            name: name.string.clone(),
+            detail,
+            kind,
+            tags: None,
+            deprecated: None,
+            range,
+            selection_range: range,
+            children,
+        }
+    }
+
+    /// Resolves the name used at offset in the script.
+    pub fn resolve(&self, name: &str, offset: usize) -> Option<Designation> {
+        let script = self.document.script.as_ref()?;
+
+        if let Some(treatment) = treatment_at(script, offset) {
+            if name == "Self" {
+                return Some(Designation {
+                    path: self.script_path.clone(),
+                    name: treatment.name.string.clone(),
+                });
+            }
+
+            if let Some(instanciation) = treatment
+                .treatments
+                .iter()
+                .chain(treatment.models.iter())
+                .find(|instanciation| instanciation.name.string == name)
+            {
+                return Some(self.resolve_global(script, &instanciation.r#type.string));
+            }
+        }
+
+        Some(self.resolve_global(script, name))
+    }
+
+    fn resolve_global(&self, script: &Script, name: &str) -> Designation {
+        match script
+            .uses
+            .iter()
+            .find(|r#use| r#use.r#as.as_ref().unwrap_or(&r#use.element).string == name)
+        {
+            Some(r#use) => Designation {
+                path: self.use_path(r#use),
+                name: r#use.element.string.clone(),
+            },
+            None => Designation {
+                path: self.script_path.clone(),
+                name: name.to_string(),
+            },
+        }
+    }
+
+    /// Gives the script path a `use` refers to, following the same rules as semantic analysis.
+    fn use_path(&self, r#use: &Use) -> Vec<String> {
+        let steps = r#use.path.iter().map(|step| step.string.clone());
+        match r#use.path.first().map(|root| root.string.as_str()) {
+            Some("root") => std::iter::once(self.script_path[0].clone())
+                .chain(steps.skip(1))
+                .collect(),
+            Some("local") => self
+                .script_path
+                .iter()
+                .cloned()
+                .chain(steps.skip(1))
+                .collect(),
+            _ => steps.collect(),
+        }
+    }
+
+    fn entry(&self, designation: &Designation) -> Option<Entry> {
+        let collection = self.collection;
+        collection
+            .identifiers()
+            .into_iter()
+            .find(|identifier| {
+                identifier.path() == &designation.path && identifier.name() == designation.name
+            })
+            .and_then(|identifier| collection.get(&(&identifier).into()).cloned())
+    }
+
+    fn treatment(&self, instance: &Word, offset: usize) -> Option<Arc<dyn Treatment>> {
+        match self.entry(&self.resolve(&instance.text, offset)?)? {
+            Entry::Treatment(treatment) => Some(treatment),
+            _ => None,
+        }
+    }
+}
+
+/// Gives the instance word and whether it is a connection destination, if word at index is a port name.
+fn port_context(words: &[Word], index: usize) -> Option<(&Word, bool)> {
+    if index >= 1 && words[index - 1].kind == Some(Kind::Dot) {
+        connection_end(words, index - 1)
+    } else {
+        None
+    }
+}
+
+/// Gives the instance word and whether it is a connection destination, for the dot at index.
+fn connection_end(words: &[Word], dot: usize) -> Option<(&Word, bool)> {
+    let instance = words.get(dot.checked_sub(1)?)?;
+    if instance.kind != Some(Kind::Name) {
+        return None;
+    }
+
+    let destination = dot >= 2 && words[dot - 2].kind == Some(Kind::RightArrow);
+
+    Some((instance, destination))
+}
+
+/// Gives every element declared in script, with its symbol kind.
+fn declarations(script: &Script) -> Vec<(&PositionnedString, SymbolKind)> {
+    let mut declarations = Vec::new();
+    declarations.extend(
+        script
+            .constants
+            .iter()
+            .map(|constant| (&constant.name, SymbolKind::CONSTANT)),
+    );
+    declarations.extend(
+        script
+            .data
+            .iter()
+            .map(|data| (&data.name, SymbolKind::STRUCT)),
+    );
+    declarations.extend(
+        script
+            .functions
+            .iter()
+            .map(|function| (&function.name, SymbolKind::FUNCTION)),
+    );
+    declarations.extend(
+        script
+            .models
+            .iter()
+            .map(|model| (&model.name, SymbolKind::OBJECT)),
+    );
+    declarations.extend(
+        script
+            .treatments
+            .iter()
+            .map(|treatment| (&treatment.name, SymbolKind::CLASS)),
+    );
+
+    declarations.sort_by_key(|(name, _)| name.position.absolute_position);
+    declarations
+}
+
+/// Gives the treatment whose declaration contains offset.
+fn treatment_at(script: &Script, offset: usize) -> Option<&melodium_lang::text::Treatment> {
+    let (name, kind) = declarations(script)
+        .into_iter()
+        .filter(|(name, _)| name.position.absolute_position <= offset)
+        .last()?;
+
+    if kind == SymbolKind::CLASS {
+        script
+            .treatments
+            .iter()
+            .find(|treatment| &treatment.name == name)
+    } else {
+        None
+    }
+}
+
+fn signature(entry: &Entry) -> String {
+    match entry {
+        Entry::Constant(constant) => constant.to_string(),
+        Entry::Context(context) => context.to_string(),
+        Entry::Data(data) => data.to_string(),
+        Entry::Function(function) => function.to_string(),
+        Entry::Model(model) => model.to_string(),
+        Entry::Treatment(treatment) => treatment.to_string(),
+    }
+}
+
+fn documentation(entry: &Entry) -> &str {
+    match entry {
+        Entry::Constant(constant) => constant.documentation(),
+        Entry::Context(context) => context.documentation(),
+        Entry::Data(data) => data.documentation(),
+        Entry::Function(function) => function.documentation(),
+        Entry::Model(model) => model.documentation(),
+        Entry::Treatment(treatment) => treatment.documentation(),
+    }
+}
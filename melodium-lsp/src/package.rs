@@ -0,0 +1,141 @@
+use melodium_loader::{Compo, LIB_ROOT_FILENAME};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Package a document belongs to, found through a `Compo.toml` file in its directory or any parent one.
+#[derive(Debug, Clone)]
+pub struct Package {
+    pub root: PathBuf,
+    pub name: String,
+}
+
+impl Package {
+    /// Looks for the package the file belongs to.
+    pub fn find(file: &Path) -> Option<Self> {
+        file.ancestors().skip(1).find_map(|directory| {
+            let composition = fs::read_to_string(directory.join("Compo.toml")).ok()?;
+            let compo = Compo::parse(&composition).to_success()?;
+            Some(Self {
+                root: directory.to_path_buf(),
+                name: compo.name,
+            })
+        })
+    }
+
+    /// Gives the package content, as expected by mapped package loading.
+    ///
+    /// Files in `overlays` take precedence over the ones on disk.
+    pub fn mapping(&self, overlays: &HashMap<PathBuf, String>) -> HashMap<String, Vec<u8>> {
+        let mut mapping = HashMap::new();
+        let mut directories = vec![self.root.clone()];
+
+        while let Some(directory) = directories.pop() {
+            let entries = match fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    directories.push(path);
+                } else if path.extension().map(|ext| ext == "mel").unwrap_or(false)
+                    || path == self.root.join("Compo.toml")
+                {
+                    let content = match overlays.get(&path) {
+                        Some(text) => text.clone().into_bytes(),
+                        None => match fs::read(&path) {
+                            Ok(content) => content,
+                            Err(_) => continue,
+                        },
+                    };
+                    if let Some(designation) = self.designation(&path) {
+                        mapping.insert(designation, content);
+                    }
+                }
+            }
+        }
+
+        for (path, text) in overlays {
+            if let Some(designation) = self.designation(path) {
+                mapping
+                    .entry(designation)
+                    .or_insert_with(|| text.clone().into_bytes());
+            }
+        }
+
+        mapping
+    }
+
+    /// Gives the path of the file relative to package root, using `/` as separator.
+    pub fn designation(&self, file: &Path) -> Option<String> {
+        file.strip_prefix(&self.root).ok().map(|relative| {
+            relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+    }
+
+    /// Gives the file of the package a script path refers to.
+    pub fn file(&self, script_path: &[String]) -> PathBuf {
+        if script_path.len() <= 1 {
+            self.root.join(LIB_ROOT_FILENAME)
+        } else {
+            let mut file = self.root.clone();
+            script_path[1..].iter().for_each(|step| file.push(step));
+            file.set_extension("mel");
+            file
+        }
+    }
+
+    /// Gives the script path of a file belonging to the package, as used in identifiers.
+    pub fn script_path(&self, file: &Path) -> Option<Vec<String>> {
+        let designation = self.designation(file)?;
+        let mut path = vec![self.name.clone()];
+
+        if designation != LIB_ROOT_FILENAME {
+            path.extend(
+                designation
+                    .strip_suffix(".mel")
+                    .unwrap_or(&designation)
+                    .split('/')
+                    .map(|step| step.to_string()),
+            );
+        }
+
+        Some(path)
+    }
+
+    /// Gives the file corresponding to the content path given within loading errors.
+    pub fn content_file(&self, content_path: &str) -> PathBuf {
+        match content_path.strip_prefix(&format!("{}/", self.name)) {
+            Some(designation) => self.root.join(designation),
+            None => self.root.join(LIB_ROOT_FILENAME),
+        }
+    }
+
+    /// Looks for a package placed next to this one.
+    pub fn sibling(&self, name: &str) -> Option<Self> {
+        Self::locate(self.root.parent()?, name)
+    }
+
+    /// Looks for the package named `name` within directory.
+    pub fn locate(directory: &Path, name: &str) -> Option<Self> {
+        let root = directory.join(name);
+        root.join("Compo.toml").is_file().then(|| Self {
+            root,
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Gives the package name declared in the header of a standalone script.
+pub fn standalone_name(text: &str) -> Option<String> {
+    text.lines()
+        .take_while(|line| line.starts_with("#!"))
+        .find_map(|line| line.strip_prefix("#! name = "))
+        .map(|name| name.trim().to_string())
+}
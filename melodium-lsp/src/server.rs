@@ -0,0 +1,261 @@
+use crate::analysis::analyse;
+use crate::document::Document;
+use crate::navigation::Context;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics, ShowMessage,
+};
+use lsp_types::request::{
+    Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as _,
+};
+use lsp_types::{
+    CompletionOptions, CompletionResponse, Diagnostic, DocumentSymbolResponse,
+    GotoDefinitionResponse, HoverProviderCapability, MessageType, OneOf, PublishDiagnosticsParams,
+    ServerCapabilities, ShowMessageParams, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TextDocumentSyncSaveOptions, Url,
+};
+use melodium::LoadingConfig;
+use melodium_common::descriptor::Collection;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub struct Server {
+    connection: Connection,
+    /// Documents currently opened in editor.
+    documents: HashMap<PathBuf, Document>,
+    /// Collection given by the last successful analysis of each document.
+    collections: HashMap<PathBuf, Arc<Collection>>,
+    /// Every compiled-in Mélodium standard library package, loaded once at startup
+    /// and used for documents that could not be analysed successfully yet.
+    core_collection: Arc<Collection>,
+    /// Files that got diagnostics published through the analysis of each document.
+    published: HashMap<PathBuf, HashSet<Url>>,
+}
+
+impl Server {
+    /// Creates server, loading core library packages.
+    ///
+    /// If they cannot be loaded, the error is shown to user and server keeps working with an empty collection,
+    /// so documents get only their own elements known until they are successfully analysed.
+    pub fn new(connection: Connection) -> anyhow::Result<Self> {
+        let core_collection = match melodium::load_all(LoadingConfig::new()).into_result() {
+            Ok((_, core_collection)) => core_collection,
+            Err(error) => {
+                connection
+                    .sender
+                    .send(Message::Notification(Notification::new(
+                        ShowMessage::METHOD.to_string(),
+                        ShowMessageParams {
+                            typ: MessageType::ERROR,
+                            message: format!(
+                                "Mélodium core library packages cannot be loaded: {error}"
+                            ),
+                        },
+                    )))?;
+                Arc::new(Collection::new())
+            }
+        };
+
+        Ok(Self {
+            connection,
+            documents: HashMap::new(),
+            collections: HashMap::new(),
+            core_collection,
+            published: HashMap::new(),
+        })
+    }
+
+    pub fn capabilities() -> ServerCapabilities {
+        ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Options(
+                TextDocumentSyncOptions {
+                    open_close: Some(true),
+                    change: Some(TextDocumentSyncKind::FULL),
+                    save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                    ..Default::default()
+                },
+            )),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            definition_provider: Some(OneOf::Left(true)),
+            completion_provider: Some(CompletionOptions {
+                trigger_characters: Some(vec![".".to_string()]),
+                ..Default::default()
+            }),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            ..Default::default()
+        }
+    }
+
+    pub fn run(mut self) -> anyhow::Result<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.request(request);
+                    self.connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => self.notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            HoverRequest::METHOD => respond::<HoverRequest>(request, |params| {
+                let position = params.text_document_position_params;
+                self.with_context(&position.text_document.uri, |context| {
+                    context.hover(&position.position)
+                })
+            }),
+            GotoDefinition::METHOD => respond::<GotoDefinition>(request, |params| {
+                let position = params.text_document_position_params;
+                let texts = self.texts();
+                self.with_context(&position.text_document.uri, |context| {
+                    context
+                        .definition(&position.position, &texts)
+                        .map(GotoDefinitionResponse::Scalar)
+                })
+            }),
+            Completion::METHOD => respond::<Completion>(request, |params| {
+                let position = params.text_document_position;
+                self.with_context(&position.text_document.uri, |context| {
+                    context
+                        .completion(&position.position)
+                        .map(CompletionResponse::Array)
+                })
+            }),
+            DocumentSymbolRequest::METHOD => respond::<DocumentSymbolRequest>(request, |params| {
+                self.with_context(&params.text_document.uri, |context| {
+                    Some(DocumentSymbolResponse::Nested(context.symbols()))
+                })
+            }),
+            _ => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request '{}'", request.method),
+            ),
+        }
+    }
+
+    fn notification(&mut self, notification: Notification) -> anyhow::Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = parameters::<DidOpenTextDocument>(notification.params)?;
+                if let Ok(file) = params.text_document.uri.to_file_path() {
+                    self.documents
+                        .insert(file.clone(), Document::new(params.text_document.text));
+                    self.analyse(&file)?;
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = parameters::<DidChangeTextDocument>(notification.params)?;
+                if let Ok(file) = params.text_document.uri.to_file_path() {
+                    if let (Some(document), Some(change)) = (
+                        self.documents.get_mut(&file),
+                        params.content_changes.into_iter().last(),
+                    ) {
+                        document.update(change.text);
+                        self.analyse(&file)?;
+                    }
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                let params = parameters::<DidSaveTextDocument>(notification.params)?;
+                if let Ok(file) = params.text_document.uri.to_file_path() {
+                    if self.documents.contains_key(&file) {
+                        self.analyse(&file)?;
+                    }
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = parameters::<DidCloseTextDocument>(notification.params)?;
+                if let Ok(file) = params.text_document.uri.to_file_path() {
+                    self.documents.remove(&file);
+                    self.collections.remove(&file);
+                    for url in self.published.remove(&file).unwrap_or_default() {
+                        self.publish(url, Vec::new())?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Analyses the document and publishes diagnostics for every file concerned.
+    fn analyse(&mut self, file: &Path) -> anyhow::Result<()> {
+        let analysis = analyse(file, &self.texts());
+
+        if let Some(collection) = analysis.collection {
+            self.collections.insert(file.to_path_buf(), collection);
+        }
+
+        for url in self.published.remove(file).unwrap_or_default() {
+            if !analysis.diagnostics.contains_key(&url) {
+                self.publish(url, Vec::new())?;
+            }
+        }
+
+        let mut published = HashSet::new();
+        for (url, diagnostics) in analysis.diagnostics {
+            published.insert(url.clone());
+            self.publish(url, diagnostics)?;
+        }
+        self.published.insert(file.to_path_buf(), published);
+
+        Ok(())
+    }
+
+    fn publish(&self, uri: Url, diagnostics: Vec<Diagnostic>) -> anyhow::Result<()> {
+        self.connection
+            .sender
+            .send(Message::Notification(Notification::new(
+                PublishDiagnostics::METHOD.to_string(),
+                PublishDiagnosticsParams::new(uri, diagnostics, None),
+            )))?;
+        Ok(())
+    }
+
+    /// Gives the content of every open document.
+    fn texts(&self) -> HashMap<PathBuf, String> {
+        self.documents
+            .iter()
+            .map(|(file, document)| (file.clone(), document.text.clone()))
+            .collect()
+    }
+
+    fn with_context<T>(&self, uri: &Url, op: impl FnOnce(&Context) -> Option<T>) -> Option<T> {
+        let file = uri.to_file_path().ok()?;
+        let document = self.documents.get(&file)?;
+        let collection = self.collections.get(&file).unwrap_or(&self.core_collection);
+
+        op(&Context::new(&file, document, collection))
+    }
+}
+
+fn parameters<N: lsp_types::notification::Notification>(
+    params: Value,
+) -> anyhow::Result<N::Params> {
+    Ok(serde_json::from_value(params)?)
+}
+
+fn respond<R: lsp_types::request::Request>(
+    request: Request,
+    op: impl FnOnce(R::Params) -> R::Result,
+) -> Response {
+    match serde_json::from_value::<R::Params>(request.params) {
+        Ok(params) => Response::new_ok(request.id, op(params)),
+        Err(error) => Response::new_err(
+            request.id,
+            ErrorCode::InvalidParams as i32,
+            error.to_string(),
+        ),
+    }
+}
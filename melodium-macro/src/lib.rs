@@ -1486,13 +1486,13 @@ pub fn mel_treatment(attr: TokenStream, item: TokenStream) -> TokenStream {
                         #borrow_outputs;
                         async move #body
                     };
-                    exec().await;
+                    let __result_status = melodium_core::common::executive::ResultStatus::from(exec().await);
 
                     #post_inputs;
                     #post_outputs;
 
                     debug_finish.await;
-                    melodium_core::common::executive::ResultStatus::Ok
+                    __result_status
                 }))]
             }
         };
@@ -1780,7 +1780,7 @@ pub fn mel_model(attr: TokenStream, item: TokenStream) -> TokenStream {
         .unwrap_or_else(|| String::from("()"))
         .parse()
         .unwrap();
-    let continuous: proc_macro2::TokenStream = continuous.iter().map(|c| format!("let auto_self = self.auto_reference.upgrade().unwrap(); self.world.add_continuous_task(Box::new(Box::pin(async move {{ auto_self.inner().{c}().await }})));")).collect::<Vec<_>>().join("").parse()
+    let continuous: proc_macro2::TokenStream = continuous.iter().map(|c| format!("let auto_self = self.auto_reference.upgrade().unwrap(); self.world.add_continuous_task(Box::new(Box::pin(async move {{ melodium_core::common::executive::ResultStatus::from(auto_self.inner().{c}().await) }})));")).collect::<Vec<_>>().join("").parse()
     .unwrap();
     let shutdown: proc_macro2::TokenStream = shutdown
         .map(|s| format!("self.model.{s}()"))
@@ -15,6 +15,7 @@ pub fn load_core_collection() -> Arc<Collection> {
 
 pub fn entry_kind(entry: &Entry) -> &'static str {
     match entry {
+        Entry::Constant(_) => "constant",
         Entry::Context(_) => "context",
         Entry::Data(_) => "data",
         Entry::Function(_) => "function",
@@ -25,6 +26,7 @@ pub fn entry_kind(entry: &Entry) -> &'static str {
 
 pub fn entry_documentation(entry: &Entry) -> &str {
     match entry {
+        Entry::Constant(c) => c.documentation(),
         Entry::Context(c) => c.documentation(),
         Entry::Data(d) => d.documentation(),
         Entry::Function(f) => f.documentation(),
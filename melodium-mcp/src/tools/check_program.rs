@@ -3,7 +3,11 @@ use melodium_common::descriptor::{Collection, Identifier, LoadingError, LoadingR
 use melodium_loader::PackageInfo;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, path::PathBuf, sync::Arc};
+use std::{
+    convert::TryFrom,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct CheckProgramRequest {
@@ -20,16 +24,72 @@ pub struct CheckProgramRequest {
 
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct Diagnostic {
+    /// Number of the loading error.
     pub id: u32,
+    /// Code of the error, such as `L0190`, `S0060` or `D0250`.
+    pub code: String,
+    /// File the error is located in, if known.
+    pub file: Option<String>,
+    pub range: Option<Range>,
     pub message: String,
 }
 
-impl From<&LoadingError> for Diagnostic {
-    fn from(error: &LoadingError) -> Self {
+/// Range of text within file, lines and columns starting at 1, end being exclusive.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Range {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Diagnostic {
+    fn new(
+        error: &LoadingError,
+        diagnostic: &melodium::diagnostic::Diagnostic,
+        program: &Path,
+    ) -> Self {
         Self {
             id: error.id,
-            message: error.kind.to_string(),
+            code: diagnostic.id.clone(),
+            file: diagnostic
+                .content
+                .as_deref()
+                .and_then(|content| melodium::diagnostic::content_file(program, content))
+                .map(|file| file.to_string_lossy().to_string()),
+            range: diagnostic.range.map(|range| Range {
+                start_line: range.start.line,
+                start_column: range.start.column,
+                end_line: range.end.line,
+                end_column: range.end.column,
+            }),
+            message: diagnostic.message.clone(),
+        }
+    }
+
+    /// Gives diagnostic of loading error, `program` being the file it was loaded from.
+    pub fn from_loading_error(error: &LoadingError, program: &Path) -> Self {
+        Self::new(
+            error,
+            &melodium::diagnostic::Diagnostic::from_loading_error(error),
+            program,
+        )
+    }
+
+    /// Gives diagnostics of every error within loading result, `program` being the file it was loaded from.
+    ///
+    /// Same errors reported through several loading steps are kept once.
+    pub fn from_result<T>(result: &LoadingResult<T>, program: &Path) -> Vec<Self> {
+        let mut diagnostics = Vec::new();
+        let mut seen = Vec::new();
+        for error in result.failure().into_iter().chain(result.errors().iter()) {
+            let diagnostic = melodium::diagnostic::Diagnostic::from_loading_error(error);
+            if !seen.contains(&diagnostic) {
+                diagnostics.push(Self::new(error, &diagnostic, program));
+                seen.push(diagnostic);
+            }
         }
+        diagnostics
     }
 }
 
@@ -57,10 +117,10 @@ pub fn check_program(request: CheckProgramRequest) -> CheckProgramResult {
             Err(_) => {
                 return CheckProgramResult {
                     success: false,
-                    errors: vec![Diagnostic {
-                        id: 0,
-                        message: format!("'{identifier}' is not a valid identifier"),
-                    }],
+                    errors: vec![Diagnostic::from_loading_error(
+                        &LoadingError::invalid_identifier(260, identifier.to_string()),
+                        Path::new(&request.path),
+                    )],
                     entrypoints: Vec::new(),
                 }
             }
@@ -70,23 +130,19 @@ pub fn check_program(request: CheckProgramRequest) -> CheckProgramResult {
         melodium::load_file(file, entrypoint, config)
     };
 
-    to_check_result(&result)
+    to_check_result(&result, Path::new(&request.path))
 }
 
 fn to_check_result(
     result: &LoadingResult<(Arc<dyn PackageInfo>, Arc<Collection>)>,
+    program: &Path,
 ) -> CheckProgramResult {
     let entrypoints = result
         .success()
         .map(|(pkg, _)| pkg.entrypoints().keys().cloned().collect())
         .unwrap_or_default();
 
-    let errors = result
-        .failure()
-        .into_iter()
-        .map(Diagnostic::from)
-        .chain(result.errors().iter().map(Diagnostic::from))
-        .collect();
+    let errors = Diagnostic::from_result(result, program);
 
     CheckProgramResult {
         success: result.is_success(),
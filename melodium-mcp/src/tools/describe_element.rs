@@ -37,6 +37,13 @@ pub enum ElementDetail {
         base_model: Option<String>,
         parameters: Vec<ParameterInfo>,
     },
+    Constant {
+        identifier: String,
+        documentation: String,
+        #[serde(rename = "type")]
+        type_: String,
+        value: String,
+    },
     Context {
         identifier: String,
         documentation: String,
@@ -174,6 +181,12 @@ pub fn describe_element(
                 )
             })),
         },
+        Entry::Constant(constant) => ElementDetail::Constant {
+            identifier: constant.identifier().to_string(),
+            documentation: constant.documentation().to_string(),
+            type_: constant.described_type().to_string(),
+            value: constant.value().to_string(),
+        },
         Entry::Context(context) => ElementDetail::Context {
             identifier: context.identifier().to_string(),
             documentation: context.documentation().to_string(),
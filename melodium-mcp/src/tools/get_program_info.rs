@@ -38,14 +38,9 @@ pub struct GetProgramInfoResult {
 
 pub fn get_program_info(request: GetProgramInfoRequest) -> GetProgramInfoResult {
     let file = PathBuf::from(&request.path);
-    let result = melodium::load_file_all_entrypoints(file, LoadingConfig::new());
+    let result = melodium::load_file_all_entrypoints(file.clone(), LoadingConfig::new());
 
-    let errors = result
-        .failure()
-        .into_iter()
-        .map(Diagnostic::from)
-        .chain(result.errors().iter().map(Diagnostic::from))
-        .collect();
+    let errors = Diagnostic::from_result(&result, &file);
 
     let entrypoints = result
         .success()
@@ -1,4 +1,4 @@
-use crate::{Context, Data, Function, Identifier, Model, Treatment};
+use crate::{Constant, Context, Data, Function, Identifier, Model, Treatment};
 use itertools::Itertools;
 use melodium_common::descriptor::{
     Collection as CommonCollection, Entry, Identified, Identifier as CommonIdentifier,
@@ -11,6 +11,7 @@ use std::collections::VecDeque;
 #[cfg_attr(feature = "webassembly", derive(tsify::Tsify))]
 #[cfg_attr(feature = "webassembly", tsify(into_wasm_abi, from_wasm_abi))]
 pub enum Element {
+    Constant(Constant),
     Context(Context),
     Data(Data),
     Function(Function),
@@ -21,6 +22,7 @@ pub enum Element {
 impl Element {
     pub fn identifier(&self) -> &Identifier {
         match self {
+            Element::Constant(c) => &c.identifier,
             Element::Context(c) => &c.identifier,
             Element::Data(d) => &d.identifier,
             Element::Function(f) => &f.identifier,
@@ -31,6 +33,7 @@ impl Element {
 
     pub fn is_compiled(&self) -> bool {
         match self {
+            Element::Constant(_) => false,
             Element::Context(_) => true,
             Element::Data(_) => true,
             Element::Function(_) => true,
@@ -43,6 +46,7 @@ impl Element {
 impl From<&Entry> for Element {
     fn from(value: &Entry) -> Self {
         match value {
+            Entry::Constant(c) => Element::Constant(c.as_ref().into()),
             Entry::Context(c) => Element::Context(c.as_ref().into()),
             Entry::Data(d) => Element::Data(d.as_ref().into()),
             Entry::Function(f) => Element::Function(f.as_ref().into()),
@@ -0,0 +1,60 @@
+use super::{Attributes, DescribedType, Identifier, RawValue, SharingError, SharingResult};
+use melodium_common::descriptor::{Collection, Constant as CommonConstant};
+use melodium_engine::descriptor::Constant as DesignedConstant;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "webassembly", derive(tsify::Tsify))]
+#[cfg_attr(feature = "webassembly", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct Constant {
+    pub identifier: Identifier,
+    pub documentation: String,
+    pub described_type: DescribedType,
+    pub value: RawValue,
+    pub attributes: Attributes,
+}
+
+impl Constant {
+    pub fn make_descriptor(&self, collection: &Collection) -> SharingResult<Arc<DesignedConstant>> {
+        let identifier = if let Ok(identifier) = (&self.identifier).try_into() {
+            identifier
+        } else {
+            return SharingResult::new_failure(SharingError::invalid_identifier(
+                18,
+                self.identifier.clone(),
+            ));
+        };
+
+        self.described_type
+            .to_described_type(collection, &identifier)
+            .and_then(|described_type| {
+                if let Some(value) = self.value.to_value(collection) {
+                    let mut descriptor =
+                        DesignedConstant::new(identifier.clone(), described_type, value);
+
+                    descriptor.set_documentation(&self.documentation);
+
+                    for (name, attribute) in &self.attributes.0 {
+                        descriptor.add_attribute(name.clone(), attribute.clone());
+                    }
+
+                    SharingResult::new_success(descriptor.commit())
+                } else {
+                    SharingResult::new_failure(SharingError::data_serialization_error(19))
+                }
+            })
+    }
+}
+
+impl From<&dyn CommonConstant> for Constant {
+    fn from(value: &dyn CommonConstant) -> Self {
+        Self {
+            identifier: value.identifier().into(),
+            documentation: value.documentation().to_string(),
+            described_type: value.described_type().into(),
+            value: value.value().into(),
+            attributes: value.attributes().into(),
+        }
+    }
+}
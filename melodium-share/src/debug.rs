@@ -1,6 +1,7 @@
 use super::Identifier;
 use crate::RawValue;
 use chrono::{DateTime, Utc};
+use melodium_common::executive::ResultStatus as CommonResultStatus;
 use melodium_engine::{
     build::{
         ContextualEnvironment as EngineContextualEnvironment, HostTreatment as EngineHostTreatment,
@@ -65,6 +66,10 @@ pub enum EventKind {
     TrackFinished {
         info: InfoTrack,
     },
+    Failure {
+        track_id: Option<u64>,
+        status: ResultStatus,
+    },
     TreatmentStarted {
         treatment: Identifier,
         host_treatment: HostTreatment,
@@ -159,6 +164,10 @@ impl From<&EngineEventKind> for EventKind {
             EngineEventKind::TrackFinished { info } => {
                 EventKind::TrackFinished { info: info.into() }
             }
+            EngineEventKind::Failure { track_id, status } => EventKind::Failure {
+                track_id: track_id.map(|id| id as u64),
+                status: status.into(),
+            },
             EngineEventKind::TreatmentStarted {
                 treatment,
                 host_treatment,
@@ -293,6 +302,26 @@ impl From<&EngineTrackResult> for TrackResult {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "webassembly", derive(tsify::Tsify))]
+#[cfg_attr(feature = "webassembly", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum ResultStatus {
+    Ok,
+    Error(String),
+    Fatal(String),
+}
+
+impl From<&CommonResultStatus> for ResultStatus {
+    fn from(status: &CommonResultStatus) -> Self {
+        match status {
+            CommonResultStatus::Ok => ResultStatus::Ok,
+            CommonResultStatus::Error(message) => ResultStatus::Error(message.clone()),
+            CommonResultStatus::Fatal(message) => ResultStatus::Fatal(message.clone()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "webassembly", derive(tsify::Tsify))]
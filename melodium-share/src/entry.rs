@@ -1,4 +1,4 @@
-use super::{Context, Data, Function, Identifier, Model, Treatment};
+use super::{Constant, Context, Data, Function, Identifier, Model, Treatment};
 use melodium_common::descriptor::Entry as CommonEntry;
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "webassembly", derive(tsify::Tsify))]
 #[cfg_attr(feature = "webassembly", tsify(into_wasm_abi, from_wasm_abi))]
 pub enum Entry {
+    Constant(Constant),
     Context(Context),
     Data(Data),
     Function(Function),
@@ -17,6 +18,7 @@ pub enum Entry {
 impl Entry {
     pub fn identifier(&self) -> &Identifier {
         match self {
+            Entry::Constant(element) => &element.identifier,
             Entry::Context(element) => &element.identifier,
             Entry::Data(element) => &element.identifier,
             Entry::Function(element) => &element.identifier,
@@ -29,6 +31,7 @@ impl Entry {
 impl From<&CommonEntry> for Entry {
     fn from(value: &CommonEntry) -> Self {
         match value {
+            CommonEntry::Constant(element) => Self::Constant((&**element).into()),
             CommonEntry::Context(element) => Self::Context((&**element).into()),
             CommonEntry::Data(element) => Self::Data((&**element).into()),
             CommonEntry::Function(element) => Self::Function((&**element).into()),
@@ -43,6 +46,7 @@ impl From<&CommonEntry> for Entry {
 #[cfg_attr(feature = "webassembly", derive(tsify::Tsify))]
 #[cfg_attr(feature = "webassembly", tsify(into_wasm_abi, from_wasm_abi))]
 pub enum EntryId {
+    Constant(Identifier),
     Context(Identifier),
     Data(Identifier),
     Function(Identifier),
@@ -53,6 +57,7 @@ pub enum EntryId {
 impl EntryId {
     pub fn identifier(&self) -> &Identifier {
         match self {
+            EntryId::Constant(id) => id,
             EntryId::Context(id) => id,
             EntryId::Data(id) => id,
             EntryId::Function(id) => id,
@@ -71,6 +76,7 @@ impl PartialOrd for EntryId {
 impl From<&CommonEntry> for EntryId {
     fn from(value: &CommonEntry) -> Self {
         match value {
+            CommonEntry::Constant(element) => Self::Constant(element.identifier().into()),
             CommonEntry::Context(element) => Self::Context(element.identifier().into()),
             CommonEntry::Data(element) => Self::Data(element.identifier().into()),
             CommonEntry::Function(element) => Self::Function(element.identifier().into()),
@@ -85,6 +91,7 @@ impl From<&CommonEntry> for EntryId {
 #[cfg_attr(feature = "webassembly", derive(tsify::Tsify))]
 #[cfg_attr(feature = "webassembly", tsify(into_wasm_abi, from_wasm_abi))]
 pub enum EntryKind {
+    Constant,
     Context,
     Data,
     Function,
@@ -95,6 +102,7 @@ pub enum EntryKind {
 impl From<&CommonEntry> for EntryKind {
     fn from(value: &CommonEntry) -> Self {
         match value {
+            CommonEntry::Constant(_) => Self::Constant,
             CommonEntry::Context(_) => Self::Context,
             CommonEntry::Data(_) => Self::Data,
             CommonEntry::Function(_) => Self::Function,
@@ -107,6 +115,7 @@ impl From<&CommonEntry> for EntryKind {
 impl From<&Entry> for EntryKind {
     fn from(value: &Entry) -> Self {
         match value {
+            Entry::Constant(_) => Self::Constant,
             Entry::Context(_) => Self::Context,
             Entry::Data(_) => Self::Data,
             Entry::Function(_) => Self::Function,
@@ -119,6 +128,7 @@ impl From<&Entry> for EntryKind {
 impl From<&EntryId> for EntryKind {
     fn from(value: &EntryId) -> Self {
         match value {
+            EntryId::Constant(_) => Self::Constant,
             EntryId::Context(_) => Self::Context,
             EntryId::Data(_) => Self::Data,
             EntryId::Function(_) => Self::Function,
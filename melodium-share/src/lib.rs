@@ -4,6 +4,7 @@
 mod attribute;
 mod collection;
 mod connection_design;
+mod constant;
 mod context;
 mod data;
 mod data_trait;
@@ -32,11 +33,15 @@ mod variability;
 pub use attribute::{Attribute, Attributes};
 pub use collection::{Collection, Element};
 pub use connection_design::{ConnectionDesign, IoDesign};
+pub use constant::Constant;
 pub use context::Context;
 pub use data::Data;
 pub use data_trait::DataTrait;
 pub use data_type::DataType;
-pub use debug::Event;
+pub use debug::{
+    ContextualEnvironment, DataContent, Event, EventKind, HostTreatment, InfoTrack, ResultStatus,
+    TrackCreation, TrackResult, TransmissionDetails,
+};
 pub use described_type::DescribedType;
 pub use entry::{Entry, EntryId, EntryKind};
 pub use error::{SharingError, SharingResult};
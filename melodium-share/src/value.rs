@@ -19,6 +19,7 @@ pub enum Value {
     Raw(RawValue),
     Array(Vec<Value>),
     Variable(String),
+    Constant(Identifier),
     Context(Identifier, String),
     Function(Identifier, BTreeMap<String, DescribedType>, Vec<Value>),
 }
@@ -51,6 +52,24 @@ impl Value {
             Value::Variable(var) => {
                 SharingResult::new_success(DesignedValue::Variable(var.clone()))
             }
+            Value::Constant(constant) => {
+                let constant: CommonIdentifier = if let Ok(identifier) = constant.try_into() {
+                    identifier
+                } else {
+                    return SharingResult::new_failure(SharingError::invalid_identifier(
+                        20,
+                        constant.clone(),
+                    ));
+                };
+                if let Some(CommonEntry::Constant(constant)) = collection.get(&(&constant).into()) {
+                    SharingResult::new_success(DesignedValue::Constant(Arc::clone(constant)))
+                } else {
+                    SharingResult::new_failure(
+                        LogicError::unexisting_constant(249, scope.clone(), constant.into(), None)
+                            .into(),
+                    )
+                }
+            }
             Value::Context(context, name) => {
                 let context: CommonIdentifier = if let Ok(identifier) = context.try_into() {
                     identifier
@@ -126,6 +145,7 @@ impl From<&DesignedValue> for Value {
             DesignedValue::Raw(val) => Value::Raw(val.into()),
             DesignedValue::Array(arr) => Value::Array(arr.iter().map(|v| v.into()).collect()),
             DesignedValue::Variable(var) => Value::Variable(var.clone()),
+            DesignedValue::Constant(constant) => Value::Constant(constant.identifier().into()),
             DesignedValue::Context(context, name) => {
                 Value::Context(context.identifier().into(), name.clone())
             }
@@ -0,0 +1,273 @@
+//! Structured diagnostics about programs, as given to tools and machine-readable outputs.
+//!
+//! [`Diagnostic`]s are made from loading errors and lint warnings, locating them within scripts
+//! whenever possible, and can be rendered as JSON through [`json`] or as SARIF through [`sarif`].
+
+use crate::VERSION;
+use melodium_common::descriptor::{Identifier, LoadingError, LoadingErrorKind, LoadingResult};
+use melodium_engine::lint::{Lint, LintKind, LintWarning};
+use melodium_lang::{error::ScriptErrorKind, text::PositionnedString};
+use melodium_loader::{ContentError, LIB_ROOT_FILENAME};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Severity of diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// Position within script, both line and column starting at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Range of text within script, `end` being exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl From<&PositionnedString> for Range {
+    fn from(text: &PositionnedString) -> Self {
+        let start = Position {
+            line: text.position.line_number,
+            column: text.position.line_position + 1,
+        };
+        let end = match text.string.rsplit_once('\n') {
+            Some((before, last)) => Position {
+                line: start.line + before.matches('\n').count() + 1,
+                column: last.len() + 1,
+            },
+            None => Position {
+                line: start.line,
+                column: start.column + text.string.len(),
+            },
+        };
+        Self { start, end }
+    }
+}
+
+/// Diagnostic about a program, being an error or a warning.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Code of the error or lint, such as `L0190`, `S0060`, `D0250`, or `W0001`.
+    pub id: String,
+    pub severity: Severity,
+    /// Content the diagnostic is located in, as named by loader (`<package>` or `<package>/<path>.mel`).
+    pub content: Option<String>,
+    pub range: Option<Range>,
+    pub message: String,
+    /// Name of the lint the diagnostic belongs to, if any.
+    pub lint: Option<Lint>,
+}
+
+impl Diagnostic {
+    /// Makes diagnostic from loading error, looking for the script and logic error it may contain.
+    pub fn from_loading_error(error: &LoadingError) -> Self {
+        if let LoadingErrorKind::ContentError { error } = &error.kind {
+            if let Some(ContentError::ScriptError { path, error }) =
+                error.downcast_ref::<ContentError>()
+            {
+                let (id, message) = match &error.kind {
+                    ScriptErrorKind::Logic { error } => {
+                        (format!("D{:04}", error.id), error.kind.to_string())
+                    }
+                    kind => (format!("S{:04}", error.id), kind.to_string()),
+                };
+                return Self {
+                    id,
+                    severity: Severity::Error,
+                    content: Some(path.clone()),
+                    range: error.positionned_string().as_ref().map(Range::from),
+                    message,
+                    lint: None,
+                };
+            }
+        }
+
+        Self {
+            id: format!("L{:04}", error.id),
+            severity: Severity::Error,
+            content: None,
+            range: None,
+            message: error.kind.to_string(),
+            lint: None,
+        }
+    }
+
+    /// Makes diagnostic from lint warning, reported with given severity.
+    pub fn from_lint(warning: &LintWarning, severity: Severity) -> Self {
+        let content = match &warning.kind {
+            LintKind::UnusedUse { script, .. } => script.clone(),
+            LintKind::UnusedParameter { identifier, .. }
+            | LintKind::UnconnectedOutputs { identifier, .. }
+            | LintKind::UnclosableStream { identifier, .. }
+            | LintKind::UnreachableTreatment { identifier } => content_of(identifier),
+        };
+
+        Self {
+            id: warning.lint().to_string(),
+            severity,
+            content: Some(content),
+            range: warning
+                .design_reference
+                .as_ref()
+                .and_then(|reference| {
+                    Arc::clone(reference)
+                        .downcast_arc::<PositionnedString>()
+                        .ok()
+                })
+                .map(|text| Range::from(&*text)),
+            message: warning.kind.to_string(),
+            lint: Some(warning.lint()),
+        }
+    }
+}
+
+/// Gives diagnostics of every error within loading result, same errors reported through several loading steps being kept once.
+pub fn from_result<T>(result: &LoadingResult<T>) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for error in result.failure().into_iter().chain(result.errors().iter()) {
+        let diagnostic = Diagnostic::from_loading_error(error);
+        if !diagnostics.contains(&diagnostic) {
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+/// Gives the file a content belongs to, `program` being the file package was loaded from.
+///
+/// Contents of `.jeu` files are not available as files, so `None` is returned for them.
+pub fn content_file(program: &Path, content: &str) -> Option<PathBuf> {
+    if program.extension().map(|ext| ext == "mel").unwrap_or(false) {
+        Some(program.to_path_buf())
+    } else if program.ends_with("Compo.toml") {
+        let root = program.parent().unwrap_or(Path::new(""));
+        Some(match content.split_once('/') {
+            Some((_, designation)) => root.join(designation),
+            None => root.join(LIB_ROOT_FILENAME),
+        })
+    } else {
+        None
+    }
+}
+
+/// Renders diagnostics as JSON document, `file` giving the file of each content.
+pub fn json(
+    diagnostics: &[Diagnostic],
+    success: bool,
+    file: impl Fn(&str) -> Option<PathBuf>,
+) -> String {
+    let diagnostics = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            json!({
+                "id": diagnostic.id,
+                "severity": diagnostic.severity.as_str(),
+                "lint": diagnostic.lint.map(|lint| lint.name()),
+                "content": diagnostic.content,
+                "file": diagnostic
+                    .content
+                    .as_deref()
+                    .and_then(&file)
+                    .map(|file| file.to_string_lossy().to_string()),
+                "range": diagnostic.range.map(|range| json!({
+                    "start": { "line": range.start.line, "column": range.start.column },
+                    "end": { "line": range.end.line, "column": range.end.column },
+                })),
+                "message": diagnostic.message,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_string_pretty(&json!({
+        "success": success,
+        "diagnostics": diagnostics,
+    }))
+    .unwrap_or_default()
+}
+
+/// Renders diagnostics as SARIF 2.1.0 log, `file` giving the file of each content.
+pub fn sarif(diagnostics: &[Diagnostic], file: impl Fn(&str) -> Option<PathBuf>) -> String {
+    let mut rules = BTreeMap::new();
+    for diagnostic in diagnostics {
+        rules.entry(diagnostic.id.clone()).or_insert_with(|| {
+            let mut rule = json!({ "id": diagnostic.id });
+            if let Some(lint) = diagnostic.lint {
+                rule["name"] = json!(lint.name());
+            }
+            rule
+        });
+    }
+
+    let results = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut result = json!({
+                "ruleId": diagnostic.id,
+                "level": diagnostic.severity.as_str(),
+                "message": { "text": diagnostic.message },
+            });
+
+            if let Some(content) = &diagnostic.content {
+                let uri = file(content)
+                    .map(|file| file.to_string_lossy().replace('\\', "/"))
+                    .unwrap_or_else(|| content.clone());
+                let mut location = json!({ "artifactLocation": { "uri": uri } });
+                if let Some(range) = diagnostic.range {
+                    location["region"] = json!({
+                        "startLine": range.start.line,
+                        "startColumn": range.start.column,
+                        "endLine": range.end.line,
+                        "endColumn": range.end.column,
+                    });
+                }
+                result["locations"] = json!([{ "physicalLocation": location }]);
+            }
+
+            result
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_string_pretty(&json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "melodium",
+                    "version": VERSION,
+                    "informationUri": "https://melodium.tech/",
+                    "rules": rules.into_values().collect::<Vec<Value>>(),
+                }
+            },
+            "results": results,
+        }],
+    }))
+    .unwrap_or_default()
+}
+
+/// Gives the content an element belongs to, as named by loader.
+fn content_of(identifier: &Identifier) -> String {
+    match identifier.path().as_slice() {
+        [root] => root.clone(),
+        path => format!("{}.mel", path.join("/")),
+    }
+}
@@ -0,0 +1,530 @@
+//! Interactive inspection of program execution, as done by `melodium run --inspect`.
+//!
+//! Inspection is driven through the [Debug Adapter Protocol](https://microsoft.github.io/debug-adapter-protocol/) over TCP,
+//! a single client being waited for before the program starts.
+//!
+//! Each track is exposed as a thread, and breakpoints are function breakpoints naming connections,
+//! as `<treatment>.<output> -> <treatment>.<input>`. Treatments are given by their label within host treatment
+//! or by their identifier, `*` or nothing matching any treatment or port, and the input side can be omitted.
+//! When data is about to pass a connection with breakpoint, its track is stopped and values can be looked at as variables.
+//! `next` resumes track and stops it again on the next data it transmits, while `continue` lets it run until next breakpoint.
+
+use crate::{format_log, LogFormat};
+use async_std::channel::{bounded, unbounded, Receiver, Sender};
+use async_std::io::{prelude::BufReadExt, BufReader, ReadExt, WriteExt};
+use async_std::net::{TcpListener, TcpStream};
+use async_std::task::JoinHandle;
+use async_trait::async_trait;
+use melodium_common::executive::{Level, Log, TrackId, TransmissionValue, Value};
+use melodium_engine::debug::{Event, EventKind, Inspector, TransmissionDetails};
+use serde_json::{json, Value as Json};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Maximal number of values kept from stopped transmission.
+const MAX_VALUES: usize = 1000;
+
+/// Side of connection breakpoint, `None` matching anything.
+#[derive(Debug, Clone, Default)]
+struct Side {
+    treatment: Option<String>,
+    port: Option<String>,
+}
+
+impl Side {
+    fn parse(text: &str) -> Self {
+        let text = text.trim();
+        let (treatment, port) = match text.rsplit_once('.') {
+            Some((treatment, port)) => (treatment, port),
+            None => (text, ""),
+        };
+        Self {
+            treatment: specific(treatment),
+            port: specific(port),
+        }
+    }
+
+    fn matches(&self, details: &TransmissionDetails) -> bool {
+        self.treatment
+            .as_ref()
+            .map(|treatment| {
+                *treatment == details.label
+                    || *treatment == details.treatment.identifier().to_string()
+            })
+            .unwrap_or(true)
+            && self
+                .port
+                .as_ref()
+                .map(|port| *port == details.name)
+                .unwrap_or(true)
+    }
+}
+
+fn specific(text: &str) -> Option<String> {
+    match text.trim() {
+        "" | "*" => None,
+        text => Some(text.to_string()),
+    }
+}
+
+/// Breakpoint on connections, as `<output side> -> <input side>`.
+#[derive(Debug, Clone)]
+struct Breakpoint {
+    id: i64,
+    output: Side,
+    input: Side,
+}
+
+impl Breakpoint {
+    fn parse(id: i64, name: &str) -> Self {
+        let (output, input) = match name.split_once("->") {
+            Some((output, input)) => (Side::parse(output), Side::parse(input)),
+            None => (Side::parse(name), Side::default()),
+        };
+        Self { id, output, input }
+    }
+
+    fn matches(&self, output: &TransmissionDetails, input: &TransmissionDetails) -> bool {
+        self.output.matches(output) && self.input.matches(input)
+    }
+}
+
+/// Transmission stopped, waiting to be resumed.
+#[derive(Debug)]
+struct Stopped {
+    track_id: TrackId,
+    connection: String,
+    values: Vec<Value>,
+    count: usize,
+    resume: Sender<()>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    attached: bool,
+    breakpoints: Vec<Breakpoint>,
+    /// Tracks to stop on their next transmission, with reason.
+    stopping: HashMap<TrackId, &'static str>,
+    stopped: BTreeMap<i64, Stopped>,
+    last_stopped_id: i64,
+    tracks: BTreeSet<TrackId>,
+}
+
+/// Debug adapter, inspecting transmissions on behalf of a client.
+#[derive(Debug)]
+pub struct DebugAdapter {
+    state: Mutex<State>,
+    messages: Sender<Json>,
+    seq: AtomicI64,
+    configured: Mutex<Option<Sender<()>>>,
+    writer: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl DebugAdapter {
+    /// Waits for a client to connect through `listener` and to be done with configuration.
+    ///
+    /// If client disconnects before, adapter is given detached, letting program run without inspection.
+    pub async fn accept(listener: TcpListener) -> std::io::Result<Arc<Self>> {
+        let (stream, _) = listener.accept().await?;
+
+        let (messages, messages_receiver) = unbounded();
+        let (configured, configured_receiver) = bounded(1);
+        let adapter = Arc::new(Self {
+            state: Mutex::new(State {
+                attached: true,
+                ..Default::default()
+            }),
+            messages,
+            seq: AtomicI64::new(1),
+            configured: Mutex::new(Some(configured)),
+            writer: Mutex::new(None),
+        });
+
+        let writer = stream.clone();
+        *adapter.writer.lock().unwrap() = Some(async_std::task::spawn(async move {
+            write_messages(writer, messages_receiver).await
+        }));
+        let reader = Arc::clone(&adapter);
+        async_std::task::spawn(async move { reader.read_messages(stream).await });
+
+        let _ = configured_receiver.recv().await;
+        Ok(adapter)
+    }
+
+    /// Follows debug events to expose tracks as threads, and logs as output,
+    /// until both receivers are closed, then tells client the program terminated.
+    pub async fn follow(&self, events: Receiver<Event>, logs: Receiver<Log>) {
+        let events = async {
+            while let Ok(event) = events.recv().await {
+                match event.kind {
+                    EventKind::TrackAdded { info, .. } => {
+                        self.state.lock().unwrap().tracks.insert(info.id);
+                        self.event(
+                            "thread",
+                            json!({ "reason": "started", "threadId": thread_id(info.id) }),
+                        );
+                    }
+                    EventKind::TrackFinished { info } => {
+                        self.state.lock().unwrap().tracks.remove(&info.id);
+                        self.event(
+                            "thread",
+                            json!({ "reason": "exited", "threadId": thread_id(info.id) }),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        };
+        let logs = async {
+            while let Ok(log) = logs.recv().await {
+                self.event(
+                    "output",
+                    json!({
+                        "category": if log.level == Level::Error { "stderr" } else { "stdout" },
+                        "output": format!("{}\n", format_log(&log, LogFormat::Text, false)),
+                    }),
+                );
+            }
+        };
+        futures::join!(events, logs);
+
+        self.event("terminated", json!({}));
+        self.detach();
+        self.messages.close();
+        let writer = self.writer.lock().unwrap().take();
+        if let Some(writer) = writer {
+            writer.await;
+        }
+    }
+
+    async fn read_messages(&self, stream: TcpStream) {
+        let mut reader = BufReader::new(stream);
+        while let Ok(Some(message)) = read_message(&mut reader).await {
+            if message["type"] == "request" {
+                self.request(&message);
+            }
+        }
+        self.detach();
+    }
+
+    fn request(&self, request: &Json) {
+        let command = request["command"].as_str().unwrap_or_default();
+        let arguments = &request["arguments"];
+        let thread = arguments["threadId"].as_i64().map(track_id);
+
+        let result = match command {
+            "initialize" => Ok(json!({
+                "supportsConfigurationDoneRequest": true,
+                "supportsFunctionBreakpoints": true,
+            })),
+            "launch" | "attach" | "setExceptionBreakpoints" => Ok(json!({})),
+            "setBreakpoints" => Ok(json!({
+                "breakpoints": arguments["breakpoints"]
+                    .as_array()
+                    .map(|breakpoints| breakpoints
+                        .iter()
+                        .map(|_| json!({
+                            "verified": false,
+                            "message": "Breakpoints are set on connections, as function breakpoints",
+                        }))
+                        .collect::<Vec<_>>())
+                    .unwrap_or_default(),
+            })),
+            "setFunctionBreakpoints" => {
+                let breakpoints = arguments["breakpoints"]
+                    .as_array()
+                    .map(|breakpoints| {
+                        breakpoints
+                            .iter()
+                            .enumerate()
+                            .map(|(id, breakpoint)| {
+                                Breakpoint::parse(
+                                    id as i64 + 1,
+                                    breakpoint["name"].as_str().unwrap_or_default(),
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                let body = json!({
+                    "breakpoints": breakpoints
+                        .iter()
+                        .map(|breakpoint| json!({ "id": breakpoint.id, "verified": true }))
+                        .collect::<Vec<_>>(),
+                });
+                self.state.lock().unwrap().breakpoints = breakpoints;
+                Ok(body)
+            }
+            "configurationDone" => {
+                self.configured.lock().unwrap().take();
+                Ok(json!({}))
+            }
+            "threads" => {
+                let state = self.state.lock().unwrap();
+                let tracks = state
+                    .tracks
+                    .iter()
+                    .copied()
+                    .chain(state.stopped.values().map(|stopped| stopped.track_id))
+                    .collect::<BTreeSet<_>>();
+                Ok(json!({
+                    "threads": tracks
+                        .into_iter()
+                        .map(|track_id| json!({ "id": thread_id(track_id), "name": format!("track {track_id}") }))
+                        .collect::<Vec<_>>(),
+                }))
+            }
+            "stackTrace" => {
+                let state = self.state.lock().unwrap();
+                let frames = state
+                    .stopped
+                    .iter()
+                    .filter(|(_, stopped)| Some(stopped.track_id) == thread)
+                    .map(|(id, stopped)| {
+                        json!({ "id": id, "name": stopped.connection, "line": 0, "column": 0 })
+                    })
+                    .collect::<Vec<_>>();
+                Ok(json!({ "totalFrames": frames.len(), "stackFrames": frames }))
+            }
+            "scopes" => {
+                let id = arguments["frameId"].as_i64().unwrap_or_default();
+                match self.state.lock().unwrap().stopped.get(&id) {
+                    Some(stopped) => Ok(json!({
+                        "scopes": [{
+                            "name": "Values",
+                            "variablesReference": id,
+                            "indexedVariables": stopped.values.len(),
+                            "expensive": false,
+                        }],
+                    })),
+                    None => Err("No such frame".to_string()),
+                }
+            }
+            "variables" => {
+                let id = arguments["variablesReference"].as_i64().unwrap_or_default();
+                match self.state.lock().unwrap().stopped.get(&id) {
+                    Some(stopped) => {
+                        let mut variables = stopped
+                            .values
+                            .iter()
+                            .enumerate()
+                            .map(|(index, value)| {
+                                json!({
+                                    "name": format!("[{index}]"),
+                                    "value": value.to_string(),
+                                    "type": value.datatype().to_string(),
+                                    "variablesReference": 0,
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        if stopped.count > stopped.values.len() {
+                            variables.push(json!({
+                                "name": "…",
+                                "value": format!("{} more values", stopped.count - stopped.values.len()),
+                                "variablesReference": 0,
+                            }));
+                        }
+                        Ok(json!({ "variables": variables }))
+                    }
+                    None => Err("No such variables".to_string()),
+                }
+            }
+            "continue" => match thread {
+                Some(track_id) => {
+                    self.resume(track_id, None);
+                    Ok(json!({ "allThreadsContinued": false }))
+                }
+                None => Err("Missing thread".to_string()),
+            },
+            "next" | "stepIn" | "stepOut" => match thread {
+                Some(track_id) => {
+                    self.resume(track_id, Some("step"));
+                    Ok(json!({}))
+                }
+                None => Err("Missing thread".to_string()),
+            },
+            "pause" => match thread {
+                Some(track_id) => {
+                    self.state
+                        .lock()
+                        .unwrap()
+                        .stopping
+                        .insert(track_id, "pause");
+                    Ok(json!({}))
+                }
+                None => Err("Missing thread".to_string()),
+            },
+            "disconnect" => {
+                self.detach();
+                Ok(json!({}))
+            }
+            command => Err(format!("Unsupported request '{command}'")),
+        };
+
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": command,
+            "success": result.is_ok(),
+        });
+        match result {
+            Ok(body) => response["body"] = body,
+            Err(message) => response["message"] = json!(message),
+        }
+        self.send(response);
+
+        if command == "initialize" {
+            self.event("initialized", json!({}));
+        }
+    }
+
+    /// Resumes every transmission stopped within track, making it stop again on next transmission if `stopping` is given.
+    fn resume(&self, track_id: TrackId, stopping: Option<&'static str>) {
+        let mut state = self.state.lock().unwrap();
+        state.stopped.retain(|_, stopped| {
+            if stopped.track_id == track_id {
+                stopped.resume.close();
+                false
+            } else {
+                true
+            }
+        });
+        if let Some(reason) = stopping {
+            state.stopping.insert(track_id, reason);
+        }
+    }
+
+    /// Stops inspecting, resuming everything.
+    fn detach(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.attached = false;
+        state.breakpoints.clear();
+        state.stopping.clear();
+        for stopped in std::mem::take(&mut state.stopped).into_values() {
+            stopped.resume.close();
+        }
+        self.configured.lock().unwrap().take();
+    }
+
+    fn event(&self, event: &str, body: Json) {
+        self.send(json!({ "type": "event", "event": event, "body": body }));
+    }
+
+    fn send(&self, mut message: Json) {
+        message["seq"] = json!(self.seq.fetch_add(1, Ordering::Relaxed));
+        let _ = self.messages.try_send(message);
+    }
+}
+
+#[async_trait]
+impl Inspector for DebugAdapter {
+    async fn transmission(
+        &self,
+        output: &TransmissionDetails,
+        input: &TransmissionDetails,
+        track_id: TrackId,
+        data: &TransmissionValue,
+    ) {
+        let resumed = {
+            let mut state = self.state.lock().unwrap();
+            if !state.attached {
+                return;
+            }
+
+            let (reason, breakpoint) = if let Some(reason) = state.stopping.remove(&track_id) {
+                (reason, None)
+            } else if let Some(breakpoint) = state
+                .breakpoints
+                .iter()
+                .find(|breakpoint| breakpoint.matches(output, input))
+            {
+                ("breakpoint", Some(breakpoint.id))
+            } else {
+                return;
+            };
+
+            let connection = format!(
+                "{}.{} -> {}.{}",
+                output.label, output.name, input.label, input.name
+            );
+            let mut values: Vec<Value> = data.clone().into();
+            let count = values.len();
+            values.truncate(MAX_VALUES);
+
+            let (resume, resumed) = bounded(1);
+            state.last_stopped_id += 1;
+            let id = state.last_stopped_id;
+            state.stopped.insert(
+                id,
+                Stopped {
+                    track_id,
+                    connection: connection.clone(),
+                    values,
+                    count,
+                    resume,
+                },
+            );
+
+            self.event(
+                "stopped",
+                json!({
+                    "reason": reason,
+                    "description": connection,
+                    "threadId": thread_id(track_id),
+                    "allThreadsStopped": false,
+                    "hitBreakpointIds": breakpoint.into_iter().collect::<Vec<_>>(),
+                }),
+            );
+
+            resumed
+        };
+
+        // Resumed once channel is closed.
+        let _ = resumed.recv().await;
+    }
+}
+
+/// Threads ids start at 1, as some clients consider 0 as no thread.
+fn thread_id(track_id: TrackId) -> i64 {
+    track_id as i64 + 1
+}
+
+fn track_id(thread_id: i64) -> TrackId {
+    (thread_id - 1).max(0) as TrackId
+}
+
+async fn read_message(reader: &mut BufReader<TcpStream>) -> std::io::Result<Option<Json>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        match line.trim_end().split_once(':') {
+            Some((name, value)) if name.eq_ignore_ascii_case("content-length") => {
+                length = value.trim().parse::<usize>().ok()
+            }
+            None if line.trim_end().is_empty() && length.is_some() => break,
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; length.unwrap_or_default()];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(serde_json::from_slice(&body).unwrap_or_default()))
+}
+
+async fn write_messages(mut stream: TcpStream, messages: Receiver<Json>) {
+    while let Ok(message) = messages.recv().await {
+        let body = message.to_string();
+        if stream
+            .write_all(format!("Content-Length: {}\r\n\r\n{body}", body.len()).as_bytes())
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+    let _ = stream.flush().await;
+}
@@ -21,26 +21,72 @@ use melodium_common::{
         Collection, Identifier, LoadingError, LoadingResult, Package, PackageRequirement,
         VersionReq,
     },
-    executive::{Level, Log, Value},
+    executive::{Level, Log, LogFilter, ResultStatus, Value},
 };
 use melodium_engine::{
-    debug::{DebugLevel, Event},
+    debug::{DebugLevel, Event, Inspector},
+    lint::{lint_package, LintWarning},
     LogicResult,
 };
 pub use melodium_loader::LoadingConfig;
 use melodium_loader::{Compo, Loader, PackageInfo};
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+pub mod diagnostic;
+#[cfg(not(target_os = "unknown"))]
+pub mod inspect;
+#[cfg(not(target_os = "unknown"))]
+pub mod metrics;
 #[cfg(feature = "fs")]
 pub mod new;
+#[cfg(not(target_os = "unknown"))]
+pub mod otlp;
+pub mod trace;
 
 pub const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 pub const TARGET: &'static str = env!("TARGET");
 pub const TARGET_FEATURES: &'static str = env!("TARGET_FEATURE");
 pub const BUILD_HOST: &'static str = env!("HOST");
 
+/// Exit code when execution succeeded.
+pub const EXIT_SUCCESS: i32 = 0;
+/// Exit code for general failures, such as invalid arguments or unreachable files.
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code when program cannot be loaded or built (syntax, semantic or design errors).
+pub const EXIT_LOADING_ERROR: i32 = 3;
+/// Exit code when program cannot be launched (logic errors, such as invalid parameters).
+pub const EXIT_LOGIC_ERROR: i32 = 4;
+/// Exit code when a track or continuous task reported failure during execution.
+pub const EXIT_EXECUTION_FAILURE: i32 = 5;
+
+/// Outcome of a program execution.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionOutcome {
+    /// Exit code requested by program, through `std/engine::exit`.
+    pub exit_code: Option<i32>,
+    /// Failures reported by tracks and continuous tasks.
+    pub failures: Vec<ResultStatus>,
+}
+
+impl ExecutionOutcome {
+    /// Gives the code the process should exit with.
+    ///
+    /// Non-zero code requested by program prevails, then `EXIT_EXECUTION_FAILURE` is given if any failure were reported,
+    /// so requesting `0` cannot hide failures.
+    /// Requested codes outside of `1..=255` cannot be given to the system untruncated, so `EXIT_EXECUTION_FAILURE` is given instead.
+    pub fn code(&self) -> i32 {
+        match self.exit_code {
+            Some(code @ 1..=255) => code,
+            Some(code) if code != EXIT_SUCCESS => EXIT_EXECUTION_FAILURE,
+            _ if !self.failures.is_empty() => EXIT_EXECUTION_FAILURE,
+            _ => EXIT_SUCCESS,
+        }
+    }
+}
+
 pub type LaunchedSignalFuture = Box<
     dyn FnOnce(
         Result<(), String>,
@@ -152,6 +198,29 @@ pub fn load_compo_force_entrypoint(
     })
 }
 
+/// Loads every element of the package described by `Compo.toml` content, as well as every element of core packages, regardless of entrypoints.
+pub fn load_compo_all_elements(
+    content: &str,
+    mut config: LoadingConfig,
+) -> LoadingResult<(Arc<dyn PackageInfo>, Arc<Collection>)> {
+    config.extend(core_config());
+
+    Compo::parse(content).and_then(|compo| {
+        let loader = Loader::new(config);
+        loader
+            .load_package(&PackageRequirement {
+                package: compo.name,
+                version_requirement: VersionReq::parse(&format!("={}", compo.version)).unwrap(),
+            })
+            .and_then(|pkg| loader.load_all().and(LoadingResult::new_success(pkg)))
+            .and_then(|pkg| {
+                loader
+                    .build()
+                    .and_then(|collection| LoadingResult::new_success((pkg, collection)))
+            })
+    })
+}
+
 pub fn load_raw(
     raw: Arc<Vec<u8>>,
     entrypoint: &str,
@@ -227,6 +296,44 @@ pub fn load_raw_force_entrypoint(
         })
 }
 
+/// Loads every element of the raw package, as well as every element of core packages, regardless of entrypoints.
+pub fn load_raw_all_elements(
+    raw: Arc<Vec<u8>>,
+    mut config: LoadingConfig,
+) -> LoadingResult<(Arc<dyn PackageInfo>, Arc<Collection>)> {
+    config.extend(core_config());
+
+    let loader = Loader::new(config);
+    loader
+        .load_raw(raw)
+        .and_then(|pkg| loader.load_all().and(LoadingResult::new_success(pkg)))
+        .and_then(|pkg| {
+            loader
+                .build()
+                .and_then(|collection| LoadingResult::new_success((pkg, collection)))
+        })
+}
+
+/// Loads every element of the mapped package, as well as every element of core packages, regardless of entrypoints.
+///
+/// Mapping is made of file paths relative to package root associated with their content, `Compo.toml` being required.
+pub fn load_mapped_all_elements(
+    mapped: HashMap<String, Vec<u8>>,
+    mut config: LoadingConfig,
+) -> LoadingResult<(Arc<dyn PackageInfo>, Arc<Collection>)> {
+    config.extend(core_config());
+
+    let loader = Loader::new(config);
+    loader
+        .load_mapped(mapped)
+        .and_then(|pkg| loader.load_all().and(LoadingResult::new_success(pkg)))
+        .and_then(|pkg| {
+            loader
+                .build()
+                .and_then(|collection| LoadingResult::new_success((pkg, collection)))
+        })
+}
+
 pub fn load_file(
     file: PathBuf,
     entrypoint: &str,
@@ -319,17 +426,67 @@ pub fn load_file_force_entrypoint(
     }
 }
 
+/// Loads every element of the file, being either a `Compo.toml` or a standalone script, as well as every element of core packages, regardless of entrypoints.
+pub fn load_file_all_elements(
+    file: PathBuf,
+    mut config: LoadingConfig,
+) -> LoadingResult<(Arc<dyn PackageInfo>, Arc<Collection>)> {
+    if file
+        .file_name()
+        .map(|file_name| file_name == "Compo.toml")
+        .unwrap_or(false)
+    {
+        config.search_locations.push(file.clone());
+        match std::fs::read_to_string(&file) {
+            Ok(content) => load_compo_all_elements(&content, config),
+            Err(err) => LoadingResult::new_failure(LoadingError::unreachable_file(
+                251,
+                file,
+                err.to_string(),
+            )),
+        }
+    } else {
+        match std::fs::read(&file) {
+            Ok(content) => load_raw_all_elements(Arc::new(content), config),
+            Err(err) => LoadingResult::new_failure(LoadingError::unreachable_file(
+                252,
+                file,
+                err.to_string(),
+            )),
+        }
+    }
+}
+
+/// Gives lint warnings about the loaded package, the ones about its scripts first, then the ones about its designed treatments.
+///
+/// `roots` are the treatments package is meant to be used through, see [`melodium_engine::lint::lint_package`].
+pub fn lint(
+    package: &Arc<dyn PackageInfo>,
+    collection: &Arc<Collection>,
+    roots: &[Identifier],
+) -> Vec<LintWarning> {
+    let mut warnings = package.lint();
+    warnings.extend(lint_package(collection, package.name(), roots));
+    warnings
+}
+
 pub async fn launch(
     collection: Arc<Collection>,
     identifier: &Identifier,
     parameters: HashMap<String, Value>,
+    overrides: HashMap<Identifier, Identifier>,
     log_path: Option<PathBuf>,
+    log_filter: LogFilter,
+    log_format: LogFormat,
     debug_path: Option<PathBuf>,
+    otlp_endpoint: Option<String>,
+    metrics_listen: Option<SocketAddr>,
+    inspect: Option<SocketAddr>,
     enable_reports: bool,
     enable_status: bool,
     tags: Option<Vec<String>>,
-) -> LogicResult<()> {
-    let engine = melodium_engine::new_engine(collection, Level::Trace, DebugLevel::Detailed);
+) -> LogicResult<ExecutionOutcome> {
+    let engine = melodium_engine::new_engine(collection, log_filter, DebugLevel::Detailed);
 
     let mut monitoring: futures::stream::FuturesUnordered<async_std::task::JoinHandle<()>> =
         futures::stream::FuturesUnordered::new();
@@ -339,7 +496,7 @@ pub async fn launch(
     // TODO for WASM
     #[cfg(not(target_os = "unknown"))]
     monitoring.push(async_std::task::spawn(async move {
-        display_logs(logs_stdout_receiver).await
+        display_logs(logs_stdout_receiver, log_format).await
     }));
     if let Some(log_path) = log_path.clone() {
         let (logs_write_sender, logs_write_receiver) = unbounded();
@@ -347,7 +504,7 @@ pub async fn launch(
         // TODO for WASM
         #[cfg(not(target_os = "unknown"))]
         monitoring.push(async_std::task::spawn(async move {
-            write_logs(log_path, logs_write_receiver).await
+            write_logs(log_path, logs_write_receiver, log_format).await
         }));
     }
     if let Some(debug_path) = debug_path {
@@ -359,6 +516,72 @@ pub async fn launch(
             write_debug(debug_path, debug_write_receiver).await
         }));
     }
+    // TODO for WASM
+    #[cfg(not(target_os = "unknown"))]
+    if let Some(otlp_endpoint) = otlp_endpoint {
+        match otlp::Endpoint::parse(&otlp_endpoint) {
+            Ok(endpoint) => {
+                let exporter = Arc::new(otlp::OtlpExporter::new(endpoint, identifier));
+                let (otlp_debug_sender, otlp_debug_receiver) = otlp::queue();
+                engine.add_debug_listener(otlp_debug_sender);
+                let (otlp_logs_sender, otlp_logs_receiver) = otlp::queue();
+                engine.add_logs_listener(otlp_logs_sender);
+
+                let traces_exporter = Arc::clone(&exporter);
+                monitoring.push(async_std::task::spawn(async move {
+                    traces_exporter.export_traces(otlp_debug_receiver).await
+                }));
+                monitoring.push(async_std::task::spawn(async move {
+                    exporter.export_logs(otlp_logs_receiver).await
+                }));
+            }
+            Err(error) => eprintln!("{}: no OTLP export: {error}", "warning".bold().yellow()),
+        }
+    }
+    // TODO for WASM
+    #[cfg(not(target_os = "unknown"))]
+    let metrics_listener = match metrics_listen {
+        Some(address) => match async_std::net::TcpListener::bind(address).await {
+            Ok(listener) => Some((listener, engine.enable_metrics())),
+            Err(error) => {
+                eprintln!(
+                    "{}: no metrics served on {address}: {error}",
+                    "warning".bold().yellow()
+                );
+                None
+            }
+        },
+        None => None,
+    };
+    // TODO for WASM
+    #[cfg(not(target_os = "unknown"))]
+    if let Some(address) = inspect {
+        let adapter = match async_std::net::TcpListener::bind(address).await {
+            Ok(listener) => {
+                eprintln!("Waiting for debugger to connect on {address}…");
+                inspect::DebugAdapter::accept(listener).await
+            }
+            Err(error) => Err(error),
+        };
+        match adapter {
+            Ok(adapter) => {
+                engine.set_inspector(Arc::clone(&adapter) as Arc<dyn Inspector>);
+                let (inspect_debug_sender, inspect_debug_receiver) = unbounded();
+                engine.add_debug_listener(inspect_debug_sender);
+                let (inspect_logs_sender, inspect_logs_receiver) = unbounded();
+                engine.add_logs_listener(inspect_logs_sender);
+                monitoring.push(async_std::task::spawn(async move {
+                    adapter
+                        .follow(inspect_debug_receiver, inspect_logs_receiver)
+                        .await
+                }));
+            }
+            Err(error) => eprintln!(
+                "{}: no inspection on {address}: {error}",
+                "warning".bold().yellow()
+            ),
+        }
+    }
 
     let mut signal_launched: Option<LaunchedSignalFuture> = None;
     let mut signal_ended: Option<EndedSignalFuture> = None;
@@ -399,18 +622,27 @@ pub async fn launch(
         let _ = program_dump_sender.send(program_dump).await;
     }
 
-    let result = engine.genesis(&identifier, parameters);
+    let result = engine.genesis(&identifier, parameters, overrides);
     if result.is_failure() {
         if let Some(launched) = signal_launched {
             launched(Err("Failed to launch engine".into())).await;
         }
-        return result;
+        return result.and_then(|_| LogicResult::new_success(ExecutionOutcome::default()));
     } else {
         if let Some(launched) = signal_launched {
             launched(Ok(())).await;
         }
+        // TODO for WASM
+        #[cfg(not(target_os = "unknown"))]
+        let metrics_server = metrics_listener
+            .map(|(listener, metrics)| async_std::task::spawn(metrics::serve(listener, metrics)));
         engine.live().await;
         engine.end().await;
+        // TODO for WASM
+        #[cfg(not(target_os = "unknown"))]
+        if let Some(metrics_server) = metrics_server {
+            metrics_server.cancel().await;
+        }
         if let Some(ended) = signal_ended {
             ended().await;
         }
@@ -418,7 +650,10 @@ pub async fn launch(
 
     while let Some(_) = monitoring.next().await {}
 
-    LogicResult::new_success(())
+    LogicResult::new_success(ExecutionOutcome {
+        exit_code: engine.exit_code(),
+        failures: engine.failures(),
+    })
 }
 
 pub fn core_config() -> LoadingConfig {
@@ -468,70 +703,93 @@ pub fn core_packages() -> Vec<Arc<dyn Package>> {
     packages
 }
 
-pub async fn display_logs(receiver: Receiver<Log>) {
+/// Format logs are displayed or written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum LogFormat {
+    /// `[<timestamp>] <level>: <label>: <message>` lines.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// `key=value` pairs per line, following logfmt conventions.
+    Logfmt,
+}
+
+pub async fn display_logs(receiver: Receiver<Log>, format: LogFormat) {
     while let Ok(log) = receiver.recv().await {
-        display_log(&log);
+        println!("{}", format_log(&log, format, true));
     }
 }
 
-fn display_log(log: &Log) {
-    match log.level {
-        Level::Error => {
-            println!(
-                "[{}] {}: {}: {}",
-                log.timestamp
-                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
-                "error".bold().red(),
-                log.label,
-                log.message
-            )
-        }
-        Level::Warning => {
-            println!(
-                "[{}] {}: {}: {}",
-                log.timestamp
-                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
-                "warning".bold().yellow(),
-                log.label,
-                log.message
-            )
-        }
-        Level::Info => {
-            println!(
-                "[{}] {}: {}: {}",
-                log.timestamp
-                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
-                "info".bold().blue(),
-                log.label,
-                log.message
-            )
-        }
-        Level::Debug => {
-            println!(
-                "[{}] {}: {}: {}",
-                log.timestamp
-                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
-                "debug".bold().purple(),
-                log.label,
-                log.message
-            )
+/// Formats log as single line, colouring its level if `colored` is set and format is [`LogFormat::Text`].
+pub fn format_log(log: &Log, format: LogFormat, colored: bool) -> String {
+    let timestamp = log
+        .timestamp
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    match format {
+        LogFormat::Text => {
+            let level = if colored {
+                match log.level {
+                    Level::Error => "error".bold().red(),
+                    Level::Warning => "warning".bold().yellow(),
+                    Level::Info => "info".bold().blue(),
+                    Level::Debug => "debug".bold().purple(),
+                    Level::Trace => "trace".bold().dimmed(),
+                }
+                .to_string()
+            } else {
+                log.level.to_string()
+            };
+            format!("[{timestamp}] {level}: {}: {}", log.label, log.message)
         }
-        Level::Trace => {
-            println!(
-                "[{}] {}: {}: {}",
-                log.timestamp
-                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
-                "trace".bold().dimmed(),
-                log.label,
-                log.message
-            )
+        LogFormat::Json => serde_json::json!({
+            "timestamp": timestamp,
+            "level": log.level.to_string(),
+            "label": log.label,
+            "message": log.message,
+            "track_id": log.track_id,
+            "run_id": log.run_id,
+            "group_id": log.group_id,
+        })
+        .to_string(),
+        LogFormat::Logfmt => {
+            let mut line = format!(
+                "time={timestamp} level={} label={} msg={}",
+                log.level,
+                logfmt_value(&log.label),
+                logfmt_value(&log.message)
+            );
+            if let Some(track_id) = log.track_id {
+                line.push_str(&format!(" track_id={track_id}"));
+            }
+            if let Some(run_id) = log.run_id {
+                line.push_str(&format!(" run_id={run_id}"));
+            }
+            if let Some(group_id) = log.group_id {
+                line.push_str(&format!(" group_id={group_id}"));
+            }
+            line
         }
     }
 }
 
+/// Gives value as is if it can stand unquoted in logfmt line, or quoted and escaped otherwise.
+fn logfmt_value(value: &str) -> String {
+    if !value.is_empty()
+        && !value
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '"' | '=' | '\\'))
+    {
+        value.to_string()
+    } else {
+        format!("{value:?}")
+    }
+}
+
 // TODO for WASM
 #[cfg(not(target_os = "unknown"))]
-pub async fn write_logs(path: PathBuf, receiver: Receiver<Log>) {
+pub async fn write_logs(path: PathBuf, receiver: Receiver<Log>, format: LogFormat) {
     if let Some(parent) = path.parent() {
         let _ = async_std::fs::create_dir_all(parent).await;
     }
@@ -545,14 +803,7 @@ pub async fn write_logs(path: PathBuf, receiver: Receiver<Log>) {
         let mut log_file = BufWriter::new(log_file);
 
         while let Ok(log) = receiver.recv().await {
-            let line = format!(
-                "[{}] {}: {}: {}\n",
-                log.timestamp
-                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
-                log.level,
-                log.label,
-                log.message,
-            );
+            let line = format!("{}\n", format_log(&log, format, false));
             let _ = log_file.write(line.as_bytes()).await;
             // Flushed on every line rather than only once the whole run ends: this file is
             // meant to be readable live (e.g. `tail -f`) while a run is in progress, and
@@ -578,23 +829,23 @@ pub async fn write_debug(path: PathBuf, receiver: Receiver<Event>) {
     {
         let mut debug_file = BufWriter::new(debug_file);
         let mut first = true;
-        let _ = debug_file.write("[".as_bytes()).await;
+        let _ = debug_file.write_all("[".as_bytes()).await;
         while let Ok(debug) = receiver.recv().await {
             if !first {
-                let _ = debug_file.write(",".as_bytes()).await;
+                let _ = debug_file.write_all(",".as_bytes()).await;
             } else {
                 first = false;
             }
             let event = melodium_share::Event::from(&debug);
             let line = serde_json::to_string(&event)
                 .unwrap_or_else(|_| "\"<failed to serialize debug event>\"".to_string());
-            let _ = debug_file.write(line.as_bytes()).await;
+            let _ = debug_file.write_all(line.as_bytes()).await;
             // Flushed on every event rather than only once the whole run ends: see the
             // matching comment in `write_logs` - the file is meant to reflect what has
             // happened so far while the run is still in progress.
             let _ = debug_file.flush().await;
         }
-        let _ = debug_file.write("]".as_bytes()).await;
+        let _ = debug_file.write_all("]".as_bytes()).await;
 
         let _ = debug_file.flush().await;
     }
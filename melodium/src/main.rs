@@ -1,18 +1,50 @@
 use clap::{Arg, ArgAction, Command, Parser, Subcommand};
 use colored::Colorize;
 use core::convert::TryFrom;
-use melodium::*;
+use melodium::{
+    diagnostic::{Diagnostic, Severity},
+    *,
+};
 use melodium_common::{
     descriptor::{Collection, DataType, Entry, Identifier, LoadingResult, Status, Treatment},
-    executive::Value,
+    executive::{Level, LogFilter, Value},
 };
+use melodium_engine::lint::{Lint, LintConfig, LintLevel};
 use melodium_lang::{
     semantic::{NoneDeclarativeElement, Value as SemanticValue},
     text::{get_words, Value as TextValue},
 };
+use melodium_loader::PackageInfo;
 use std::{collections::HashMap, sync::RwLock};
 use std::{collections::HashSet, sync::Arc};
-use std::{net::IpAddr, path::PathBuf};
+use std::{
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+};
+
+mod testing;
+
+const EXIT_CODES_HELP: &str = "Exit codes:
+  0  Success
+  1  General failure, such as invalid arguments or unreachable files
+  2  Invalid command line usage
+  3  Program cannot be loaded or built
+  4  Program cannot be launched
+  5  Failure reported during execution
+  Any code from 1 to 255 requested by program through `std/engine::exit`, which prevails over 5,
+  other non-zero codes being replaced by 5";
+
+const CHECK_EXIT_CODES_HELP: &str = "Exit codes:
+  0  Success
+  1  General failure, such as invalid arguments or unreachable files
+  2  Invalid command line usage
+  3  Program cannot be loaded or built, or denied lints were found";
+
+const FMT_EXIT_CODES_HELP: &str = "Exit codes:
+  0  Success
+  1  General failure, such as unreachable files, or files not formatted when checking
+  2  Invalid command line usage
+  3  Script cannot be parsed";
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about)]
@@ -30,6 +62,7 @@ struct Cli {
 }
 
 #[derive(clap::Args, Debug)]
+#[clap(after_help = EXIT_CODES_HELP)]
 /// Run given program, with optional arguments
 struct Run {
     #[clap(long)]
@@ -38,12 +71,39 @@ struct Run {
     #[clap(long, value_name = "IDENTIFIER")]
     /// Force identifier to be used as entrypoint.
     force_entry: Option<String>,
+    #[clap(long = "override", value_name = "OVERRIDDEN=OVERRIDING")]
+    /// Build model or treatment identified by OVERRIDING in place of the one identified by OVERRIDDEN, can be repeated.
+    overrides: Vec<String>,
     #[clap(long)]
     /// Write logs to path.
     logs: Option<PathBuf>,
+    #[clap(long, value_name = "LEVEL", default_value = "trace", value_parser = parse_log_level)]
+    /// Most verbose level of logs to keep, among `error`, `warning`, `info`, `debug` and `trace`.
+    log_level: Level,
+    #[clap(long, value_name = "LABEL=LEVEL,…", value_parser = parse_log_filter)]
+    /// Level of logs to keep for specific labels, overriding --log-level, can be repeated.
+    log_filter: Vec<String>,
+    #[clap(long, value_enum, default_value_t = LogFormat::Text)]
+    /// Format of logs, both displayed and written to --logs path.
+    log_format: LogFormat,
     #[clap(long)]
     /// Write debug to path.
     debug: Option<PathBuf>,
+    #[clap(long, value_name = "URL", value_parser = parse_otlp_endpoint)]
+    /// Export traces of tracks and treatments, and logs, to OpenTelemetry collector through OTLP/HTTP, such as `http://localhost:4318` (plain `http://` only, without TLS).
+    otlp_endpoint: Option<String>,
+    #[clap(long, value_name = "ADDRESS")]
+    /// Serve runtime metrics of tracks, transmissions and continuous tasks on address using Prometheus text format, such as `127.0.0.1:9090`.
+    metrics_listen: Option<SocketAddr>,
+    #[clap(
+        long,
+        value_name = "ADDRESS",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "127.0.0.1:4711"
+    )]
+    /// Wait for a debugger to connect through Debug Adapter Protocol on address (`127.0.0.1:4711` if not given), to stop on data passing connections and look at values.
+    inspect: Option<SocketAddr>,
     #[clap(long, default_value_t = false)]
     /// Whether to report execution to API, requires API token to be set in environment variable `MELODIUM_API_TOKEN`. Also requires API URL to be set in environment variable `MELODIUM_API_URL` if different from `https://api.melodium.tech/0.1`.
     api_report: bool,
@@ -69,6 +129,7 @@ struct Run {
 }
 
 #[derive(clap::Args, Debug)]
+#[clap(after_help = CHECK_EXIT_CODES_HELP)]
 /// Check given program
 struct Check {
     /// Check all elements in the required packages, ignoring entrypoint.
@@ -86,6 +147,25 @@ struct Check {
     #[clap(value_parser, value_name = "COMMAND")]
     /// Entrypoint command to check (default to `main`).
     prog_cmd: Option<String>,
+    /// Also check lints on program package, reporting likely mistakes as warnings.
+    #[clap(long, action, requires = "file")]
+    lint: bool,
+    #[clap(long, value_name = "LINT", requires = "lint")]
+    /// Lint to not report, given by name or code.
+    allow: Vec<String>,
+    #[clap(long, value_name = "LINT", requires = "lint")]
+    /// Lint to report as error, given by name or code.
+    deny: Vec<String>,
+    #[clap(long, value_enum, default_value_t = CheckFormat::Human)]
+    /// Format of the diagnostics, machine-readable ones being written to standard output.
+    format: CheckFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum CheckFormat {
+    Human,
+    Json,
+    Sarif,
 }
 
 #[derive(clap::Args, Debug)]
@@ -122,6 +202,63 @@ struct New {
 /// [Not available in this release] Creates a new program
 struct New {}
 
+#[derive(clap::Args, Debug)]
+#[clap(after_help = FMT_EXIT_CODES_HELP)]
+/// Formats script files canonically
+struct Fmt {
+    #[clap(long, action)]
+    /// Only check if files are formatted, failing if any would be changed.
+    check: bool,
+    #[clap(value_parser, required = true)]
+    /// Script files to format.
+    files: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+#[clap(after_help = EXIT_CODES_HELP)]
+/// Run test treatments of given program, being the ones annotated with `#[test]`
+struct Test {
+    #[clap(long)]
+    /// Path to look for packages.
+    path: Vec<String>,
+    #[clap(long, value_name = "PATTERN")]
+    /// Only run tests whose identifier contains pattern.
+    filter: Option<String>,
+    #[clap(long, value_name = "SECONDS", default_value_t = 60.0)]
+    /// Timeout applied to tests not setting their own through `#[test(timeout = <seconds>)]`.
+    timeout: f64,
+    #[clap(long, value_enum, default_value_t = TestFormat::Human)]
+    /// Format of the report.
+    format: TestFormat,
+    #[clap(long)]
+    /// Write report to path instead of standard output.
+    output: Option<PathBuf>,
+    #[clap(value_parser)]
+    /// Program file to test, can be either `.mel`, `Compo.toml` or `.jeu` file.
+    file: String,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum TestFormat {
+    Human,
+    Tap,
+    Junit,
+}
+
+#[derive(clap::Args, Debug)]
+/// Summarise run from debug file written by `melodium run --debug`
+struct Trace {
+    #[clap(long, value_name = "COUNT", default_value_t = 10)]
+    /// Number of entries listed within each section of summary.
+    top: usize,
+    #[clap(long, value_name = "PATH")]
+    /// Also write timeline of run to path, as Chrome trace-event JSON (viewable with Perfetto or `chrome://tracing`).
+    chrome: Option<PathBuf>,
+    #[clap(value_parser)]
+    /// Debug file to read.
+    file: PathBuf,
+}
+
 #[cfg(feature = "distribution")]
 #[derive(clap::Args, Debug)]
 /// Makes engine available for distribution
@@ -252,6 +389,9 @@ enum Commands {
     Check(Check),
     Info(Info),
     New(New),
+    Fmt(Fmt),
+    Test(Test),
+    Trace(Trace),
     Dist(Dist),
     #[clap(subcommand)]
     Jeu(Jeu),
@@ -273,8 +413,15 @@ pub fn main() {
             file: Some(file),
             prog_args: cli.file_args,
             force_entry: None,
+            overrides: Vec::new(),
             logs: None,
+            log_level: Level::Trace,
+            log_filter: Vec::new(),
+            log_format: LogFormat::Text,
             debug: None,
+            otlp_endpoint: None,
+            metrics_listen: None,
+            inspect: None,
             api_report: false,
             api_report_disable_logs: false,
             api_report_disable_status: false,
@@ -291,6 +438,9 @@ pub fn main() {
             Commands::New(args) => new(args),
             #[cfg(not(feature = "fs"))]
             Commands::New(_) => {}
+            Commands::Fmt(args) => fmt(args),
+            Commands::Test(args) => test(args),
+            Commands::Trace(args) => trace(args),
             #[cfg(feature = "distribution")]
             Commands::Dist(args) => dist(args),
             #[cfg(not(feature = "distribution"))]
@@ -314,83 +464,235 @@ pub fn main() {
             "{}: run `melodium --help` get commands",
             "info".bold().blue()
         );
-        std::process::exit(1);
+        std::process::exit(EXIT_FAILURE);
     }
 }
 
 fn run(args: Run) {
-    if let Ok((Some(identifier), collection)) = check_load(Check {
-        all: false,
-        file: args.file,
-        path: args.path,
-        force_entry: args.force_entry.clone(),
-        prog_cmd: args.prog_args.first().cloned(),
-    }) {
-        let (entry_name, arguments) = if args
-            .prog_args
-            .first()
-            .map(|arg| !arg.starts_with('-'))
-            .unwrap_or(false)
-        {
-            let mut args = args.prog_args.clone();
-            if args.first().is_some() {
-                (Some(args.remove(0)), args)
-            } else {
-                (None, args)
+    let mut overrides = HashMap::new();
+    for r#override in &args.overrides {
+        match r#override.split_once('=').map(|(overridden, overriding)| {
+            (
+                Identifier::try_from(overridden.trim()),
+                Identifier::try_from(overriding.trim()),
+            )
+        }) {
+            Some((Ok(overridden), Ok(overriding))) => {
+                overrides.insert(overridden, overriding);
             }
-        } else {
-            if args.force_entry.is_some() {
-                (None, args.prog_args.clone())
-            } else {
-                (Some("main".to_string()), args.prog_args.clone())
+            _ => {
+                eprintln!(
+                    "{}: '{}' is not a valid override, expecting OVERRIDDEN=OVERRIDING identifiers",
+                    "error".bold().red(),
+                    r#override
+                );
+                std::process::exit(EXIT_FAILURE);
             }
-        };
-
-        let treatment = if let Some(Entry::Treatment(tr)) = collection.get(&(&identifier).into()) {
-            tr
-        } else {
-            eprintln!("{}: entrypoint must be a treatment", "failure".bold().red());
-            std::process::exit(1);
-        };
+        }
+    }
 
-        let params = parse_args(
-            entry_name.clone(),
-            treatment,
-            arguments,
-            args.parse_arguments,
-        );
+    let loaded = check_load(
+        Check {
+            all: false,
+            file: args.file,
+            path: args.path,
+            force_entry: args.force_entry.clone(),
+            prog_cmd: args.prog_args.first().cloned(),
+            lint: false,
+            allow: Vec::new(),
+            deny: Vec::new(),
+            format: CheckFormat::Human,
+        },
+        !overrides.is_empty(),
+        None,
+    );
+    let (identifier, collection) = match loaded {
+        Ok((_, Some(identifier), collection)) => (identifier, collection),
+        Ok((_, None, _)) => std::process::exit(EXIT_LOADING_ERROR),
+        Err(code) => std::process::exit(code),
+    };
 
-        let launch = async_std::task::block_on(launch(
-            collection,
-            &identifier,
-            params,
-            args.logs,
-            args.debug,
-            args.api_report && !args.api_report_disable_logs,
-            args.api_report && !args.api_report_disable_status,
-            entry_name.map(|name| vec![format!("entrypoint={name}")]),
-        ));
-        if let Some(failure) = launch.failure() {
-            eprintln!("{}: {failure}", "failure".bold().red());
+    let (entry_name, arguments) = if args
+        .prog_args
+        .first()
+        .map(|arg| !arg.starts_with('-'))
+        .unwrap_or(false)
+    {
+        let mut args = args.prog_args.clone();
+        if args.first().is_some() {
+            (Some(args.remove(0)), args)
+        } else {
+            (None, args)
         }
-        launch
-            .errors()
-            .iter()
-            .for_each(|err| eprintln!("{}: {err}", "error".bold().red()));
     } else {
-        std::process::exit(1);
+        if args.force_entry.is_some() {
+            (None, args.prog_args.clone())
+        } else {
+            (Some("main".to_string()), args.prog_args.clone())
+        }
+    };
+
+    let treatment = if let Some(Entry::Treatment(tr)) = collection.get(&(&identifier).into()) {
+        tr
+    } else {
+        eprintln!("{}: entrypoint must be a treatment", "failure".bold().red());
+        std::process::exit(EXIT_LOADING_ERROR);
+    };
+
+    let params = parse_args(
+        entry_name.clone(),
+        treatment,
+        arguments,
+        args.parse_arguments,
+    );
+
+    let mut log_filter = LogFilter::new(args.log_level);
+    for filter in &args.log_filter {
+        // Already validated while parsing arguments.
+        let _ = log_filter.add_filters(filter);
+    }
+
+    let launch = async_std::task::block_on(launch(
+        collection,
+        &identifier,
+        params,
+        overrides,
+        args.logs,
+        log_filter,
+        args.log_format,
+        args.debug,
+        args.otlp_endpoint,
+        args.metrics_listen,
+        args.inspect,
+        args.api_report && !args.api_report_disable_logs,
+        args.api_report && !args.api_report_disable_status,
+        entry_name.map(|name| vec![format!("entrypoint={name}")]),
+    ));
+    if let Some(failure) = launch.failure() {
+        eprintln!("{}: {failure}", "failure".bold().red());
+    }
+    launch
+        .errors()
+        .iter()
+        .for_each(|err| eprintln!("{}: {err}", "error".bold().red()));
+    match launch.success() {
+        Some(outcome) => std::process::exit(outcome.code()),
+        None => std::process::exit(EXIT_LOGIC_ERROR),
     }
 }
 
 fn check(args: Check) {
-    if let Ok(_) = check_load(args) {
-        std::process::exit(0);
-    } else {
-        std::process::exit(1);
+    let mut lint_config = LintConfig::new();
+    for (lints, level) in [
+        (&args.allow, LintLevel::Allow),
+        (&args.deny, LintLevel::Deny),
+    ] {
+        for lint in lints {
+            match Lint::try_from(lint.as_str()) {
+                Ok(lint) => lint_config.set_level(lint, level),
+                Err(lint) => {
+                    eprintln!("{}: '{lint}' is not a known lint", "error".bold().red());
+                    std::process::exit(EXIT_FAILURE);
+                }
+            }
+        }
     }
+
+    let lint = args.lint;
+    let format = args.format;
+    let program = args.file.as_ref().map(PathBuf::from);
+    let mut diagnostics = Vec::new();
+    let mut code = EXIT_SUCCESS;
+
+    match check_load(
+        args,
+        lint,
+        Some(&mut diagnostics).filter(|_| format != CheckFormat::Human),
+    ) {
+        Ok((Some(package), _, collection)) if lint => {
+            // Tests are roots as well, but only for packages having entrypoints, the other ones being libraries.
+            let mut roots = package.entrypoints().values().cloned().collect::<Vec<_>>();
+            if !roots.is_empty() {
+                roots.extend(
+                    testing::discover(&collection, package.name(), None)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|case| case.identifier),
+                );
+            }
+
+            for warning in melodium::lint(&package, &collection, &roots) {
+                let severity = match lint_config.level(warning.lint()) {
+                    LintLevel::Allow => continue,
+                    LintLevel::Warn => Severity::Warning,
+                    LintLevel::Deny => {
+                        code = EXIT_LOADING_ERROR;
+                        Severity::Error
+                    }
+                };
+                let diagnostic = Diagnostic::from_lint(&warning, severity);
+
+                if format == CheckFormat::Human {
+                    let location = diagnostic
+                        .range
+                        .map(|range| {
+                            format!(
+                                " (line {} position {})",
+                                range.start.line,
+                                range.start.column - 1
+                            )
+                        })
+                        .unwrap_or_default();
+                    match severity {
+                        Severity::Warning => {
+                            eprintln!("{}: {warning}{location}", "warning".bold().yellow())
+                        }
+                        Severity::Error => {
+                            eprintln!("{}: {warning}{location}", "error".bold().red())
+                        }
+                    }
+                } else {
+                    diagnostics.push(diagnostic);
+                }
+            }
+        }
+        Ok(_) => {}
+        Err(err) => code = err,
+    }
+
+    let file = |content: &str| {
+        program
+            .as_deref()
+            .and_then(|program| diagnostic::content_file(program, content))
+    };
+    match format {
+        CheckFormat::Human => {}
+        CheckFormat::Json => println!(
+            "{}",
+            diagnostic::json(&diagnostics, code == EXIT_SUCCESS, file)
+        ),
+        CheckFormat::Sarif => println!("{}", diagnostic::sarif(&diagnostics, file)),
+    }
+
+    std::process::exit(code);
 }
 
-fn check_load(args: Check) -> Result<(Option<Identifier>, Arc<Collection>), ()> {
+/// Loads program according to arguments, giving exit code in case of failure.
+///
+/// If `all_elements` is set, every element of the program package is loaded, whatever entrypoint is used.
+/// If `diagnostics` is given, loading errors are added to it instead of being printed.
+fn check_load(
+    args: Check,
+    all_elements: bool,
+    mut diagnostics: Option<&mut Vec<Diagnostic>>,
+) -> Result<
+    (
+        Option<Arc<dyn PackageInfo>>,
+        Option<Identifier>,
+        Arc<Collection>,
+    ),
+    i32,
+> {
     if args.all
         && (args
             .prog_cmd
@@ -417,17 +719,19 @@ fn check_load(args: Check) -> Result<(Option<Identifier>, Arc<Collection>), ()>
 
     let file = args.file.as_ref().map(|f| PathBuf::from(f));
 
+    let mut forced = None;
     let result = if args.all {
         match file {
+            Some(file) if all_elements => load_file_all_elements(file, config),
             Some(file) => load_file_all_entrypoints(file, config),
             None => {
                 // Short circuit
                 let result = load_all(config);
-                print_result(&result);
+                report_result(&result, diagnostics);
                 return result
                     .as_result()
-                    .map(|(_pkgs, collection)| (None, collection.clone()))
-                    .map_err(|_| ());
+                    .map(|(_pkgs, collection)| (None, None, collection.clone()))
+                    .map_err(|_| EXIT_LOADING_ERROR);
             }
         }
     } else {
@@ -436,6 +740,7 @@ fn check_load(args: Check) -> Result<(Option<Identifier>, Arc<Collection>), ()>
             &args.force_entry,
             file,
         ) {
+            (_, None, Some(file)) if all_elements => load_file_all_elements(file, config),
             (None, None, Some(file)) => load_file(file, "main", config),
             (Some(entrypoint), None, Some(file)) => load_file(file, entrypoint, config),
             (None, Some(identifier), Some(file)) => {
@@ -446,32 +751,44 @@ fn check_load(args: Check) -> Result<(Option<Identifier>, Arc<Collection>), ()>
                             "{}: '{str}' is not a valid identifier",
                             "error".bold().red()
                         );
-                        return Err(());
+                        return Err(EXIT_FAILURE);
                     }
                 };
-                load_file_force_entrypoint(file, &identifier, config)
+                let result = if all_elements {
+                    load_file_all_elements(file, config)
+                } else {
+                    load_file_force_entrypoint(file, &identifier, config)
+                };
+                forced = Some(identifier);
+                result
             }
             (_, _, None) => {
                 eprintln!("{}: file must be given", "error".bold().red());
-                return Err(());
+                return Err(EXIT_FAILURE);
             }
             (Some(_), Some(_), _) => {
                 eprintln!(
                     "{}: entrypoint cannot be specified and forced at same time",
                     "error".bold().red()
                 );
-                return Err(());
+                return Err(EXIT_FAILURE);
             }
         }
     };
 
-    print_result(&result);
+    report_result(&result, diagnostics.take());
 
     result
         .into_result()
         .map(|(pkg, collection)| {
             (
-                if !args.all {
+                Some(Arc::clone(&pkg)),
+                if let Some(forced) = forced {
+                    match collection.get(&(&forced).into()) {
+                        Some(Entry::Treatment(treatment)) => Some(treatment.identifier().clone()),
+                        _ => Some(forced),
+                    }
+                } else if !args.all {
                     pkg.entrypoints()
                         .get(
                             args.prog_cmd
@@ -486,7 +803,7 @@ fn check_load(args: Check) -> Result<(Option<Identifier>, Arc<Collection>), ()>
                 collection,
             )
         })
-        .map_err(|_| ())
+        .map_err(|_| EXIT_LOADING_ERROR)
 }
 
 fn info(args: Info) {
@@ -513,7 +830,7 @@ fn info(args: Info) {
         }
         let _ = cmd.print_long_help();
     } else {
-        std::process::exit(1);
+        std::process::exit(EXIT_LOADING_ERROR);
     }
 }
 
@@ -527,7 +844,7 @@ fn new(args: New) {
         Ok(version) => version,
         Err(err) => {
             eprintln!("{}: {err}", "error".bold().red());
-            std::process::exit(1);
+            std::process::exit(EXIT_FAILURE);
         }
     };
 
@@ -535,7 +852,7 @@ fn new(args: New) {
         Ok(path) => path,
         Err(err) => {
             eprintln!("{}: {err}", "error".bold().red());
-            std::process::exit(1);
+            std::process::exit(EXIT_FAILURE);
         }
     };
 
@@ -556,7 +873,7 @@ fn new(args: New) {
 
         if let Err(err) = std::fs::write(&path, contents) {
             eprintln!("{}: {err}", "error".bold().red());
-            std::process::exit(1);
+            std::process::exit(EXIT_FAILURE);
         }
 
         println!(
@@ -575,7 +892,7 @@ fn new(args: New) {
                     full_path.push(filename);
                     if let Err(err) = std::fs::write(full_path, content) {
                         eprintln!("{}: {err}", "error".bold().red());
-                        std::process::exit(1);
+                        std::process::exit(EXIT_FAILURE);
                     }
                 }
 
@@ -587,10 +904,218 @@ fn new(args: New) {
             }
             Err(err) => {
                 eprintln!("{}: {err}", "error".bold().red());
-                std::process::exit(1);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+}
+
+fn fmt(args: Fmt) {
+    let mut unformatted = false;
+    let mut failed = false;
+
+    for file in &args.files {
+        let text = match std::fs::read_to_string(file) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("{}: {file}: {err}", "error".bold().red());
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+
+        let formatted = match melodium_lang::restitution::format_script(&text) {
+            Status::Success { success, errors: _ } => success,
+            Status::Failure { failure, errors } => {
+                let mut printed = HashSet::new();
+                for err in std::iter::once(&failure).chain(errors.iter()) {
+                    let err_msg = err.to_string();
+                    if printed.insert(err_msg.clone()) {
+                        eprintln!("{}: {file}: {err_msg}", "error".bold().red());
+                    }
+                }
+                failed = true;
+                continue;
+            }
+        };
+
+        if formatted != text {
+            if args.check {
+                println!("{file} would be reformatted");
+                unformatted = true;
+            } else if let Err(err) = std::fs::write(file, formatted) {
+                eprintln!("{}: {file}: {err}", "error".bold().red());
+                std::process::exit(EXIT_FAILURE);
             }
         }
     }
+
+    if failed {
+        std::process::exit(EXIT_LOADING_ERROR);
+    } else if unformatted {
+        std::process::exit(EXIT_FAILURE);
+    }
+}
+
+fn test(args: Test) {
+    let default_timeout = match std::time::Duration::try_from_secs_f64(args.timeout) {
+        Ok(timeout) => timeout,
+        Err(_) => {
+            eprintln!(
+                "{}: '{}' is not a valid timeout",
+                "error".bold().red(),
+                args.timeout
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let config = LoadingConfig {
+        core_packages: Vec::new(),
+        search_locations: args.path.iter().map(PathBuf::from).collect(),
+        raw_elements: Vec::new(),
+    };
+    let file = PathBuf::from(&args.file);
+
+    let result = load_file_all_elements(file.clone(), config);
+    print_result(&result);
+    let (pkg, collection) = match result.into_result() {
+        Ok(loaded) => loaded,
+        Err(_) => std::process::exit(EXIT_LOADING_ERROR),
+    };
+
+    let cases = match testing::discover(&collection, pkg.name(), args.filter.as_deref()) {
+        Ok(cases) => cases,
+        Err(err) => {
+            eprintln!("{}: {err}", "error".bold().red());
+            std::process::exit(EXIT_LOADING_ERROR);
+        }
+    };
+
+    let human = args.format == TestFormat::Human;
+    let progress = human || args.output.is_some();
+    if progress {
+        println!(
+            "running {} test{}",
+            cases.len(),
+            if cases.len() == 1 { "" } else { "s" }
+        );
+    }
+
+    let start = std::time::Instant::now();
+    let mut reports = Vec::new();
+    for case in &cases {
+        let report = match testing::run(
+            case,
+            &file,
+            &args.path,
+            case.timeout.unwrap_or(default_timeout),
+        ) {
+            Ok(report) => report,
+            Err(err) => {
+                eprintln!(
+                    "{}: cannot run test '{}': {err}",
+                    "error".bold().red(),
+                    case.identifier
+                );
+                std::process::exit(EXIT_FAILURE);
+            }
+        };
+
+        if progress {
+            println!(
+                "test {} ... {} ({:.3}s)",
+                case.identifier,
+                match report.outcome {
+                    testing::TestOutcome::Passed => "ok".green(),
+                    testing::TestOutcome::Failed(_) => "FAILED".red(),
+                    testing::TestOutcome::TimedOut(_) => "TIMED OUT".red(),
+                },
+                report.duration.as_secs_f64()
+            );
+        }
+        reports.push(report);
+    }
+
+    let failed = reports.iter().filter(|report| !report.passed()).count();
+
+    if human && failed > 0 {
+        println!("\nfailures:");
+        for report in reports.iter().filter(|report| !report.passed()) {
+            println!("\n---- {} ----", report.case.identifier);
+            print!("{}", report.output);
+            if let testing::TestOutcome::TimedOut(_) = report.outcome {
+                println!("{}", testing::outcome_message(&report.outcome));
+            }
+        }
+    }
+    if progress {
+        println!(
+            "\ntest result: {}. {} passed; {failed} failed; finished in {:.3}s",
+            if failed == 0 {
+                "ok".green()
+            } else {
+                "FAILED".red()
+            },
+            reports.len() - failed,
+            start.elapsed().as_secs_f64()
+        );
+    }
+
+    let report = match args.format {
+        TestFormat::Human => None,
+        TestFormat::Tap => Some(testing::tap(&reports)),
+        TestFormat::Junit => Some(testing::junit(pkg.name(), &reports)),
+    };
+    if let Some(report) = report {
+        match &args.output {
+            Some(output) => {
+                if let Err(err) = std::fs::write(output, report) {
+                    eprintln!(
+                        "{}: {}: {err}",
+                        "error".bold().red(),
+                        output.to_string_lossy()
+                    );
+                    std::process::exit(EXIT_FAILURE);
+                }
+            }
+            None => print!("{report}"),
+        }
+    }
+
+    if failed > 0 {
+        std::process::exit(EXIT_EXECUTION_FAILURE);
+    }
+}
+
+fn trace(args: Trace) {
+    let events = match std::fs::read_to_string(&args.file)
+        .map_err(|err| err.to_string())
+        .and_then(|content| trace::read(&content))
+    {
+        Ok(events) => events,
+        Err(err) => {
+            eprintln!(
+                "{}: {}: {err}",
+                "error".bold().red(),
+                args.file.to_string_lossy()
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    };
+
+    let trace = trace::Trace::new(&events);
+    print!("{}", trace.summary(args.top));
+
+    if let Some(chrome) = &args.chrome {
+        if let Err(err) = std::fs::write(chrome, trace.chrome()) {
+            eprintln!(
+                "{}: {}: {err}",
+                "error".bold().red(),
+                chrome.to_string_lossy()
+            );
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
 }
 
 #[cfg(feature = "distribution")]
@@ -606,7 +1131,7 @@ fn dist(args: Dist) {
 
     let (logs_stdout_sender, logs_stdout_receiver) = unbounded();
     monitoring.push(async_std::task::spawn(async move {
-        crate::display_logs(logs_stdout_receiver).await
+        crate::display_logs(logs_stdout_receiver, LogFormat::Text).await
     }));
     let mut logs_senders = vec![logs_stdout_sender];
 
@@ -614,7 +1139,7 @@ fn dist(args: Dist) {
         let (logs_write_sender, logs_write_receiver) = unbounded();
         logs_senders.push(logs_write_sender);
         monitoring.push(async_std::task::spawn(async move {
-            crate::write_logs(path, logs_write_receiver).await
+            crate::write_logs(path, logs_write_receiver, LogFormat::Text).await
         }));
     });
 
@@ -844,7 +1369,7 @@ fn dist(args: Dist) {
                 "{}: monitoring tasks did not complete in time, forcing exit",
                 "warning".bold().yellow()
             );
-            std::process::exit(0);
+            std::process::exit(EXIT_SUCCESS);
         }
     });
 }
@@ -914,7 +1439,7 @@ fn doc(args: Doc) {
         melodium_doc::Documentation::new(PathBuf::from(&args.output), collection, subject);
     if let Err(err) = documentation.make_documentation() {
         eprintln!("{}: {err}", "error".bold().red());
-        std::process::exit(1);
+        std::process::exit(EXIT_FAILURE);
     } else {
         println!(
             "{}: documentation generated, run `mdbook build` in '{path}' to build publishable book",
@@ -967,7 +1492,7 @@ fn parse_args(
                                 "{}: argument '{name}' cannot be parsed",
                                 "failure".bold().red()
                             );
-                            std::process::exit(1);
+                            std::process::exit(EXIT_FAILURE);
                         }
                     };
                     words.push(melodium_lang::text::Word::default());
@@ -982,7 +1507,7 @@ fn parse_args(
                                 "{}: argument '{name}' cannot be parsed: {err}",
                                 "failure".bold().red()
                             );
-                            std::process::exit(1);
+                            std::process::exit(EXIT_FAILURE);
                         }
                     };
 
@@ -993,7 +1518,7 @@ fn parse_args(
                                 errors
                                     .iter()
                                     .for_each(|err| eprintln!("{}: {err}", "error".bold().red()));
-                                std::process::exit(1);
+                                std::process::exit(EXIT_FAILURE);
                             }
                             success
                         }
@@ -1002,7 +1527,7 @@ fn parse_args(
                             errors
                                 .iter()
                                 .for_each(|err| eprintln!("{}: {err}", "error".bold().red()));
-                            std::process::exit(1);
+                            std::process::exit(EXIT_FAILURE);
                         }
                     };
 
@@ -1014,16 +1539,21 @@ fn parse_args(
                         "{}: provided treatment have generics, it cannot be used as entrypoint",
                         "failure".bold().red()
                     );
-                            std::process::exit(1);
+                            std::process::exit(EXIT_FAILURE);
                         };
 
-                    let value = match value.read().unwrap().make_executive_value(&datatype) {
+                    // Arguments are standalone values, no constant can be referred there.
+                    let value = match value
+                        .read()
+                        .unwrap()
+                        .make_executive_value(&datatype, &Collection::new())
+                    {
                         Status::Success { success, errors } => {
                             if !errors.is_empty() {
                                 errors
                                     .iter()
                                     .for_each(|err| eprintln!("{}: {err}", "error".bold().red()));
-                                std::process::exit(1);
+                                std::process::exit(EXIT_FAILURE);
                             }
                             success
                         }
@@ -1032,7 +1562,7 @@ fn parse_args(
                             errors
                                 .iter()
                                 .for_each(|err| eprintln!("{}: {err}", "error".bold().red()));
-                            std::process::exit(1);
+                            std::process::exit(EXIT_FAILURE);
                         }
                     };
 
@@ -1046,7 +1576,7 @@ fn parse_args(
                         "{}: provided treatment have generics, it cannot be used as entrypoint",
                         "failure".bold().red()
                     );
-                            std::process::exit(1);
+                            std::process::exit(EXIT_FAILURE);
                         };
 
                     fn naive_parse(name: &str, dt: &DataType, value: &str) -> Value {
@@ -1059,7 +1589,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1071,7 +1601,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1083,7 +1613,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1095,7 +1625,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1107,7 +1637,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1119,7 +1649,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1131,7 +1661,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1143,7 +1673,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1155,7 +1685,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1167,7 +1697,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1179,7 +1709,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1191,7 +1721,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1203,7 +1733,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1215,7 +1745,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1227,7 +1757,7 @@ fn parse_args(
                                             "{}: parameter '{name}' is type '{dt}': {err} ",
                                             "failure".bold().red()
                                         );
-                                        std::process::exit(1);
+                                        std::process::exit(EXIT_FAILURE);
                                     }
                                 })
                             }
@@ -1243,7 +1773,7 @@ fn parse_args(
                             }
                             _ => {
                                 eprintln!("{}: parameter '{name}' is type '{dt}', that cannot be set up without parsing, see --parse-arguments option", "failure".bold().red());
-                                std::process::exit(1);
+                                std::process::exit(EXIT_FAILURE);
                             }
                         }
                     }
@@ -1259,6 +1789,20 @@ fn parse_args(
     parsed
 }
 
+fn parse_log_level(level: &str) -> Result<Level, String> {
+    Level::try_from(level)
+}
+
+fn parse_log_filter(filter: &str) -> Result<String, String> {
+    LogFilter::default()
+        .add_filters(filter)
+        .map(|_| filter.to_string())
+}
+
+fn parse_otlp_endpoint(endpoint: &str) -> Result<String, String> {
+    otlp::Endpoint::parse(endpoint).map(|_| endpoint.to_string())
+}
+
 #[cfg(all(feature = "jeu", feature = "fs"))]
 fn build_jeu(args: JeuBuild) {
     let input = PathBuf::from(args.package);
@@ -1269,7 +1813,7 @@ fn build_jeu(args: JeuBuild) {
     print_result(&result);
 
     if result.is_failure() {
-        std::process::exit(1);
+        std::process::exit(EXIT_LOADING_ERROR);
     }
 }
 
@@ -1280,7 +1824,14 @@ fn extract_jeu(args: JeuExtract) {
 
     if let Err(err) = melodium_loader::extract_jeu(&input, &output) {
         eprintln!("{}: {err}", "error".bold().red());
-        std::process::exit(1);
+        std::process::exit(EXIT_FAILURE);
+    }
+}
+
+fn report_result<T>(result: &LoadingResult<T>, diagnostics: Option<&mut Vec<Diagnostic>>) {
+    match diagnostics {
+        Some(diagnostics) => diagnostics.extend(diagnostic::from_result(result)),
+        None => print_result(result),
     }
 }
 
@@ -0,0 +1,65 @@
+//! Exposure of engine runtime metrics through HTTP, to be scraped by Prometheus.
+//!
+//! Metrics are answered to `GET /metrics` (or `GET /`) requests using Prometheus text exposition format,
+//! as given by [`Metrics::prometheus`].
+
+use async_std::io::{ReadExt, WriteExt};
+use async_std::net::{TcpListener, TcpStream};
+use futures::StreamExt;
+use melodium_engine::metrics::Metrics;
+use std::sync::Arc;
+
+/// Maximal size of request head that is read.
+const MAX_REQUEST_SIZE: usize = 8192;
+
+/// Serves metrics on `listener`, until the returned future is dropped.
+pub async fn serve(listener: TcpListener, metrics: Arc<Metrics>) {
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        if let Ok(stream) = stream {
+            let metrics = Arc::clone(&metrics);
+            async_std::task::spawn(async move {
+                let _ = respond(stream, &metrics).await;
+            });
+        }
+    }
+}
+
+async fn respond(mut stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 || request.len() + read > MAX_REQUEST_SIZE {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let (status, body) = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some("/metrics" | "/")) => ("200 OK", metrics.prometheus()),
+        (Some("GET"), Some(_)) => ("404 Not Found", "Not found, see /metrics\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "Only GET is allowed\n".to_string(),
+        ),
+    };
+
+    let content_type = if status.starts_with("200") {
+        "text/plain; version=0.0.4; charset=utf-8"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await
+}
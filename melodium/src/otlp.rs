@@ -0,0 +1,476 @@
+//! Export of execution traces and logs to an OpenTelemetry collector, through OTLP over HTTP with JSON encoding.
+//!
+//! Each run is a trace, having a root span for the whole run, a span for each track (children of their parent track,
+//! or of the root span), and a span for each treatment build executed within a track.
+//! Logs are exported as log records, linked to the span of the track they come from.
+//!
+//! Only plain `http://` endpoints are supported, as is usual for a local collector: requests are made through a minimal
+//! HTTP/1.1 client, without TLS, that only reads the status line of responses.
+//! Once a request to the collector failed, nothing more is sent, remaining spans and log records being dropped.
+
+use crate::VERSION;
+use async_std::channel::{bounded, unbounded, Receiver, Sender, TrySendError};
+use async_std::future::timeout;
+use async_std::io::{ReadExt, WriteExt};
+use async_std::net::TcpStream;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use core::time::Duration;
+use melodium_common::descriptor::Identifier;
+use melodium_common::executive::{Level, Log, TrackId};
+use melodium_engine::debug::{Event, EventKind, TrackCreation, TrackResult};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Maximal number of spans or log records sent at once.
+const BATCH_SIZE: usize = 512;
+/// Maximal time spans or log records are kept before being sent.
+const BATCH_DELAY: Duration = Duration::from_secs(1);
+/// Maximal time a request to endpoint can take, so an unresponsive collector cannot hold execution.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Maximal number of events or logs waiting to be exported, further ones being dropped.
+const QUEUE_CAPACITY: usize = 8192;
+/// First id given to treatment spans, lower ids being the root and track spans.
+const TREATMENT_SPAN_IDS: u64 = 1 << 62;
+
+/// Gives sender to register as engine listener, and receiver to give to exporter.
+///
+/// Elements are dropped rather than queued when exporter lags behind by more than [`QUEUE_CAPACITY`],
+/// so a slow collector cannot make memory grow during execution.
+pub fn queue<T: Send + 'static>() -> (Sender<T>, Receiver<T>) {
+    let (sender, incoming) = unbounded();
+    let (queue, receiver) = bounded(QUEUE_CAPACITY);
+    async_std::task::spawn(async move {
+        while let Ok(element) = incoming.recv().await {
+            if let Err(TrySendError::Closed(_)) = queue.try_send(element) {
+                break;
+            }
+        }
+    });
+    (sender, receiver)
+}
+
+/// OTLP endpoint, as `http://<host>:<port>[/<path>]`, IPv6 hosts being written within brackets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl Endpoint {
+    pub fn parse(endpoint: &str) -> Result<Self, String> {
+        let rest = endpoint
+            .strip_prefix("http://")
+            .ok_or_else(|| format!("'{endpoint}' is not an http:// endpoint"))?;
+        let (authority, path) = match rest.find('/') {
+            Some(position) => rest.split_at(position),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.strip_prefix('[') {
+            Some(bracketed) => {
+                let (host, port) = bracketed
+                    .split_once(']')
+                    .ok_or_else(|| format!("'{endpoint}' has unclosed bracket in host"))?;
+                match port {
+                    "" => (host, None),
+                    port => (
+                        host,
+                        Some(port.strip_prefix(':').ok_or_else(|| {
+                            format!("'{endpoint}' has unexpected characters after host")
+                        })?),
+                    ),
+                }
+            }
+            None => match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            },
+        };
+        let port = match port {
+            Some(port) => port
+                .parse()
+                .map_err(|_| format!("'{port}' is not a valid port"))?,
+            None => 4318,
+        };
+        if host.is_empty() {
+            return Err(format!("'{endpoint}' has no host"));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Gives `<host>:<port>`, with IPv6 host within brackets.
+    fn authority(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// Exporter of a run to OTLP endpoint.
+#[derive(Debug)]
+pub struct OtlpExporter {
+    endpoint: Endpoint,
+    resource: Value,
+    trace_id: String,
+    root_span_id: u64,
+    root_name: String,
+    start: DateTime<Utc>,
+    /// Next treatment span id, track spans having ids computed from track ids.
+    next_span_id: AtomicU64,
+    failed: AtomicBool,
+}
+
+impl OtlpExporter {
+    pub fn new(endpoint: Endpoint, entrypoint: &Identifier) -> Self {
+        Self {
+            endpoint,
+            resource: json!({
+                "attributes": [
+                    attribute("service.name", json!({ "stringValue": "melodium" })),
+                    attribute("service.version", json!({ "stringValue": VERSION })),
+                    attribute("melodium.entrypoint", json!({ "stringValue": entrypoint.to_string() })),
+                    attribute("melodium.run_id", json!({ "stringValue": melodium_engine::execution_run_id().to_string() })),
+                    attribute("melodium.group_id", json!({ "stringValue": melodium_engine::execution_group_id().to_string() })),
+                ]
+            }),
+            trace_id: melodium_engine::execution_run_id().simple().to_string(),
+            root_span_id: 1,
+            root_name: format!("run {entrypoint}"),
+            start: Utc::now(),
+            next_span_id: AtomicU64::new(TREATMENT_SPAN_IDS),
+            failed: AtomicBool::new(false),
+        }
+    }
+
+    /// Exports spans built from debug events, until receiver is closed.
+    ///
+    /// Spans still open at that time are ended, along with the root span of the run.
+    pub async fn export_traces(&self, receiver: Receiver<Event>) {
+        let mut tracks: HashMap<TrackId, Span> = HashMap::new();
+        let mut treatments: HashMap<(TrackId, u64), Span> = HashMap::new();
+        let mut batch = Vec::new();
+
+        loop {
+            let event = match async_std::future::timeout(BATCH_DELAY, receiver.recv()).await {
+                Ok(Ok(event)) => Some(event),
+                Ok(Err(_)) => break,
+                Err(_) => None,
+            };
+
+            let waited = event.is_none();
+            if let Some(event) = event {
+                match event.kind {
+                    EventKind::TrackAdded { info, creation } => {
+                        let id = self.track_span_id(info.id);
+                        let parent = info
+                            .parent_id
+                            .map(|parent| self.track_span_id(parent))
+                            .unwrap_or(self.root_span_id);
+
+                        let mut span =
+                            Span::new(id, parent, format!("track {}", info.id), event.timestamp);
+                        span.attribute("melodium.track_id", int(info.id as u64));
+                        if let TrackCreation::Source { source, .. } = creation {
+                            span.attribute("melodium.source", string(&source));
+                        }
+                        tracks.insert(info.id, span);
+                    }
+                    EventKind::TrackFinished { info } => {
+                        if let Some(mut span) = tracks.remove(&info.id) {
+                            if let Some(TrackResult::NotAllOk(_, statuses)) = &info.results {
+                                span.error.get_or_insert_with(|| {
+                                    statuses
+                                        .iter()
+                                        .map(|status| status.to_string())
+                                        .collect::<Vec<_>>()
+                                        .join("; ")
+                                });
+                            }
+                            span.end = Some(event.timestamp);
+                            batch.push(span);
+                        }
+                    }
+                    EventKind::Failure {
+                        track_id: Some(track_id),
+                        status,
+                    } => {
+                        if let Some(span) = tracks.get_mut(&track_id) {
+                            span.error.get_or_insert_with(|| status.to_string());
+                        }
+                    }
+                    EventKind::TreatmentStarted {
+                        treatment,
+                        build_id,
+                        track_id,
+                        label,
+                        ..
+                    } => {
+                        let mut span = Span::new(
+                            self.next_span_id.fetch_add(1, Ordering::Relaxed),
+                            self.track_span_id(track_id),
+                            treatment.identifier().to_string(),
+                            event.timestamp,
+                        );
+                        span.attribute("melodium.label", string(&label));
+                        span.attribute("melodium.build_id", int(build_id as u64));
+                        span.attribute("melodium.track_id", int(track_id as u64));
+                        treatments.insert((track_id, build_id as u64), span);
+                    }
+                    EventKind::TreatmentFinished {
+                        build_id, track_id, ..
+                    } => {
+                        if let Some(mut span) = treatments.remove(&(track_id, build_id as u64)) {
+                            span.end = Some(event.timestamp);
+                            batch.push(span);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if batch.len() >= BATCH_SIZE || (waited && !batch.is_empty()) {
+                self.send_spans(std::mem::take(&mut batch)).await;
+            }
+        }
+
+        let end = Utc::now();
+        batch.extend(treatments.into_values().chain(tracks.into_values()));
+        let mut root = Span::new(self.root_span_id, 0, self.root_name.clone(), self.start);
+        root.end = Some(end);
+        batch.push(root);
+        for span in &mut batch {
+            span.end.get_or_insert(end);
+        }
+        for spans in batch.chunks(BATCH_SIZE) {
+            self.send_spans(spans.to_vec()).await;
+        }
+    }
+
+    /// Exports log records, until receiver is closed.
+    pub async fn export_logs(&self, receiver: Receiver<Log>) {
+        let mut batch = Vec::new();
+
+        loop {
+            let log = match async_std::future::timeout(BATCH_DELAY, receiver.recv()).await {
+                Ok(Ok(log)) => Some(log),
+                Ok(Err(_)) => break,
+                Err(_) => None,
+            };
+
+            if let Some(log) = &log {
+                batch.push(self.log_record(log));
+            }
+
+            if batch.len() >= BATCH_SIZE || (log.is_none() && !batch.is_empty()) {
+                self.send_logs(std::mem::take(&mut batch)).await;
+            }
+        }
+
+        if !batch.is_empty() {
+            self.send_logs(batch).await;
+        }
+    }
+
+    /// Gives span id of track, logs and events being received independently.
+    fn track_span_id(&self, track_id: TrackId) -> u64 {
+        self.root_span_id + 1 + track_id as u64
+    }
+
+    fn log_record(&self, log: &Log) -> Value {
+        let (severity_number, severity_text) = match log.level {
+            Level::Error => (17, "ERROR"),
+            Level::Warning => (13, "WARN"),
+            Level::Info => (9, "INFO"),
+            Level::Debug => (5, "DEBUG"),
+            Level::Trace => (1, "TRACE"),
+        };
+        let span_id = log
+            .track_id
+            .map(|track_id| self.track_span_id(track_id))
+            .unwrap_or(self.root_span_id);
+
+        let mut attributes = vec![attribute("melodium.label", string(&log.label))];
+        if let Some(track_id) = log.track_id {
+            attributes.push(attribute("melodium.track_id", int(track_id as u64)));
+        }
+
+        json!({
+            "timeUnixNano": nanos(&log.timestamp),
+            "severityNumber": severity_number,
+            "severityText": severity_text,
+            "body": string(&log.message),
+            "attributes": attributes,
+            "traceId": self.trace_id,
+            "spanId": span_id_hex(span_id),
+        })
+    }
+
+    async fn send_spans(&self, spans: Vec<Span>) {
+        let spans = spans
+            .iter()
+            .map(|span| span.to_json(&self.trace_id))
+            .collect::<Vec<_>>();
+        let body = json!({
+            "resourceSpans": [{
+                "resource": self.resource,
+                "scopeSpans": [{
+                    "scope": { "name": "melodium", "version": VERSION },
+                    "spans": spans,
+                }],
+            }],
+        });
+        self.send("/v1/traces", body).await;
+    }
+
+    async fn send_logs(&self, records: Vec<Value>) {
+        let body = json!({
+            "resourceLogs": [{
+                "resource": self.resource,
+                "scopeLogs": [{
+                    "scope": { "name": "melodium", "version": VERSION },
+                    "logRecords": records,
+                }],
+            }],
+        });
+        self.send("/v1/logs", body).await;
+    }
+
+    /// Sends body to endpoint, warning only about the first failure to avoid flooding output.
+    ///
+    /// Nothing is sent anymore after a failure, so an unreachable collector does not delay the end of the run.
+    async fn send(&self, signal: &str, body: Value) {
+        if self.failed.load(Ordering::Relaxed) {
+            return;
+        }
+        let result = timeout(REQUEST_TIMEOUT, self.post(signal, body.to_string()))
+            .await
+            .unwrap_or_else(|_| {
+                Err(format!(
+                    "no response within {} seconds",
+                    REQUEST_TIMEOUT.as_secs()
+                ))
+            });
+        if let Err(error) = result {
+            if !self.failed.swap(true, Ordering::Relaxed) {
+                eprintln!(
+                    "{}: OTLP export to {} failed: {error}",
+                    "warning".bold().yellow(),
+                    self.endpoint.authority()
+                );
+            }
+        }
+    }
+
+    async fn post(&self, signal: &str, body: String) -> Result<(), String> {
+        let mut stream = TcpStream::connect((self.endpoint.host.as_str(), self.endpoint.port))
+            .await
+            .map_err(|err| err.to_string())?;
+        let request = format!(
+            "POST {}{signal} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.endpoint.path,
+            self.endpoint.authority(),
+            body.len()
+        );
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let mut response = Vec::new();
+        stream
+            .read_to_end(&mut response)
+            .await
+            .map_err(|err| err.to_string())?;
+        let response = String::from_utf8_lossy(&response);
+        let status = response.lines().next().unwrap_or_default();
+        match status.split_whitespace().nth(1) {
+            Some(code) if code.starts_with('2') => Ok(()),
+            _ => Err(format!("collector responded '{status}'")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Span {
+    id: u64,
+    /// Parent span, zero if none.
+    parent: u64,
+    name: String,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+    attributes: Vec<Value>,
+    error: Option<String>,
+}
+
+impl Span {
+    fn new(id: u64, parent: u64, name: String, start: DateTime<Utc>) -> Self {
+        Self {
+            id,
+            parent,
+            name,
+            start,
+            end: None,
+            attributes: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn attribute(&mut self, key: &str, value: Value) {
+        self.attributes.push(attribute(key, value));
+    }
+
+    fn to_json(&self, trace_id: &str) -> Value {
+        let mut span = json!({
+            "traceId": trace_id,
+            "spanId": span_id_hex(self.id),
+            "name": self.name,
+            // SPAN_KIND_INTERNAL
+            "kind": 1,
+            "startTimeUnixNano": nanos(&self.start),
+            "endTimeUnixNano": nanos(self.end.as_ref().unwrap_or(&self.start)),
+            "attributes": self.attributes,
+            "status": match &self.error {
+                // STATUS_CODE_ERROR
+                Some(message) => json!({ "code": 2, "message": message }),
+                None => json!({}),
+            },
+        });
+        if self.parent != 0 {
+            span["parentSpanId"] = json!(span_id_hex(self.parent));
+        }
+        span
+    }
+}
+
+fn attribute(key: &str, value: Value) -> Value {
+    json!({ "key": key, "value": value })
+}
+
+fn string(value: &str) -> Value {
+    json!({ "stringValue": value })
+}
+
+fn int(value: u64) -> Value {
+    // 64 bits integers are given as strings in OTLP JSON encoding.
+    json!({ "intValue": value.to_string() })
+}
+
+fn span_id_hex(id: u64) -> String {
+    format!("{id:016x}")
+}
+
+fn nanos(timestamp: &DateTime<Utc>) -> String {
+    timestamp
+        .timestamp_nanos_opt()
+        .unwrap_or_default()
+        .to_string()
+}
@@ -0,0 +1,341 @@
+//! Discovery and running of test treatments, as done by `melodium test`.
+//!
+//! Test treatments are treatments annotated with `#[test]` or `#[test(timeout = <seconds>)]`.
+//! Each of them is run as entrypoint of its own `melodium run` process, so a test that hangs or
+//! crashes cannot affect the other ones.
+//!
+//! A test can also be annotated with `#[overrides(<overridden> = <overriding>, …)]`, giving full identifiers
+//! of models or treatments to build in place of other ones while running it.
+
+use core::convert::TryFrom;
+use melodium_common::descriptor::{Attribuable, Collection, Entry, Identifier};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Attribute marking a treatment as test.
+pub const TEST_ATTRIBUTE: &str = "test";
+/// Attribute giving overrides to use when running test.
+pub const OVERRIDES_ATTRIBUTE: &str = "overrides";
+
+/// Test treatment found in package.
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub identifier: Identifier,
+    /// Timeout set through `#[test(timeout = <seconds>)]`, if any.
+    pub timeout: Option<Duration>,
+    /// Overrides set through `#[overrides(…)]`.
+    pub overrides: Vec<(Identifier, Identifier)>,
+}
+
+/// Outcome of a test run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestOutcome {
+    Passed,
+    /// Test failed, with messages describing why.
+    Failed(Vec<String>),
+    /// Test did not finish within allowed time.
+    TimedOut(Duration),
+}
+
+/// Report of a test run.
+#[derive(Debug, Clone)]
+pub struct TestReport {
+    pub case: TestCase,
+    pub outcome: TestOutcome,
+    pub duration: Duration,
+    /// Everything the test process wrote on its standard and error outputs.
+    pub output: String,
+}
+
+impl TestReport {
+    pub fn passed(&self) -> bool {
+        self.outcome == TestOutcome::Passed
+    }
+}
+
+/// Finds test treatments belonging to `package`, sorted by identifier.
+///
+/// If `filter` is given, only tests whose identifier contains it are kept.
+pub fn discover(
+    collection: &Collection,
+    package: &str,
+    filter: Option<&str>,
+) -> Result<Vec<TestCase>, String> {
+    let mut cases = Vec::new();
+    for identifier in collection.identifiers() {
+        if identifier.root() != package
+            || filter
+                .map(|filter| !identifier.to_string().contains(filter))
+                .unwrap_or(false)
+        {
+            continue;
+        }
+
+        if let Some(entry @ Entry::Treatment(_)) = collection.get(&(&identifier).into()) {
+            if let Some(attribute) = entry.attributes().get(TEST_ATTRIBUTE) {
+                cases.push(TestCase {
+                    timeout: timeout(attribute)
+                        .map_err(|err| format!("test '{identifier}': {err}"))?,
+                    overrides: entry
+                        .attributes()
+                        .get(OVERRIDES_ATTRIBUTE)
+                        .map(|attribute| overrides(attribute))
+                        .transpose()
+                        .map_err(|err| format!("test '{identifier}': {err}"))?
+                        .unwrap_or_default(),
+                    identifier,
+                });
+            }
+        }
+    }
+    cases.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+    Ok(cases)
+}
+
+/// Parses test attribute content, being either empty or `timeout = <seconds>`.
+fn timeout(attribute: &str) -> Result<Option<Duration>, String> {
+    let attribute = attribute.trim();
+    if attribute.is_empty() {
+        return Ok(None);
+    }
+
+    match attribute.split_once('=') {
+        Some((key, value)) if key.trim() == "timeout" => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+            .map(Some)
+            .ok_or_else(|| format!("'{}' is not a valid timeout in seconds", value.trim())),
+        _ => Err(format!("unknown test attribute '{attribute}'")),
+    }
+}
+
+/// Parses overrides attribute content, being `<overridden> = <overriding>` pairs separated by commas.
+fn overrides(attribute: &str) -> Result<Vec<(Identifier, Identifier)>, String> {
+    attribute
+        .split(',')
+        .filter(|r#override| !r#override.trim().is_empty())
+        .map(|r#override| {
+            match r#override.split_once('=').map(|(overridden, overriding)| {
+                (
+                    Identifier::try_from(overridden.trim()),
+                    Identifier::try_from(overriding.trim()),
+                )
+            }) {
+                Some((Ok(overridden), Ok(overriding))) => Ok((overridden, overriding)),
+                _ => Err(format!("'{}' is not a valid override", r#override.trim())),
+            }
+        })
+        .collect()
+}
+
+/// Runs the test case within its own process, killing it if it exceeds `timeout`.
+///
+/// `file` and `paths` are the ones the package was loaded with.
+pub fn run(
+    case: &TestCase,
+    file: &PathBuf,
+    paths: &[String],
+    timeout: Duration,
+) -> std::io::Result<TestReport> {
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .arg("run")
+        .arg("--force-entry")
+        .arg(case.identifier.to_string());
+    for (overridden, overriding) in &case.overrides {
+        command
+            .arg("--override")
+            .arg(format!("{overridden}={overriding}"));
+    }
+    for path in paths {
+        command.arg("--path").arg(path);
+    }
+    command
+        .arg(file)
+        .env_remove("CLICOLOR_FORCE")
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let start = Instant::now();
+    let mut child = command.spawn()?;
+    let stdout = capture(child.stdout.take());
+    let stderr = capture(child.stderr.take());
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        } else if start.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let duration = start.elapsed();
+
+    let output = format!(
+        "{}{}",
+        stdout.join().unwrap_or_default(),
+        stderr.join().unwrap_or_default()
+    );
+
+    let outcome = match status {
+        Some(status) if status.success() => TestOutcome::Passed,
+        Some(status) => {
+            let mut messages = failure_messages(&output);
+            if messages.is_empty() {
+                messages.push(match status.code() {
+                    Some(code) => format!("exited with code {code}"),
+                    None => "terminated by signal".to_string(),
+                });
+            }
+            TestOutcome::Failed(messages)
+        }
+        None => TestOutcome::TimedOut(timeout),
+    };
+
+    Ok(TestReport {
+        case: case.clone(),
+        outcome,
+        duration,
+        output,
+    })
+}
+
+fn capture<R: Read + Send + 'static>(reader: Option<R>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut content = Vec::new();
+        if let Some(mut reader) = reader {
+            let _ = reader.read_to_end(&mut content);
+        }
+        String::from_utf8_lossy(&content).to_string()
+    })
+}
+
+/// Gives error and failure messages written by the test process.
+fn failure_messages(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = match line.strip_prefix('[') {
+                Some(rest) => rest.split_once("] ").map(|(_, line)| line).unwrap_or(line),
+                None => line,
+            };
+            line.strip_prefix("error: ")
+                .or_else(|| line.strip_prefix("failure: "))
+                .map(|message| message.to_string())
+        })
+        .collect()
+}
+
+/// Renders reports using the Test Anything Protocol, version 13.
+pub fn tap(reports: &[TestReport]) -> String {
+    let mut tap = format!("TAP version 13\n1..{}\n", reports.len());
+    for (number, report) in reports.iter().enumerate() {
+        let number = number + 1;
+        let identifier = &report.case.identifier;
+        match &report.outcome {
+            TestOutcome::Passed => tap.push_str(&format!("ok {number} - {identifier}\n")),
+            outcome => {
+                tap.push_str(&format!("not ok {number} - {identifier}\n  ---\n"));
+                tap.push_str(&format!(
+                    "  message: {}\n",
+                    yaml_string(&outcome_message(outcome))
+                ));
+                tap.push_str(&format!(
+                    "  duration_ms: {}\n  ...\n",
+                    report.duration.as_millis()
+                ));
+            }
+        }
+    }
+    tap
+}
+
+/// Renders reports as JUnit XML document, tests being grouped by script.
+pub fn junit(package: &str, reports: &[TestReport]) -> String {
+    let failures = reports.iter().filter(|report| !report.passed()).count();
+    let time: f64 = reports
+        .iter()
+        .map(|report| report.duration.as_secs_f64())
+        .sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{failures}\" errors=\"0\" time=\"{time:.3}\">\n",
+        xml_escape(package),
+        reports.len(),
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" errors=\"0\" time=\"{time:.3}\">\n",
+        xml_escape(package),
+        reports.len(),
+    ));
+    for report in reports {
+        let identifier = &report.case.identifier;
+        xml.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+            xml_escape(identifier.name()),
+            xml_escape(&identifier.path().join("/")),
+            report.duration.as_secs_f64()
+        ));
+        match &report.outcome {
+            TestOutcome::Passed => xml.push_str("/>\n"),
+            outcome => {
+                xml.push_str(">\n");
+                xml.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"{}\"/>\n",
+                    xml_escape(&outcome_message(outcome)),
+                    match outcome {
+                        TestOutcome::TimedOut(_) => "timeout",
+                        _ => "failure",
+                    }
+                ));
+                xml.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    xml_escape(&report.output)
+                ));
+                xml.push_str("    </testcase>\n");
+            }
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// Gives a single-line description of the outcome.
+pub fn outcome_message(outcome: &TestOutcome) -> String {
+    match outcome {
+        TestOutcome::Passed => "passed".to_string(),
+        TestOutcome::Failed(messages) => messages.join("; "),
+        TestOutcome::TimedOut(timeout) => {
+            format!("timed out after {:.3}s", timeout.as_secs_f64())
+        }
+    }
+}
+
+fn yaml_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn xml_escape(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&apos;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
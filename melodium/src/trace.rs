@@ -0,0 +1,477 @@
+//! Reading of debug traces written by `melodium run --debug`, as done by `melodium trace`.
+//!
+//! A [`Trace`] is made from the events of a run, and can be summarised as text through [`Trace::summary`]
+//! or rendered as timeline using Chrome trace-event format through [`Trace::chrome`], that can be opened
+//! within Perfetto or `chrome://tracing`.
+
+use core::fmt::Write;
+use melodium_share::{
+    DataContent, Event, EventKind, HostTreatment, Identifier, ResultStatus, TrackCreation,
+    TrackResult, TransmissionDetails,
+};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+
+/// Reads events from content of debug file.
+///
+/// Files of runs still in progress or interrupted are accepted, their closing bracket being missing.
+pub fn read(content: &str) -> Result<Vec<Event>, String> {
+    let content = content.trim();
+    serde_json::from_str(content).or_else(|error| {
+        serde_json::from_str(&format!("{}]", content.trim_end_matches(',')))
+            .map_err(|_| error.to_string())
+    })
+}
+
+/// Track, as seen within trace.
+///
+/// All times are given in microseconds since trace start.
+#[derive(Debug, Clone)]
+pub struct Track {
+    pub id: u64,
+    pub parent_id: Option<u64>,
+    /// What track was created from, as `<source> of <model>`, or `direct`.
+    pub origin: String,
+    pub start: i64,
+    /// End of track, `None` if track did not finish within trace.
+    pub end: Option<i64>,
+    pub result: Option<TrackResult>,
+    pub children: Vec<u64>,
+}
+
+/// Execution of treatment within a track.
+///
+/// All times are given in microseconds since trace start.
+#[derive(Debug, Clone)]
+pub struct Execution {
+    pub treatment: Identifier,
+    pub label: String,
+    pub track_id: u64,
+    pub start: i64,
+    /// End of execution, `None` if treatment did not finish within trace.
+    pub end: Option<i64>,
+}
+
+/// End of a connection, as `label.name` within host treatment.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Endpoint {
+    pub host: String,
+    pub label: String,
+    pub name: String,
+}
+
+impl From<&TransmissionDetails> for Endpoint {
+    fn from(details: &TransmissionDetails) -> Self {
+        Self {
+            host: match &details.host_treatment {
+                HostTreatment::Treatment(identifier) => identifier.to_string(),
+                HostTreatment::Direct => "direct".to_string(),
+            },
+            label: details.label.clone(),
+            name: details.name.clone(),
+        }
+    }
+}
+
+/// Volume transmitted through a connection.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Volume {
+    pub transmissions: u64,
+    /// Values transmitted, undetailed transmissions not being counted.
+    pub values: u64,
+}
+
+/// Failure reported during run.
+#[derive(Debug, Clone)]
+pub struct Failure {
+    pub track_id: Option<u64>,
+    pub time: i64,
+    pub message: String,
+}
+
+/// Analysis of a run, made from its debug events.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    /// Duration covered by trace, in microseconds.
+    pub duration: i64,
+    pub tracks: BTreeMap<u64, Track>,
+    pub executions: Vec<Execution>,
+    pub connections: BTreeMap<(Endpoint, Endpoint), Volume>,
+    pub failures: Vec<Failure>,
+}
+
+impl Trace {
+    pub fn new(events: &[Event]) -> Self {
+        let mut trace = Self::default();
+        let origin = match events.iter().map(|event| event.timestamp).min() {
+            Some(origin) => origin,
+            None => return trace,
+        };
+
+        let mut models = HashMap::new();
+        let mut running = BTreeMap::new();
+        for event in events {
+            let time = (event.timestamp - origin)
+                .num_microseconds()
+                .unwrap_or(i64::MAX);
+            trace.duration = trace.duration.max(time);
+
+            match &event.kind {
+                EventKind::ModelAdded { model_id, model } => {
+                    models.insert(*model_id, model.clone());
+                }
+                EventKind::TrackAdded { info, creation } => {
+                    let origin = match creation {
+                        TrackCreation::Direct => "direct".to_string(),
+                        TrackCreation::Source {
+                            source, model_id, ..
+                        } => match models.get(model_id) {
+                            Some(model) => format!("{source} of {model}"),
+                            None => source.clone(),
+                        },
+                    };
+                    trace.tracks.insert(
+                        info.id,
+                        Track {
+                            id: info.id,
+                            parent_id: info.parent_id,
+                            origin,
+                            start: time,
+                            end: None,
+                            result: None,
+                            children: Vec::new(),
+                        },
+                    );
+                }
+                EventKind::TrackFinished { info } => {
+                    if let Some(track) = trace.tracks.get_mut(&info.id) {
+                        track.end = Some(time);
+                        track.result = info.results.clone();
+                    }
+                }
+                EventKind::Failure { track_id, status } => {
+                    let message = match status {
+                        ResultStatus::Ok => continue,
+                        ResultStatus::Error(message) => message.clone(),
+                        ResultStatus::Fatal(message) => format!("fatal: {message}"),
+                    };
+                    trace.failures.push(Failure {
+                        track_id: *track_id,
+                        time,
+                        message,
+                    });
+                }
+                EventKind::TreatmentStarted {
+                    treatment,
+                    build_id,
+                    track_id,
+                    label,
+                    ..
+                } => {
+                    running.insert(
+                        (*track_id, treatment.clone(), *build_id),
+                        trace.executions.len(),
+                    );
+                    trace.executions.push(Execution {
+                        treatment: treatment.clone(),
+                        label: label.clone(),
+                        track_id: *track_id,
+                        start: time,
+                        end: None,
+                    });
+                }
+                EventKind::TreatmentFinished {
+                    treatment,
+                    build_id,
+                    track_id,
+                    ..
+                } => {
+                    if let Some(index) = running.remove(&(*track_id, treatment.clone(), *build_id))
+                    {
+                        trace.executions[index].end = Some(time);
+                    }
+                }
+                EventKind::DataTransmitted {
+                    output,
+                    input,
+                    data,
+                    ..
+                } => {
+                    let volume = trace
+                        .connections
+                        .entry((output.into(), input.into()))
+                        .or_default();
+                    volume.transmissions += 1;
+                    volume.values += match data {
+                        DataContent::Undetailed => 0,
+                        DataContent::Count { count } => *count,
+                        DataContent::Values { values } => values.len() as u64,
+                    };
+                }
+                _ => {}
+            }
+        }
+
+        let links = trace
+            .tracks
+            .values()
+            .filter_map(|track| track.parent_id.map(|parent_id| (parent_id, track.id)))
+            .collect::<Vec<_>>();
+        for (parent_id, id) in links {
+            if let Some(parent) = trace.tracks.get_mut(&parent_id) {
+                parent.children.push(id);
+            }
+        }
+
+        trace
+    }
+
+    /// Gives time elapsed from `start` to `end`, or to the end of trace if `end` is `None`.
+    pub fn elapsed(&self, start: i64, end: Option<i64>) -> i64 {
+        end.unwrap_or(self.duration) - start
+    }
+
+    /// Summarises run as text, listing at most `top` entries within each section.
+    pub fn summary(&self, top: usize) -> String {
+        let mut text = String::new();
+        let _ = writeln!(
+            text,
+            "Run of {}: {}, {}, {}",
+            duration(self.duration),
+            plural(self.tracks.len(), "track"),
+            plural(self.executions.len(), "treatment execution"),
+            plural(self.failures.len(), "failure"),
+        );
+
+        let mut treatments: BTreeMap<&Identifier, (usize, i64, i64)> = BTreeMap::new();
+        for execution in &self.executions {
+            let elapsed = self.elapsed(execution.start, execution.end);
+            let (count, total, max) = treatments.entry(&execution.treatment).or_default();
+            *count += 1;
+            *total += elapsed;
+            *max = (*max).max(elapsed);
+        }
+        let mut treatments = treatments.into_iter().collect::<Vec<_>>();
+        treatments.sort_by(|(_, (_, a, _)), (_, (_, b, _))| b.cmp(a));
+        if !treatments.is_empty() {
+            let _ = writeln!(text, "\nTreatments:");
+            let _ = writeln!(
+                text,
+                "  {:>8}  {:>10}  {:>10}  {:>10}  treatment",
+                "count", "total", "mean", "max"
+            );
+            for (treatment, (count, total, max)) in treatments.iter().take(top) {
+                let _ = writeln!(
+                    text,
+                    "  {count:>8}  {:>10}  {:>10}  {:>10}  {treatment}",
+                    duration(*total),
+                    duration(total / *count as i64),
+                    duration(*max),
+                );
+            }
+            more(&mut text, "  ", treatments.len(), top, "treatment");
+        }
+
+        let mut slowest = self.executions.iter().collect::<Vec<_>>();
+        slowest.sort_by_key(|execution| -self.elapsed(execution.start, execution.end));
+        if !slowest.is_empty() {
+            let _ = writeln!(text, "\nSlowest treatments:");
+            let _ = writeln!(text, "  {:>10}  {:>8}  treatment", "duration", "track");
+            for execution in slowest.iter().take(top) {
+                let _ = writeln!(
+                    text,
+                    "  {:>10}  {:>8}  {} ({}){}",
+                    duration(self.elapsed(execution.start, execution.end)),
+                    execution.track_id,
+                    execution.label,
+                    execution.treatment,
+                    if execution.end.is_none() {
+                        ", unfinished"
+                    } else {
+                        ""
+                    }
+                );
+            }
+        }
+
+        let roots = self
+            .tracks
+            .values()
+            .filter(|track| {
+                track
+                    .parent_id
+                    .map(|parent_id| !self.tracks.contains_key(&parent_id))
+                    .unwrap_or(true)
+            })
+            .map(|track| track.id)
+            .collect::<Vec<_>>();
+        if !roots.is_empty() {
+            let _ = writeln!(text, "\nTracks:");
+            self.track_tree(&mut text, &roots, 1, top);
+        }
+
+        let mut connections = self.connections.iter().collect::<Vec<_>>();
+        connections.sort_by(|(_, a), (_, b)| {
+            b.values
+                .cmp(&a.values)
+                .then(b.transmissions.cmp(&a.transmissions))
+        });
+        if !connections.is_empty() {
+            let _ = writeln!(text, "\nConnections:");
+            let _ = writeln!(
+                text,
+                "  {:>10}  {:>13}  connection",
+                "values", "transmissions"
+            );
+            for ((output, input), volume) in connections.iter().take(top) {
+                let connection = if output.host == input.host {
+                    format!(
+                        "{}.{} -> {}.{} in {}",
+                        output.label, output.name, input.label, input.name, output.host
+                    )
+                } else {
+                    format!(
+                        "{}.{} in {} -> {}.{} in {}",
+                        output.label, output.name, output.host, input.label, input.name, input.host
+                    )
+                };
+                let _ = writeln!(
+                    text,
+                    "  {:>10}  {:>13}  {connection}",
+                    volume.values, volume.transmissions
+                );
+            }
+            more(&mut text, "  ", connections.len(), top, "connection");
+        }
+
+        if !self.failures.is_empty() {
+            let _ = writeln!(text, "\nFailures:");
+            for failure in self.failures.iter().take(top) {
+                let _ = match failure.track_id {
+                    Some(track_id) => writeln!(
+                        text,
+                        "  at {}, track {track_id}: {}",
+                        duration(failure.time),
+                        failure.message
+                    ),
+                    None => writeln!(text, "  at {}: {}", duration(failure.time), failure.message),
+                };
+            }
+            more(&mut text, "  ", self.failures.len(), top, "failure");
+        }
+
+        text
+    }
+
+    /// Renders run as Chrome trace-event JSON, each track being shown as thread running its treatments.
+    pub fn chrome(&self) -> String {
+        let mut events = vec![json!({
+            "name": "process_name",
+            "ph": "M",
+            "pid": 1,
+            "args": { "name": "melodium" },
+        })];
+
+        for track in self.tracks.values() {
+            events.push(json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": track.id,
+                "args": { "name": format!("track {} ({})", track.id, track.origin) },
+            }));
+            events.push(json!({
+                "name": format!("track {}", track.id),
+                "cat": "track",
+                "ph": "X",
+                "ts": track.start,
+                "dur": self.elapsed(track.start, track.end),
+                "pid": 1,
+                "tid": track.id,
+                "args": {
+                    "origin": track.origin,
+                    "parent": track.parent_id,
+                    "result": result(track),
+                },
+            }));
+        }
+
+        for execution in &self.executions {
+            events.push(json!({
+                "name": execution.label,
+                "cat": "treatment",
+                "ph": "X",
+                "ts": execution.start,
+                "dur": self.elapsed(execution.start, execution.end),
+                "pid": 1,
+                "tid": execution.track_id,
+                "args": {
+                    "treatment": execution.treatment.to_string(),
+                    "finished": execution.end.is_some(),
+                },
+            }));
+        }
+
+        for failure in &self.failures {
+            events.push(json!({
+                "name": "failure",
+                "cat": "failure",
+                "ph": "i",
+                "s": if failure.track_id.is_some() { "t" } else { "p" },
+                "ts": failure.time,
+                "pid": 1,
+                "tid": failure.track_id.unwrap_or_default(),
+                "args": { "message": failure.message },
+            }));
+        }
+
+        serde_json::to_string(&json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        }))
+        .unwrap_or_default()
+    }
+
+    fn track_tree(&self, text: &mut String, ids: &[u64], depth: usize, top: usize) {
+        let indent = "  ".repeat(depth);
+        for track in ids.iter().take(top).filter_map(|id| self.tracks.get(id)) {
+            let _ = writeln!(
+                text,
+                "{indent}#{} {}, {}, {}",
+                track.id,
+                track.origin,
+                duration(self.elapsed(track.start, track.end)),
+                result(track)
+            );
+            self.track_tree(text, &track.children, depth + 1, top);
+        }
+        more(text, &indent, ids.len(), top, "track");
+    }
+}
+
+fn result(track: &Track) -> &'static str {
+    match (&track.result, track.end) {
+        (Some(TrackResult::AllOk), _) => "ok",
+        (Some(TrackResult::NotAllOk), _) => "failed",
+        (None, Some(_)) => "finished",
+        (None, None) => "unfinished",
+    }
+}
+
+fn more(text: &mut String, indent: &str, count: usize, top: usize, name: &str) {
+    if count > top {
+        let _ = writeln!(text, "{indent}… {} more", plural(count - top, name));
+    }
+}
+
+fn plural(count: usize, name: &str) -> String {
+    format!("{count} {name}{}", if count == 1 { "" } else { "s" })
+}
+
+fn duration(micros: i64) -> String {
+    match micros {
+        micros if micros < 1_000 => format!("{micros}µs"),
+        micros if micros < 1_000_000 => format!("{:.3}ms", micros as f64 / 1e3),
+        micros => format!("{:.3}s", micros as f64 / 1e6),
+    }
+}
@@ -0,0 +1,36 @@
+use std::process::{exit, Command};
+
+const FILENAME: &str = "declarations.txt";
+const EXPECTED_CONTENT: &str = "Point { x: 3, level: High }";
+
+fn main() {
+    let mut melodium = Command::new("melodium")
+        .arg("run")
+        .arg("declarations.mel")
+        .spawn()
+        .expect("failed to launch Mélodium executable");
+
+    match melodium.wait() {
+        Ok(status) if status.success() => match std::fs::read_to_string(FILENAME) {
+            Ok(contents) => {
+                if contents != EXPECTED_CONTENT {
+                    eprintln!("Invalid result content: {contents}");
+                    exit(1);
+                }
+            }
+            Err(err) => {
+                eprintln!("Error reading file: {err}");
+                exit(1);
+            }
+        },
+        Ok(status) => {
+            exit(status.code().unwrap_or(1));
+        }
+        Err(err) => {
+            eprintln!("Execution error: {err}");
+            exit(1);
+        }
+    }
+
+    let _ = std::fs::remove_file(FILENAME);
+}
@@ -0,0 +1,75 @@
+use std::process::{exit, Command};
+
+const FILENAME: &str = "model_override.txt";
+const EXPECTED_CONTENT: &str = "recorded response";
+const INCOMPATIBLE_CODE: i32 = 4;
+const INCOMPATIBLE_ERROR: &str = "D0252";
+
+fn main() {
+    // Stand-ins replay recorded response
+    let mut melodium = Command::new("melodium")
+        .arg("run")
+        .arg("--override")
+        .arg("http/client::HttpClient=model_override::ReplayClient")
+        .arg("--override")
+        .arg("http/client::get=model_override::replay")
+        .arg("model_override.mel")
+        .arg("--file")
+        .arg(FILENAME)
+        .spawn()
+        .expect("failed to launch Mélodium executable");
+
+    match melodium.wait() {
+        Ok(status) if status.success() => match std::fs::read_to_string(FILENAME) {
+            Ok(contents) => {
+                if contents != EXPECTED_CONTENT {
+                    eprintln!("Invalid result content: {contents}");
+                    exit(1);
+                }
+            }
+            Err(err) => {
+                eprintln!("Error reading file: {err}");
+                exit(1);
+            }
+        },
+        Ok(status) => {
+            exit(status.code().unwrap_or(1));
+        }
+        Err(err) => {
+            eprintln!("Execution error: {err}");
+            exit(1);
+        }
+    }
+
+    let _ = std::fs::remove_file(FILENAME);
+
+    // Model not based on the overridden one is rejected
+    let melodium = Command::new("melodium")
+        .arg("run")
+        .arg("--override")
+        .arg("http/client::HttpClient=http/server::HttpServer")
+        .arg("model_override.mel")
+        .arg("--file")
+        .arg(FILENAME)
+        .output()
+        .expect("failed to launch Mélodium executable");
+
+    match melodium.status.code() {
+        Some(INCOMPATIBLE_CODE) => {
+            let output = String::from_utf8_lossy(&melodium.stdout).to_string()
+                + &String::from_utf8_lossy(&melodium.stderr);
+            if !output.contains(INCOMPATIBLE_ERROR) {
+                eprintln!("Incompatible override not reported as {INCOMPATIBLE_ERROR}");
+                exit(1);
+            }
+        }
+        Some(code) => {
+            eprintln!("Run exited with code {code} instead of {INCOMPATIBLE_CODE}");
+            exit(1);
+        }
+        None => {
+            eprintln!("Run terminated by signal");
+            exit(1);
+        }
+    }
+}
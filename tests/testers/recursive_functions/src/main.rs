@@ -0,0 +1,31 @@
+use std::process::{exit, Command};
+
+const EXPECTED_CODE: i32 = 3;
+const EXPECTED_ERROR: &str = "D0245";
+
+fn main() {
+    let melodium = Command::new("melodium")
+        .arg("check")
+        .arg("recursive_functions.mel")
+        .output()
+        .expect("failed to launch Mélodium executable");
+
+    match melodium.status.code() {
+        Some(EXPECTED_CODE) => {
+            let output = String::from_utf8_lossy(&melodium.stdout).to_string()
+                + &String::from_utf8_lossy(&melodium.stderr);
+            if !output.contains(EXPECTED_ERROR) {
+                eprintln!("Recursion not reported as {EXPECTED_ERROR}");
+                exit(1);
+            }
+        }
+        Some(code) => {
+            eprintln!("Check exited with code {code} instead of {EXPECTED_CODE}");
+            exit(1);
+        }
+        None => {
+            eprintln!("Check terminated by signal");
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::process::{exit, Command};
+
+const EXPECTED_CODE: i32 = 3;
+const EXPECTED_ERRORS: &[&str] = &["S0233", "S0217", "S0002"];
+
+fn main() {
+    let melodium = Command::new("melodium")
+        .arg("check")
+        .arg("syntax_errors.mel")
+        .output()
+        .expect("failed to launch Mélodium executable");
+
+    match melodium.status.code() {
+        Some(EXPECTED_CODE) => {
+            let output = String::from_utf8_lossy(&melodium.stdout).to_string()
+                + &String::from_utf8_lossy(&melodium.stderr);
+            for error in EXPECTED_ERRORS {
+                if !output.contains(error) {
+                    eprintln!("Syntax error {error} not reported");
+                    exit(1);
+                }
+            }
+        }
+        Some(code) => {
+            eprintln!("Check exited with code {code} instead of {EXPECTED_CODE}");
+            exit(1);
+        }
+        None => {
+            eprintln!("Check terminated by signal");
+            exit(1);
+        }
+    }
+}